@@ -0,0 +1,103 @@
+//! Mesh rendering benchmarks on a million tile board, run with `cargo bench -p board_plugin`.
+//! Each case reports the median time over a few runs on a seeded board.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::{Entity, Vec2};
+use bevy::utils::HashMap;
+use board_plugin::{
+    bounds::Bounds,
+    components::{Coordinates, TileChunk},
+    resources::{Board, TileMap},
+    systems::chunk_mesh::{chunk_mesh, chunk_uvs},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const SAMPLES: usize = 15;
+const SIZE: u16 = 1000;
+
+/// Seeded board with every tile covered, mapped to its chunk as `create_board` does
+fn bench_board() -> (Board, Vec<TileChunk>) {
+    let mut tile_map = TileMap::empty((SIZE.into(), SIZE.into()));
+    tile_map.set_bombs_with_rng(u16::MAX, &mut StdRng::seed_from_u64(0));
+    let mut chunks = Vec::new();
+    let mut covered_tiles = HashMap::default();
+    for y0 in (0..SIZE).step_by(TileChunk::SIZE.into()) {
+        for x0 in (0..SIZE).step_by(TileChunk::SIZE.into()) {
+            let chunk = TileChunk {
+                origin: Coordinates::new(x0, y0),
+                width: TileChunk::SIZE.min(SIZE - x0),
+                height: TileChunk::SIZE.min(SIZE - y0),
+            };
+            let entity = Entity::from_raw(chunks.len() as u32);
+            for y in y0..y0 + chunk.height {
+                for x in x0..x0 + chunk.width {
+                    covered_tiles.insert(Coordinates::new(x, y), entity);
+                }
+            }
+            chunks.push(chunk);
+        }
+    }
+    let size = f32::from(SIZE);
+    let board = Board::new(
+        Entity::from_raw(u32::MAX),
+        tile_map,
+        covered_tiles,
+        Bounds::new(Vec2::ZERO, Vec2::splat(size)),
+        1.0,
+    );
+    (board, chunks)
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+fn report(name: &str, chunks: usize, time: Duration) {
+    println!(
+        "{:<36} {:>5} chunks {:>10.3} ms",
+        name,
+        chunks,
+        time.as_secs_f64() * 1e3
+    );
+}
+
+fn bench<F: FnMut()>(name: &str, chunks: usize, mut f: F) {
+    let samples = (0..SAMPLES)
+        .map(|_| {
+            let now = Instant::now();
+            f();
+            now.elapsed()
+        })
+        .collect();
+    report(name, chunks, median(samples));
+}
+
+fn main() {
+    let (mut board, chunks) = bench_board();
+    let name = format!("{}x{}", SIZE, SIZE);
+
+    // Spawning the board builds every mesh once
+    bench(&format!("chunk_mesh all {}", name), chunks.len(), || {
+        for chunk in &chunks {
+            black_box(chunk_mesh(&board, chunk, 0.05));
+        }
+    });
+    // Worst case frame, every chunk touched by a reveal
+    bench(&format!("chunk_uvs all {}", name), chunks.len(), || {
+        for chunk in &chunks {
+            black_box(chunk_uvs(&board, chunk));
+        }
+    });
+
+    // Typical frame, a reveal or a flag touching a single chunk
+    let start = board.tile_map.first_empty_tile().unwrap();
+    let uncovered = board.uncover([start]);
+    let chunk = chunks.iter().find(|c| c.contains(start)).unwrap();
+    println!("{} tiles uncovered", uncovered.len());
+    bench(&format!("chunk_uvs single {}", name), 1, || {
+        black_box(chunk_uvs(&board, chunk));
+    });
+}
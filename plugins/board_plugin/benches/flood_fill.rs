@@ -0,0 +1,123 @@
+//! Flood fill benchmarks on large boards, run with `cargo bench -p board_plugin`.
+//! Each case reports the median time of single pass reveals over a few seeded boards.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::{Entity, Vec2};
+use bevy::utils::HashMap;
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    resources::{Board, Tile, TileMap},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const SAMPLES: usize = 15;
+
+/// Seeded map of the given size and bomb density, with the empty tile opening the largest area
+fn bench_map(seed: u64, size: usize, density: f64) -> (TileMap, Coordinates) {
+    let mut tile_map = TileMap::empty((size, size));
+    // Bomb counts are stored on 16 bits, very large dense boards are capped
+    let bomb_count = ((size * size) as f64 * density).min(f64::from(u16::MAX)) as u16;
+    tile_map.set_bombs_with_rng(bomb_count, &mut StdRng::seed_from_u64(seed));
+    let centre = Coordinates::new((size / 2) as u16, (size / 2) as u16);
+    let start = tile_map
+        .map()
+        .indexed_iter()
+        .filter(|(_, tile)| **tile == Tile::Empty)
+        .map(|((x, y), _)| Coordinates::new(x as u16, y as u16))
+        .min_by_key(|c| u32::from(c.x.abs_diff(centre.x)) + u32::from(c.y.abs_diff(centre.y)))
+        .unwrap_or(centre);
+    (tile_map, start)
+}
+
+fn bench_board(tile_map: TileMap) -> Board {
+    let (width, height) = (tile_map.width(), tile_map.height());
+    let mut covered_tiles = HashMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            covered_tiles.insert(
+                Coordinates::new(x as u16, y as u16),
+                Entity::from_raw((x + y * width) as u32),
+            );
+        }
+    }
+    Board::new(
+        Entity::from_raw(u32::MAX),
+        tile_map,
+        covered_tiles,
+        Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32)),
+        1.0,
+    )
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+fn report(name: &str, revealed: usize, time: Duration) {
+    println!(
+        "{:<36} {:>9} tiles {:>10.3} ms {:>8.1} Mtiles/s",
+        name,
+        revealed,
+        time.as_secs_f64() * 1e3,
+        revealed as f64 / time.as_secs_f64() / 1e6
+    );
+}
+
+/// Flood fill over the tile map alone
+fn tile_map_flood_fill(size: usize, density: f64) {
+    let (tile_map, start) = bench_map(size as u64, size, density);
+    let mut revealed = 0;
+    let samples = (0..SAMPLES)
+        .map(|_| {
+            let now = Instant::now();
+            revealed = black_box(tile_map.flood_fill([start], |_| true)).len();
+            now.elapsed()
+        })
+        .collect();
+    report(
+        &format!(
+            "TileMap::flood_fill {}x{} {:.0}%",
+            size,
+            size,
+            density * 100.0
+        ),
+        revealed,
+        median(samples),
+    );
+}
+
+/// Flood fill and covered tile bookkeeping, as done by `uncover_tiles`
+fn board_uncover(size: usize, density: f64) {
+    let (tile_map, start) = bench_map(size as u64, size, density);
+    let mut revealed = 0;
+    let samples = (0..SAMPLES)
+        .map(|_| {
+            let mut board = bench_board(tile_map.clone());
+            let now = Instant::now();
+            revealed = black_box(board.uncover([start])).len();
+            now.elapsed()
+        })
+        .collect();
+    report(
+        &format!("Board::uncover {}x{} {:.0}%", size, size, density * 100.0),
+        revealed,
+        median(samples),
+    );
+}
+
+fn main() {
+    for size in [100, 500, 1000] {
+        for density in [0.0, 0.05, 0.15] {
+            tile_map_flood_fill(size, density);
+        }
+    }
+    for size in [100, 500] {
+        for density in [0.0, 0.05] {
+            board_uncover(size, density);
+        }
+    }
+}
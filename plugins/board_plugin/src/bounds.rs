@@ -13,12 +13,13 @@ impl Bounds {
         Self { mins, size }
     }
 
+    /// Is `coords` inside the bounds, the max edges being excluded so that they belong to no tile
     #[inline]
     #[must_use]
     pub fn in_bounds(&self, coords: Vec2) -> bool {
         coords.x >= self.mins.x
             && coords.y >= self.mins.y
-            && coords.x <= self.mins.x + self.size.x
-            && coords.y <= self.mins.y + self.size.y
+            && coords.x < self.mins.x + self.size.x
+            && coords.y < self.mins.y + self.size.y
     }
 }
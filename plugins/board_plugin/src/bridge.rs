@@ -0,0 +1,26 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+
+use crate::{resources::GameBridge, systems};
+
+/// Lets a host embedding the app, such as a web page, start and restore games through a
+/// `GameBridge` resource and follow their progress. Must be added along a `BoardPlugin` with the
+/// same running state.
+pub struct BridgePlugin<T> {
+    pub running_state: T,
+}
+
+/// State entered when the host starts a game
+#[derive(Debug, Clone)]
+pub struct BridgeTarget<T>(pub T);
+
+impl<T: StateData> Plugin for BridgePlugin<T> {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameBridge>()
+            .insert_resource(BridgeTarget(self.running_state.clone()))
+            .add_system(systems::bridge::handle_requests::<T>)
+            .add_system(systems::bridge::publish_state);
+
+        log::info!("Loaded Bridge Plugin");
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::Component;
+
+/// Tile cover shrinking and fading out once its tile is uncovered, despawned when done
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+pub struct RevealAnimation {
+    /// Seconds to wait before the cover starts to shrink
+    pub delay: f32,
+    pub elapsed: f32,
+}
+
+/// Flash growing and fading out over an exploded bomb, despawned when done
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+pub struct ExplosionAnimation {
+    pub elapsed: f32,
+}
+
+/// Mark sprite popping in when a tile is marked
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+pub struct MarkAnimation {
+    pub elapsed: f32,
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::Component;
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::Add;
+
+/// Chunk component, locates a chunk (or a tile within it) on an endless board
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct ChunkCoordinates {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl ChunkCoordinates {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(i32, i32)> for ChunkCoordinates {
+    #[inline]
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add<(i32, i32)> for ChunkCoordinates {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, (x, y): (i32, i32)) -> Self::Output {
+        Self {
+            x: self.x + x,
+            y: self.y + y,
+        }
+    }
+}
+
+impl Display for ChunkCoordinates {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.x, self.y)
+    }
+}
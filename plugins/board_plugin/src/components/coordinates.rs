@@ -1,10 +1,24 @@
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Add, Sub};
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
-#[derive(Debug, Copy, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+#[derive(
+    Debug,
+    Copy,
+    Default,
+    Clone,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Component,
+    Serialize,
+    Deserialize,
+)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
@@ -16,6 +30,29 @@ impl Coordinates {
     pub const fn new(x: u16, y: u16) -> Self {
         Self { x, y }
     }
+
+    /// Coordinates moved by a signed offset, or `None` if they would leave the `u16` range
+    #[inline]
+    #[must_use]
+    pub const fn checked_offset(self, (x, y): (i8, i8)) -> Option<Self> {
+        match (
+            self.x.checked_add_signed(x as i16),
+            self.y.checked_add_signed(y as i16),
+        ) {
+            (Some(x), Some(y)) => Some(Self { x, y }),
+            _ => None,
+        }
+    }
+
+    /// Sum of both coordinates, or `None` on overflow
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match (self.x.checked_add(rhs.x), self.y.checked_add(rhs.y)) {
+            (Some(x), Some(y)) => Some(Self { x, y }),
+            _ => None,
+        }
+    }
 }
 
 impl From<(u16, u16)> for Coordinates {
@@ -37,17 +74,6 @@ impl Add for Coordinates {
     }
 }
 
-impl Add<(i8, i8)> for Coordinates {
-    type Output = Self;
-
-    #[inline]
-    fn add(self, (x, y): (i8, i8)) -> Self::Output {
-        let x = ((self.x as i16) + i16::from(x)) as u16;
-        let y = ((self.y as i16) + i16::from(y)) as u16;
-        Self { x, y }
-    }
-}
-
 impl Sub for Coordinates {
     type Output = Self;
 
@@ -0,0 +1,21 @@
+use bevy::prelude::Component;
+
+/// Root node of the HUD
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct Hud;
+
+/// HUD text showing the bomb count minus the flag count
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct HudMineCounter;
+
+/// HUD text showing the elapsed game time
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct HudTimer;
+
+/// HUD button restarting the game, its face shows the game state
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct HudFace;
+
+/// HUD text describing the current board
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct HudPreset;
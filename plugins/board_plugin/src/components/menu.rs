@@ -0,0 +1,83 @@
+use bevy::prelude::Component;
+
+use crate::resources::BoardPreset;
+
+/// Field of the custom board form
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CustomField {
+    Width,
+    Height,
+    BombCount,
+}
+
+/// Root node of the menu, holding the custom board being edited
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct Menu {
+    pub width: u16,
+    pub height: u16,
+    pub bomb_count: u16,
+}
+
+impl Menu {
+    /// Custom board form, clamped to valid values
+    #[inline]
+    #[must_use]
+    pub fn new(width: u16, height: u16, bomb_count: u16) -> Self {
+        let preset = BoardPreset::custom(width, height, bomb_count);
+        let (width, height) = preset.map_size();
+        Self {
+            width: width as u16,
+            height: height as u16,
+            bomb_count: preset.bomb_count(),
+        }
+    }
+
+    /// Custom preset matching the form
+    #[inline]
+    #[must_use]
+    pub const fn preset(&self) -> BoardPreset {
+        BoardPreset::Custom {
+            width: self.width,
+            height: self.height,
+            bomb_count: self.bomb_count,
+        }
+    }
+
+    /// Changes a form field by `delta`, keeping the form valid
+    #[inline]
+    pub fn adjust(&mut self, field: CustomField, delta: i16) {
+        let (mut width, mut height, mut bomb_count) = (self.width, self.height, self.bomb_count);
+        let value = match field {
+            CustomField::Width => &mut width,
+            CustomField::Height => &mut height,
+            CustomField::BombCount => &mut bomb_count,
+        };
+        *value = value.saturating_add_signed(delta);
+        *self = Self::new(width, height, bomb_count);
+    }
+}
+
+/// Menu button action
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum MenuButton {
+    /// Starts a game with a preset
+    Preset(BoardPreset),
+    /// Changes a custom board field
+    Adjust(CustomField, i16),
+    /// Starts a game with the custom board
+    StartCustom,
+    /// Switches to the next undo policy
+    CycleUndo,
+}
+
+/// Menu text showing a custom board field
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct MenuFieldText(pub CustomField);
+
+/// Menu text showing the custom board constraints
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct MenuHint;
+
+/// Menu text showing the undo policy
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct MenuUndoText;
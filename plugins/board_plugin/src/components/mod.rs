@@ -1,9 +1,23 @@
+pub use animation::{ExplosionAnimation, MarkAnimation, RevealAnimation};
 pub use bomb::Bomb;
 pub use bomb_neighbour::BombNeighbour;
+pub use chunk_coordinates::ChunkCoordinates;
 pub use coordinates::Coordinates;
+pub use hud::{Hud, HudFace, HudMineCounter, HudPreset, HudTimer};
+pub use menu::{CustomField, Menu, MenuButton, MenuFieldText, MenuHint, MenuUndoText};
+pub use themed::Themed;
+pub use tile_chunk::{ChunkDirty, TileChunk};
+pub use tile_highlight::TileHighlight;
 pub use uncover::Uncover;
 
+mod animation;
 mod bomb;
 mod bomb_neighbour;
+mod chunk_coordinates;
 mod coordinates;
+mod hud;
+mod menu;
+mod themed;
+mod tile_chunk;
+mod tile_highlight;
 mod uncover;
@@ -0,0 +1,17 @@
+use bevy::prelude::Component;
+
+/// Board asset used by an entity, so a new theme can re-skin it in place
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub enum Themed {
+    Board,
+    Tile,
+    CoveredTile,
+    Bomb,
+    Flag,
+    Question,
+    Cursor,
+    /// Bomb counter text with its count
+    BombCounter(u8),
+    /// UI text using the theme font
+    Label,
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::Component;
+
+use crate::components::Coordinates;
+
+/// Square block of tiles drawn as a single mesh, see `TileRendering::Mesh`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub struct TileChunk {
+    /// Coordinates of the bottom left tile
+    pub origin: Coordinates,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl TileChunk {
+    /// Side of a chunk in tiles
+    pub const SIZE: u16 = 64;
+
+    /// Does the chunk contain the tile
+    #[inline]
+    #[must_use]
+    pub const fn contains(&self, coords: Coordinates) -> bool {
+        coords.x >= self.origin.x
+            && coords.y >= self.origin.y
+            && coords.x - self.origin.x < self.width
+            && coords.y - self.origin.y < self.height
+    }
+}
+
+/// Marks a tile chunk whose mesh must be rebuilt from the board state
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+pub struct ChunkDirty;
@@ -0,0 +1,10 @@
+use bevy::prelude::Component;
+
+/// Overlay sprite drawn over a tile of the board
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum TileHighlight {
+    /// The tile under the mouse cursor
+    Hover,
+    /// A tile previewed as pressed while a mouse button is held
+    Pressed,
+}
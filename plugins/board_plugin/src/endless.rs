@@ -0,0 +1,118 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::RegisterInspectable;
+
+use crate::{
+    components::{ChunkCoordinates, Uncover},
+    events::{BombExplosionEvent, ChunkTileMarkEvent, ChunkTileTriggerEvent},
+    resources::{BoardAssets, EndlessBoard, EndlessOptions, Tile},
+    systems,
+};
+
+/// Endless board, procedurally generated in chunks around the camera
+pub struct EndlessBoardPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: StateData> Plugin for EndlessBoardPlugin<T> {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "debug")]
+        app.register_inspectable::<ChunkCoordinates>();
+
+        // When the running states comes into the stack we load a board
+        app.add_system_set(
+            SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
+        )
+        // We handle input, camera movement and trigger events only if the state is active
+        .add_system_set(
+            SystemSet::on_update(self.running_state.clone())
+                .with_system(systems::endless::input_handling)
+                .with_system(systems::endless::trigger_event_handler)
+                .with_system(systems::camera::keyboard_pan)
+                .with_system(systems::camera::drag_pan)
+                .with_system(systems::camera::wheel_zoom)
+                .with_system(systems::touch::pinch_zoom),
+        )
+        // We handle uncovering and chunk streaming even if the state is inactive
+        .add_system_set(
+            SystemSet::on_in_stack_update(self.running_state.clone())
+                .with_system(systems::endless::uncover_tiles)
+                .with_system(systems::endless::mark_tiles)
+                .with_system(systems::endless::stream_chunks)
+                .with_system(systems::animation::animate_reveal)
+                .with_system(systems::animation::animate_explosions)
+                .with_system(systems::animation::animate_marks),
+        )
+        .add_system_set(
+            SystemSet::on_exit(self.running_state.clone())
+                .with_system(Self::cleanup_board)
+                .with_system(systems::camera::reset_camera),
+        )
+        .add_event::<ChunkTileTriggerEvent>()
+        .add_event::<ChunkTileMarkEvent>()
+        .add_event::<BombExplosionEvent>();
+
+        log::info!("Loaded Endless Board Plugin");
+    }
+}
+
+impl<T> EndlessBoardPlugin<T> {
+    /// System to generate the starting chunk of the board, the others are streamed in as the
+    /// camera moves
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn create_board(
+        mut commands: Commands,
+        endless_options: Option<Res<EndlessOptions>>,
+        board_assets: Res<BoardAssets>,
+    ) {
+        let options = match endless_options {
+            None => EndlessOptions::default(),
+            Some(o) => o.clone(),
+        };
+        // Invalid options would generate no chunk, we fall back to the default ones
+        let options = match options.validate() {
+            Ok(()) => options,
+            Err(e) => {
+                log::error!("Refusing endless options, using the default ones: {}", e);
+                EndlessOptions::default()
+            }
+        };
+        log::info!("endless board seed: {}", options.seed);
+
+        let board_entity = commands
+            .spawn()
+            .insert(Name::new("Endless Board"))
+            .insert(Transform::default())
+            .insert(GlobalTransform::default())
+            .id();
+
+        let safe_start = options.safe_start;
+        let mut board = EndlessBoard::new(board_entity, options);
+        let origin = ChunkCoordinates::default();
+        systems::endless::spawn_chunk(&mut commands, &mut board, &board_assets, origin);
+
+        if safe_start {
+            let chunk = board.chunk_mut(origin);
+            let empty_tile = chunk
+                .covered_tiles
+                .iter()
+                .filter(|&(coords, _)| {
+                    chunk.tile_map.map()[(coords.x as usize, coords.y as usize)] == Tile::Empty
+                })
+                .min_by_key(|&(coords, _)| (coords.y, coords.x));
+            if let Some((_, entity)) = empty_tile {
+                commands.entity(*entity).insert(Uncover);
+            }
+        }
+
+        commands.insert_resource(board);
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn cleanup_board(board: Res<EndlessBoard>, mut commands: Commands) {
+        commands.entity(board.entity).despawn_recursive();
+        commands.remove_resource::<EndlessBoard>();
+    }
+}
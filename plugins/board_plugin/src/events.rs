@@ -1,4 +1,5 @@
-use crate::components::Coordinates;
+use crate::components::{ChunkCoordinates, Coordinates};
+use crate::resources::{BoardOptionsError, TilePick};
 
 #[derive(Debug, Copy, Clone)]
 pub struct TileTriggerEvent(pub Coordinates);
@@ -6,8 +7,39 @@ pub struct TileTriggerEvent(pub Coordinates);
 #[derive(Debug, Copy, Clone)]
 pub struct TileMarkEvent(pub Coordinates);
 
+#[derive(Debug, Copy, Clone)]
+pub struct TileChordEvent(pub Coordinates);
+
+/// The mouse cursor moved from the `left` tile to the `entered` one, `None` being off the board
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TileHoverEvent {
+    pub left: Option<TilePick>,
+    pub entered: Option<TilePick>,
+}
+
+/// Takes back the last move, as allowed by the undo policy of the board
+#[derive(Debug, Copy, Clone)]
+pub struct UndoEvent;
+
+/// Plays the last undone move again
+#[derive(Debug, Copy, Clone)]
+pub struct RedoEvent;
+
+/// Tiles uncovered by a trigger or a chord, flood fill included
+#[derive(Debug, Clone)]
+pub struct TilesUncoveredEvent(pub Vec<Coordinates>);
+
 #[derive(Debug, Copy, Clone)]
 pub struct BoardCompletedEvent;
 
 #[derive(Debug, Copy, Clone)]
 pub struct BombExplosionEvent;
+
+#[derive(Debug, Copy, Clone)]
+pub struct ChunkTileTriggerEvent(pub ChunkCoordinates, pub Coordinates);
+
+#[derive(Debug, Copy, Clone)]
+pub struct ChunkTileMarkEvent(pub ChunkCoordinates, pub Coordinates);
+
+#[derive(Debug, Clone)]
+pub struct BoardOptionsErrorEvent(pub BoardOptionsError);
@@ -0,0 +1,40 @@
+use bevy::{
+    ecs::schedule::{RunCriteria, StateData},
+    log,
+    prelude::*,
+};
+
+use crate::systems::{self, BoardRunCriteria};
+
+/// Heads-up display showing the remaining mines, the game time, a restart face button and the
+/// board description. Must be added along a `BoardPlugin` with the same running state.
+pub struct HudPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: StateData> Plugin for HudPlugin<T> {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(self.running_state.clone()).with_system(systems::hud::spawn_hud),
+        )
+        .add_system_set(
+            SystemSet::on_update(self.running_state.clone())
+                .with_system(systems::hud::face_button::<T>),
+        )
+        // The board readouts are skipped while no board exists
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(
+                    BoardRunCriteria::Update,
+                    systems::board_exists,
+                ))
+                .with_system(systems::hud::update_hud),
+        )
+        .add_system_set(
+            SystemSet::on_exit(self.running_state.clone()).with_system(systems::hud::despawn_hud),
+        );
+
+        log::info!("Loaded HUD Plugin");
+    }
+}
@@ -39,18 +39,40 @@
 )]
 
 pub mod bounds;
+pub mod bridge;
 pub mod components;
+pub mod endless;
 pub mod events;
+pub mod hud;
+pub mod menu;
+pub mod replay;
 pub mod resources;
 pub mod systems;
+pub mod theme;
 
-use bevy::{ecs::schedule::StateData, log, math::Vec3Swizzles, prelude::*, utils::HashMap};
+use bevy::{
+    ecs::schedule::{RunCriteria, StateData},
+    log,
+    math::Vec3Swizzles,
+    prelude::*,
+    sprite::MaterialMesh2dBundle,
+    utils::HashMap,
+};
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::RegisterInspectable;
 use bounds::Bounds;
-use components::{Bomb, BombNeighbour, Coordinates, Uncover};
-use events::{BoardCompletedEvent, BombExplosionEvent, TileMarkEvent, TileTriggerEvent};
-use resources::{Board, BoardAssets, BoardOptions, BoardPosition, Tile, TileMap, TileSize};
+use components::{Bomb, BombNeighbour, ChunkDirty, Coordinates, Themed, TileChunk};
+use events::{
+    BoardCompletedEvent, BoardOptionsErrorEvent, BombExplosionEvent, RedoEvent, TileChordEvent,
+    TileHoverEvent, TileMarkEvent, TileTriggerEvent, TilesUncoveredEvent, UndoEvent,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use resources::{
+    AnimationOptions, Board, BoardAssets, BoardCursor, BoardHistory, BoardOptions, BoardSave,
+    BoardStatus, HoveredTile, Tile, TileMap, TilePress, TileRendering,
+};
+use std::time::Duration;
+use systems::{BoardRunCriteria, BoardSystem};
 
 pub struct BoardPlugin<T> {
     pub running_state: T,
@@ -64,32 +86,86 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
             app.register_inspectable::<Coordinates>();
             app.register_inspectable::<BombNeighbour>();
             app.register_inspectable::<Bomb>();
-            app.register_inspectable::<Uncover>();
+            app.register_inspectable::<components::Uncover>();
         }
 
         // When the running states comes into the stack we load a board
         app.add_system_set(
             SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
         )
-        // We handle input and trigger events only if the state is active
+        // The board systems are skipped while no board exists
+        .stage(CoreStage::Update, |stage: &mut SystemStage| {
+            stage
+                .add_system_run_criteria(
+                    State::on_update(self.running_state.clone()).label(BoardRunCriteria::Update),
+                )
+                .add_system_run_criteria(
+                    State::on_in_stack_update(self.running_state.clone())
+                        .label(BoardRunCriteria::InStackUpdate),
+                )
+        })
+        // We handle input, camera movement and trigger events only if the state is active
         .add_system_set(
-            SystemSet::on_update(self.running_state.clone())
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(
+                    BoardRunCriteria::Update,
+                    systems::board_exists,
+                ))
                 .with_system(systems::input::input_handling)
-                .with_system(systems::uncover::trigger_event_handler),
+                .with_system(systems::input::track_hover)
+                .with_system(systems::highlight::highlight_tiles)
+                .with_system(systems::keyboard::keyboard_handling)
+                .with_system(systems::touch::touch_handling)
+                .with_system(systems::uncover::trigger_event_handler)
+                .with_system(systems::uncover::chord_event_handler)
+                .with_system(systems::undo::undo_event_handler)
+                .with_system(systems::status::update_status)
+                .with_system(systems::camera::follow_cursor)
+                .with_system(systems::camera::drag_pan)
+                .with_system(systems::camera::wheel_zoom)
+                .with_system(systems::touch::pinch_zoom)
+                .with_system(systems::camera::fit_to_window.after(BoardSystem::Layout)),
         )
-        // We handle uncovering even if the state is inactive
+        // We handle uncovering and relayout even if the state is inactive
         .add_system_set(
-            SystemSet::on_in_stack_update(self.running_state.clone())
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(
+                    BoardRunCriteria::InStackUpdate,
+                    systems::board_exists,
+                ))
                 .with_system(systems::uncover::uncover_tiles)
-                .with_system(systems::mark::mark_tiles),
+                .with_system(systems::mark::mark_tiles)
+                .with_system(systems::animation::animate_reveal)
+                .with_system(systems::animation::animate_explosions)
+                .with_system(systems::animation::animate_marks)
+                .with_system(systems::chunk_mesh::build_chunk_meshes)
+                .with_system(systems::chunk_mesh::update_tile_atlas)
+                .with_system(systems::layout::relayout_board.label(BoardSystem::Layout))
+                .with_system(systems::keyboard::update_cursor.after(BoardSystem::Layout)),
         )
         .add_system_set(
-            SystemSet::on_exit(self.running_state.clone()).with_system(Self::cleanup_board),
+            SystemSet::on_exit(self.running_state.clone())
+                .with_system(Self::cleanup_board)
+                .with_system(systems::camera::reset_camera),
         )
         .add_event::<TileTriggerEvent>()
         .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .add_event::<TileHoverEvent>()
+        .add_event::<UndoEvent>()
+        .add_event::<RedoEvent>()
         .add_event::<BombExplosionEvent>()
-        .add_event::<BoardCompletedEvent>();
+        .add_event::<TilesUncoveredEvent>()
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<BoardOptionsErrorEvent>();
+        // Gamepads are only polled when their support is built in
+        #[cfg(feature = "gamepad")]
+        app.add_system(
+            systems::gamepad::gamepad_handling.with_run_criteria(RunCriteria::pipe(
+                BoardRunCriteria::Update,
+                systems::board_exists,
+            )),
+        );
 
         log::info!("Loaded Board Plugin");
     }
@@ -103,27 +179,49 @@ impl<T> BoardPlugin<T> {
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
         board_assets: Res<BoardAssets>,
-        window: Res<WindowDescriptor>,
+        windows: Res<Windows>,
+        board_save: Option<Res<BoardSave>>,
+        mut board_options_error_ewr: EventWriter<BoardOptionsErrorEvent>,
     ) {
-        let options = match board_options {
+        let mut options = match board_options {
             None => BoardOptions::default(),
             Some(o) => o.clone(),
         };
+        // Saved games are restored only once
+        if let Some(save) = board_save.as_deref() {
+            save.apply(&mut options);
+            commands.remove_resource::<BoardSave>();
+        }
+        if let Err(e) = options.validate() {
+            log::error!("Refusing to create board: {}", e);
+            board_options_error_ewr.send(BoardOptionsErrorEvent(e));
+            return;
+        }
 
-        let mut tile_map = TileMap::empty(options.map_size);
-        tile_map.set_bombs(options.bomb_count);
+        let tile_map = match (board_save.as_deref(), options.seed) {
+            (Some(save), _) => save.tile_map(),
+            (None, Some(seed)) => {
+                let mut tile_map = TileMap::empty(options.map_size);
+                tile_map.set_bombs_with_rng(options.bomb_count, &mut StdRng::seed_from_u64(seed));
+                tile_map
+            }
+            (None, None) => {
+                let mut tile_map = TileMap::empty(options.map_size);
+                tile_map.set_bombs(options.bomb_count);
+                tile_map
+            }
+        };
 
         #[cfg(feature = "debug")]
         log::info!("{}", tile_map.console_output());
 
-        let tile_size = match options.tile_size {
-            TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => Self::adaptative_tile_size(
-                &window,
-                (min, max),
-                (tile_map.width(), tile_map.height()),
-            ),
-        };
+        // Headless apps have no window, adaptive tiles get their smallest size
+        let window_size = windows
+            .get_primary()
+            .map_or(Vec2::ZERO, |w| Vec2::new(w.width(), w.height()));
+        let tile_size = options
+            .tile_size
+            .tile_size(window_size, (tile_map.width(), tile_map.height()));
 
         let board_size = Vec2::new(
             tile_map.width() as f32 * tile_size,
@@ -131,15 +229,10 @@ impl<T> BoardPlugin<T> {
         );
         log::info!("board size: {}", board_size);
         // We define the board anchor position (bottom left)
-        let board_mins = match options.position {
-            BoardPosition::Centered(offset) => {
-                Vec3::new(-board_size.x * 0.5, -board_size.y * 0.5, 0.0) + offset
-            }
-            BoardPosition::Offset(p) => p,
-        };
+        let board_mins = options.position.board_mins(board_size);
 
-        let mut safe_start = None;
         let mut covered_tiles = HashMap::with_capacity(tile_map.width() * tile_map.height());
+        let mut tile_entities = HashMap::with_capacity(tile_map.width() * tile_map.height());
         let board_entity = commands
             .spawn()
             .insert(Name::new("Board"))
@@ -156,28 +249,30 @@ impl<T> BoardPlugin<T> {
                         transform: Transform::from_xyz(board_size.x * 0.5, board_size.y * 0.5, 0.),
                         ..default()
                     })
-                    .insert(Name::new("Background"));
+                    .insert(Name::new("Background"))
+                    .insert(Themed::Board);
             })
-            .with_children(|parent| {
-                Self::spawn_tiles(
+            .with_children(|parent| match options.rendering {
+                TileRendering::Sprites => Self::spawn_tiles(
                     parent,
                     &tile_map,
                     tile_size,
                     options.tile_padding,
                     &board_assets,
                     &mut covered_tiles,
-                    &mut safe_start,
-                );
+                    &mut tile_entities,
+                ),
+                TileRendering::Mesh => Self::spawn_chunks(
+                    parent,
+                    &tile_map,
+                    tile_size,
+                    &mut covered_tiles,
+                    &mut tile_entities,
+                ),
             })
             .id();
 
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
-        }
-
-        commands.insert_resource(Board::new(
+        let mut board = Board::new(
             board_entity,
             tile_map,
             covered_tiles,
@@ -186,7 +281,52 @@ impl<T> BoardPlugin<T> {
                 size: board_size,
             },
             tile_size,
-        ));
+        );
+        board.tile_entities = tile_entities;
+        let mut status = BoardStatus::new();
+        if let Some(save) = board_save.as_deref() {
+            Self::restore_board(
+                &mut commands,
+                &mut board,
+                &mut status,
+                save,
+                &board_assets,
+                options.rendering,
+            );
+        } else if options.safe_start {
+            board
+                .pending_uncover
+                .extend(board.tile_map.first_empty_tile());
+        }
+
+        let cursor_entity = commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: board_assets.cursor_material.colour,
+                    custom_size: Some(Vec2::splat(tile_size)),
+                    ..default()
+                },
+                texture: board_assets.cursor_material.texture.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, 10.0),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(Name::new("Cursor"))
+            .insert(Themed::Cursor)
+            .id();
+        let cursor_start = Coordinates::new(
+            (board.tile_map.width() / 2) as u16,
+            (board.tile_map.height() / 2) as u16,
+        );
+        commands.insert_resource(BoardCursor::new(cursor_entity, cursor_start));
+        match options.undo {
+            None => commands.remove_resource::<BoardHistory>(),
+            Some(policy) => commands.insert_resource(BoardHistory::new(policy)),
+        }
+        commands.insert_resource(HoveredTile::default());
+        commands.insert_resource(TilePress::default());
+        commands.insert_resource(status);
+        commands.insert_resource(board);
     }
 
     /// Spawn the tiles.
@@ -197,7 +337,7 @@ impl<T> BoardPlugin<T> {
         tile_padding: f32,
         board_assets: &BoardAssets,
         covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
+        tile_entities: &mut HashMap<Coordinates, Entity>,
     ) {
         for y in 0..tile_map.height() {
             for x in 0..tile_map.width() {
@@ -219,52 +359,46 @@ impl<T> BoardPlugin<T> {
                     ..default()
                 })
                 .insert(Name::new(format!("Tile ({}, {})", x, y)))
+                .insert(Themed::Tile)
                 .insert(coordinates);
+                tile_entities.insert(coordinates, cmd.id());
 
                 cmd.with_children(|parent| {
                     let entity = parent
-                        .spawn_bundle(SpriteBundle {
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(tile_size - tile_padding)),
-                                color: board_assets.covered_tile_material.colour,
-                                ..default()
-                            },
-                            texture: board_assets.covered_tile_material.texture.clone(),
-                            transform: Transform::from_xyz(0.0, 0.0, 2.0),
-                            ..default()
-                        })
+                        .spawn_bundle(board_assets.covered_tile_bundle(tile_size - tile_padding))
                         .insert(Name::new("Tile Cover"))
+                        .insert(Themed::CoveredTile)
                         .id();
                     covered_tiles.insert(coordinates, entity);
-
-                    if safe_start_entity.is_none() && tile_map.map()[(x, y)] == Tile::Empty {
-                        *safe_start_entity = Some(entity);
-                    }
                 });
 
                 match tile_map.map()[(x, y)] {
                     Tile::Bomb => {
                         cmd.insert(Bomb);
                         cmd.with_children(|parent| {
-                            parent.spawn_bundle(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(tile_size - tile_padding)),
+                            parent
+                                .spawn_bundle(SpriteBundle {
+                                    sprite: Sprite {
+                                        custom_size: Some(Vec2::splat(tile_size - tile_padding)),
+                                        color: board_assets.bomb_material.colour,
+                                        ..default()
+                                    },
+                                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                                    texture: board_assets.bomb_material.texture.clone(),
                                     ..default()
-                                },
-                                transform: Transform::from_xyz(0.0, 0.0, 1.0),
-                                texture: board_assets.bomb_material.texture.clone(),
-                                ..default()
-                            });
+                                })
+                                .insert(Themed::Bomb);
                         });
                     }
                     Tile::BombNeighbor(count) => {
                         cmd.insert(BombNeighbour::new(count));
                         cmd.with_children(|parent| {
-                            parent.spawn_bundle(Self::bomb_count_text_bundle(
-                                count,
-                                board_assets,
-                                tile_size - tile_padding,
-                            ));
+                            parent
+                                .spawn_bundle(
+                                    board_assets
+                                        .bomb_count_text_bundle(count, tile_size - tile_padding),
+                                )
+                                .insert(Themed::BombCounter(count));
                         });
                     }
                     Tile::Empty => (),
@@ -273,43 +407,102 @@ impl<T> BoardPlugin<T> {
         }
     }
 
-    /// Generates the bomb counter text 2D Bundle for a given value.
-    fn bomb_count_text_bundle(count: u8, board_assets: &BoardAssets, size: f32) -> Text2dBundle {
-        let colour = board_assets.bomb_counter_color(count);
-        Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: count.to_string(),
-                    style: TextStyle {
-                        color: colour,
-                        font: board_assets.bomb_counter_font.clone(),
-                        font_size: size,
-                    },
-                }],
-                alignment: TextAlignment {
-                    vertical: VerticalAlign::Center,
-                    horizontal: HorizontalAlign::Center,
-                },
-            },
-            transform: Transform::from_xyz(0.0, 0.0, 1.0),
-            ..default()
+    /// Restores the uncovered tiles, marks and game time of a saved game on a new board
+    fn restore_board(
+        commands: &mut Commands,
+        board: &mut Board,
+        status: &mut BoardStatus,
+        save: &BoardSave,
+        board_assets: &BoardAssets,
+        rendering: TileRendering,
+    ) {
+        log::info!("Restoring saved game");
+        board.pending_uncover.extend(save.uncovered.iter().copied());
+        for &(coords, mark) in &save.marks {
+            let entity = match board.covered_tiles.get(&coords) {
+                None => {
+                    log::warn!("Skipping mark on uncovered tile {}", coords);
+                    continue;
+                }
+                Some(&e) => e,
+            };
+            board.marked_tiles.insert(coords, mark);
+            // Chunk meshes draw the marks from the board state
+            if rendering == TileRendering::Sprites {
+                systems::mark::spawn_mark(
+                    commands,
+                    entity,
+                    mark,
+                    board_assets,
+                    board.tile_size,
+                    &AnimationOptions::disabled(),
+                );
+            }
         }
+        status
+            .timer
+            .set_elapsed(Duration::from_secs_f32(save.elapsed));
     }
 
-    /// Computes a tile size that matches the window according to the tile map size.
-    fn adaptative_tile_size(
-        window: &Res<WindowDescriptor>,
-        (min, max): (f32, f32),
-        (board_width, board_height): (usize, usize),
-    ) -> f32 {
-        let max_width = window.width / board_width as f32;
-        let max_heigth = window.height / board_height as f32;
-        max_width.min(max_heigth).clamp(min, max)
+    /// Spawn a mesh entity per chunk of tiles, covering every tile of the chunk.
+    /// The meshes are built by `systems::chunk_mesh::build_chunk_meshes`.
+    fn spawn_chunks(
+        parent: &mut ChildBuilder,
+        tile_map: &TileMap,
+        tile_size: f32,
+        covered_tiles: &mut HashMap<Coordinates, Entity>,
+        tile_entities: &mut HashMap<Coordinates, Entity>,
+    ) {
+        let (width, height) = (tile_map.width() as u16, tile_map.height() as u16);
+        for y0 in (0..height).step_by(TileChunk::SIZE.into()) {
+            for x0 in (0..width).step_by(TileChunk::SIZE.into()) {
+                let chunk = TileChunk {
+                    origin: Coordinates::new(x0, y0),
+                    width: TileChunk::SIZE.min(width - x0),
+                    height: TileChunk::SIZE.min(height - y0),
+                };
+                let entity = parent
+                    .spawn_bundle(MaterialMesh2dBundle::<ColorMaterial> {
+                        transform: Transform {
+                            translation: Vec3::new(f32::from(x0), f32::from(y0), 0.0) * tile_size
+                                + Vec3::Z,
+                            scale: Vec3::new(tile_size, tile_size, 1.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .insert(Name::new(format!("Tile Chunk ({}, {})", x0, y0)))
+                    .insert(chunk)
+                    .insert(ChunkDirty)
+                    .id();
+                for y in y0..y0 + chunk.height {
+                    for x in x0..x0 + chunk.width {
+                        covered_tiles.insert(Coordinates::new(x, y), entity);
+                        tile_entities.insert(Coordinates::new(x, y), entity);
+                    }
+                }
+            }
+        }
     }
 
     #[allow(clippy::needless_pass_by_value)]
-    fn cleanup_board(board: Res<Board>, mut commands: Commands) {
-        commands.entity(board.entity).despawn_recursive();
+    fn cleanup_board(
+        board: Option<Res<Board>>,
+        cursor: Option<Res<BoardCursor>>,
+        mut commands: Commands,
+    ) {
+        // No board exists if the options were refused
+        if let Some(board) = board {
+            commands.entity(board.entity).despawn_recursive();
+        }
+        if let Some(cursor) = cursor {
+            commands.entity(cursor.entity).despawn_recursive();
+        }
         commands.remove_resource::<Board>();
+        commands.remove_resource::<BoardCursor>();
+        commands.remove_resource::<BoardStatus>();
+        commands.remove_resource::<HoveredTile>();
+        commands.remove_resource::<TilePress>();
+        commands.remove_resource::<BoardHistory>();
     }
 }
@@ -0,0 +1,36 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+
+use crate::systems;
+
+/// Menu picking the board preset before starting a game
+pub struct MenuPlugin<T> {
+    /// State showing the menu
+    pub menu_state: T,
+    /// State entered once a preset is picked, running the `BoardPlugin`
+    pub game_state: T,
+}
+
+/// State entered when a game is started from the menu
+#[derive(Debug, Clone)]
+pub struct MenuTarget<T>(pub T);
+
+impl<T: StateData> Plugin for MenuPlugin<T> {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MenuTarget(self.game_state.clone()))
+            .add_system_set(
+                SystemSet::on_enter(self.menu_state.clone()).with_system(systems::menu::spawn_menu),
+            )
+            .add_system_set(
+                SystemSet::on_update(self.menu_state.clone())
+                    .with_system(systems::menu::menu_buttons::<T>)
+                    .with_system(systems::menu::update_form),
+            )
+            .add_system_set(
+                SystemSet::on_exit(self.menu_state.clone())
+                    .with_system(systems::menu::despawn_menu),
+            );
+
+        log::info!("Loaded Menu Plugin");
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::{
+    ecs::schedule::{ShouldRun, StateData},
+    log,
+    prelude::*,
+};
+
+use crate::{
+    resources::ReplayRecorder,
+    systems::{self, BoardSystem},
+};
+
+/// Records the moves of classic board games in a `ReplayRecorder` resource, and plays back the
+/// moves of a `ReplayPlayer` resource. Must be added along a `BoardPlugin` with the same running
+/// state.
+pub struct ReplayPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: StateData> Plugin for ReplayPlugin<T> {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        // Moves are recorded and played before the board systems handle them. State run criteria
+        // only end their loop in the state stage, the running state is checked directly.
+        let running_state = self.running_state.clone();
+        app.init_resource::<ReplayRecorder>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                systems::replay::record_moves.label(BoardSystem::Record),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                systems::replay::play_moves
+                    .with_run_criteria(move |state: Res<State<T>>| {
+                        if state.current() == &running_state {
+                            ShouldRun::Yes
+                        } else {
+                            ShouldRun::No
+                        }
+                    })
+                    .after(BoardSystem::Record),
+            );
+
+        log::info!("Loaded Replay Plugin");
+    }
+}
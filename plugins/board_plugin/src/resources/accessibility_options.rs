@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{BoardAssets, Palette};
+
+/// Player accessibility choices applied over the selected theme. Must be used as a resource.
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct AccessibilityOptions {
+    /// Palette replacing the theme colours, the theme colours are kept when unset.
+    pub palette: Option<Palette>,
+    /// Draws a distinct glyph next to every bomb counter.
+    pub glyphs: bool,
+}
+
+impl AccessibilityOptions {
+    /// Switches to the next palette, going back to the theme colours after the last one
+    #[inline]
+    pub const fn cycle_palette(&mut self) {
+        self.palette = match self.palette {
+            None => Some(Palette::Standard),
+            Some(Palette::HighContrast) => None,
+            Some(p) => Some(p.next()),
+        };
+    }
+
+    /// Applies the choices over the theme board assets
+    #[inline]
+    pub fn apply(&self, board_assets: &mut BoardAssets) {
+        if let Some(palette) = self.palette {
+            palette.apply(board_assets);
+        }
+        board_assets.bomb_counter_glyphs |= self.glyphs;
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Board animation options. Must be used as a resource.
+/// Animations are purely visual, the board state is updated before they start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationOptions {
+    /// Plays the animations, tiles are revealed and marked instantly otherwise.
+    pub enabled: bool,
+    /// Seconds a tile cover takes to shrink and fade out.
+    pub reveal_duration: f32,
+    /// Seconds between two rings of a flood fill reveal, rings further from the uncovered tile
+    /// start later.
+    pub reveal_stagger: f32,
+    /// Seconds an explosion flash lasts.
+    pub explosion_duration: f32,
+    /// Seconds a mark takes to pop in.
+    pub mark_duration: f32,
+}
+
+impl AnimationOptions {
+    /// Options with every animation skipped
+    #[inline]
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for AnimationOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reveal_duration: 0.15,
+            reveal_stagger: 0.03,
+            explosion_duration: 0.6,
+            mark_duration: 0.15,
+        }
+    }
+}
@@ -1,25 +1,37 @@
 use crate::bounds::Bounds;
-use crate::{Coordinates, TileMap};
+use crate::{resources::Mark, Coordinates, Tile, TileMap};
 use bevy::{
     log,
-    prelude::{Entity, Vec2, Window},
+    prelude::{Entity, Vec2},
     utils::HashMap,
 };
 
+/// A tile found under a world position
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TilePick {
+    pub coordinates: Coordinates,
+    /// Tile entity, or the chunk entity drawing the tile with mesh rendering
+    pub entity: Entity,
+}
+
 #[derive(Debug)]
 pub struct Board {
     pub entity: Entity,
     pub tile_map: TileMap,
     pub covered_tiles: HashMap<Coordinates, Entity>,
+    /// Tile entities, or the chunk entities drawing the tiles with mesh rendering
+    pub tile_entities: HashMap<Coordinates, Entity>,
     pub bounds: Bounds,
     pub tile_size: f32,
-    pub marked_tiles: Vec<Coordinates>,
+    pub marked_tiles: HashMap<Coordinates, Mark>,
+    /// Tiles to uncover on the next reveal pass
+    pub pending_uncover: Vec<Coordinates>,
 }
 
 impl Board {
     #[inline]
     #[must_use]
-    pub const fn new(
+    pub fn new(
         entity: Entity,
         tile_map: TileMap,
         covered_tiles: HashMap<Coordinates, Entity>,
@@ -30,50 +42,108 @@ impl Board {
             entity,
             tile_map,
             covered_tiles,
+            tile_entities: HashMap::default(),
             bounds,
             tile_size,
-            marked_tiles: vec![],
+            marked_tiles: HashMap::default(),
+            pending_uncover: Vec::new(),
         }
     }
 
-    /// Translates a mouse position to board coordinates
+    /// Translates a world position to board coordinates
     #[inline]
     #[must_use]
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
-        // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size * 0.5;
-
+    pub fn world_to_tile(&self, position: Vec2) -> Option<Coordinates> {
         // Bounds check
         if !self.bounds.in_bounds(position) {
             return None;
         }
         // World space to board space
         let coordinates = position - self.bounds.mins;
-        Some(Coordinates {
+        let coordinates = Coordinates {
             x: (coordinates.x / self.tile_size) as u16,
             y: (coordinates.y / self.tile_size) as u16,
-        })
+        };
+        // Guards against rounding on the far edges
+        self.tile_map.contains(coordinates).then_some(coordinates)
+    }
+
+    /// Finds the tile and its entity at a world position
+    #[inline]
+    #[must_use]
+    pub fn pick(&self, position: Vec2) -> Option<TilePick> {
+        let coordinates = self.world_to_tile(position)?;
+        self.tile_entities
+            .get(&coordinates)
+            .map(|&entity| TilePick {
+                coordinates,
+                entity,
+            })
+    }
+
+    /// Centre of the tile at `coords` relative to the board entity
+    #[inline]
+    #[must_use]
+    pub fn tile_centre(&self, coords: Coordinates) -> Vec2 {
+        (Vec2::new(f32::from(coords.x), f32::from(coords.y)) + 0.5) * self.tile_size
     }
 
-    /// We try to mark or unmark a tile, returning the entity and if the tile is marked
-    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
+    /// Translates board coordinates to the world position of the tile centre
+    #[inline]
+    #[must_use]
+    pub fn tile_to_world(&self, coords: Coordinates) -> Vec2 {
+        self.bounds.mins + self.tile_centre(coords)
+    }
+
+    /// We try to cycle the mark of a tile, returning the entity and its new mark.
+    /// Question marks are only part of the cycle if `question_marks` is set.
+    pub fn try_toggle_mark(
+        &mut self,
+        coords: &Coordinates,
+        question_marks: bool,
+    ) -> Option<(Entity, Option<Mark>)> {
         let entity = *self.covered_tiles.get(coords)?;
-        let mark = if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
-            false
-        } else {
-            self.marked_tiles.push(*coords);
-            true
-        };
+        let mark = Mark::cycle(self.marked_tiles.get(coords).copied(), question_marks);
+        match mark {
+            None => {
+                self.unmark_tile(coords)?;
+            }
+            Some(m) => {
+                self.marked_tiles.insert(*coords, m);
+            }
+        }
         Some((entity, mark))
     }
 
+    /// Is the tile flagged
+    #[must_use]
+    #[inline]
+    pub fn is_flagged(&self, coords: &Coordinates) -> bool {
+        self.marked_tiles.get(coords) == Some(&Mark::Flag)
+    }
+
+    /// Number of flagged tiles, question marks excluded
+    #[must_use]
+    #[inline]
+    pub fn flag_count(&self) -> usize {
+        self.marked_tiles
+            .values()
+            .filter(|&&mark| mark == Mark::Flag)
+            .count()
+    }
+
+    /// Bomb count minus flag count, negative when too many tiles are flagged
+    #[must_use]
+    #[inline]
+    pub fn remaining_bombs(&self) -> i64 {
+        i64::from(self.tile_map.bomb_count()) - self.flag_count() as i64
+    }
+
     /// Retrieves a covered tile entity
     #[must_use]
     #[inline]
     pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        if self.marked_tiles.contains(coords) {
+        if self.is_flagged(coords) {
             None
         } else {
             self.covered_tiles.get(coords)
@@ -84,34 +154,72 @@ impl Board {
     #[must_use]
     #[inline]
     pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-        if self.marked_tiles.contains(coords) {
+        if self.marked_tiles.contains_key(coords) {
             self.unmark_tile(coords)?;
         }
         self.covered_tiles.remove(coords)
     }
 
-    /// We retrieve the adjacent covered tile entities of `coord`
-    #[must_use]
+    /// Uncovers `starts` and flood fills from the empty ones in a single pass, returning every
+    /// uncovered tile with its cover entity and its distance in rings from the nearest start
     #[inline]
-    pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
-        TileMap::safe_square_at(coord)
-            .filter_map(|c| self.covered_tiles.get(&c))
+    #[must_use]
+    pub fn uncover<I: IntoIterator<Item = Coordinates>>(
+        &mut self,
+        starts: I,
+    ) -> Vec<(Coordinates, Entity, u32)> {
+        let revealed = self
+            .tile_map
+            .flood_fill(starts, |c| self.covered_tiles.contains_key(&c));
+        revealed
+            .into_iter()
+            .filter_map(|(coords, depth)| {
+                self.try_uncover_tile(&coords)
+                    .map(|entity| (coords, entity, depth))
+            })
+            .collect()
+    }
+
+    /// Retrieves the covered tiles uncovered by chording on `coords`.
+    /// Chording only applies to an uncovered bomb neighbour with as many flagged neighbours as its
+    /// bomb count.
+    #[must_use]
+    pub fn tiles_to_chord(&self, coords: Coordinates) -> Vec<Coordinates> {
+        if self.covered_tiles.contains_key(&coords) {
+            return vec![];
+        }
+        let count = match self
+            .tile_map
+            .map()
+            .get((coords.x as usize, coords.y as usize))
             .copied()
+        {
+            Some(Tile::BombNeighbor(count)) => count as usize,
+            _ => return vec![],
+        };
+        let marks = self
+            .tile_map
+            .neighbours(coords)
+            .filter(|c| self.is_flagged(c))
+            .count();
+        if marks != count {
+            return vec![];
+        }
+        self.tile_map
+            .neighbours(coords)
+            .filter(|c| self.tile_to_uncover(c).is_some())
             .collect()
     }
 
     /// Removes the `coords` from `marked_tiles`
     #[must_use]
     #[inline]
-    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
-        let pos = match self.marked_tiles.iter().position(|a| a == coords) {
-            None => {
-                log::error!("Failed to unmark tile at {}", coords);
-                return None;
-            }
-            Some(p) => p,
-        };
-        Some(self.marked_tiles.remove(pos))
+    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Mark> {
+        let mark = self.marked_tiles.remove(coords);
+        if mark.is_none() {
+            log::error!("Failed to unmark tile at {}", coords);
+        }
+        mark
     }
 
     /// Is the board complete
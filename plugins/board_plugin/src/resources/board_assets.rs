@@ -1,6 +1,9 @@
 use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
 
+use crate::resources::{Mark, Palette};
+
 /// Material of a Sprite with a texture and color
 #[derive(Debug, Clone)]
 pub struct SpriteMaterial {
@@ -17,29 +20,52 @@ impl Default for SpriteMaterial {
     }
 }
 
-/// Assets for the board. Must be used as a resource.
-#[derive(Debug, Clone)]
+/// Assets for the board. Must be used as a resource, theme packs are loaded as this asset.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "6b7c7d0e-4f3a-4a2e-9d4b-1c8e5f2a9b30"]
 pub struct BoardAssets {
+    /// Theme id.
     pub label: String,
     pub board_material: SpriteMaterial,
     pub tile_material: SpriteMaterial,
     pub covered_tile_material: SpriteMaterial,
     pub bomb_counter_font: Handle<Font>,
     pub bomb_counter_colours: Vec<Color>,
+    /// Draws a distinct glyph next to every bomb counter, so counts are not told apart by colour
+    /// alone.
+    pub bomb_counter_glyphs: bool,
     pub flag_material: SpriteMaterial,
+    pub question_material: SpriteMaterial,
     pub bomb_material: SpriteMaterial,
+    pub cursor_material: SpriteMaterial,
+}
+
+/// Plain white materials, for apps running without a theme pack such as headless ones
+impl Default for BoardAssets {
+    fn default() -> Self {
+        Self {
+            label: "Default".to_string(),
+            board_material: SpriteMaterial::default(),
+            tile_material: SpriteMaterial::default(),
+            covered_tile_material: SpriteMaterial::default(),
+            bomb_counter_font: Handle::default(),
+            bomb_counter_colours: Self::default_colors(),
+            bomb_counter_glyphs: false,
+            flag_material: SpriteMaterial::default(),
+            question_material: SpriteMaterial::default(),
+            bomb_material: SpriteMaterial::default(),
+            cursor_material: SpriteMaterial::default(),
+        }
+    }
 }
 
 impl BoardAssets {
+    /// Bomb counter glyphs, from one neighbouring bomb to eight
+    pub const BOMB_COUNTER_GLYPHS: [&'static str; 8] = [".", ":", "^", "+", "*", "#", "%", "@"];
+
     /// Default bomb counter color set
     pub fn default_colors() -> Vec<Color> {
-        vec![
-            Color::WHITE,
-            Color::GREEN,
-            Color::YELLOW,
-            Color::ORANGE,
-            Color::PURPLE,
-        ]
+        Palette::Standard.colours().to_vec()
     }
 
     /// Safely retrieves the color matching a bomb counter
@@ -53,4 +79,67 @@ impl BoardAssets {
             },
         }
     }
+
+    /// Retrieves the sprite material of a tile mark
+    #[inline]
+    #[must_use]
+    pub const fn mark_material(&self, mark: Mark) -> &SpriteMaterial {
+        match mark {
+            Mark::Flag => &self.flag_material,
+            Mark::Question => &self.question_material,
+        }
+    }
+
+    /// Generates the bomb counter text for a given value, with its glyph if enabled
+    pub fn bomb_count_text(&self, count: u8, size: f32) -> Text {
+        let style = TextStyle {
+            color: self.bomb_counter_color(count),
+            font: self.bomb_counter_font.clone(),
+            font_size: size,
+        };
+        let mut sections = vec![TextSection {
+            value: count.to_string(),
+            style: style.clone(),
+        }];
+        let glyph = Self::BOMB_COUNTER_GLYPHS.get(usize::from(count.saturating_sub(1)));
+        if let (true, Some(glyph)) = (self.bomb_counter_glyphs, glyph) {
+            sections.push(TextSection {
+                value: (*glyph).to_string(),
+                style: TextStyle {
+                    font_size: size * 0.5,
+                    ..style
+                },
+            });
+        }
+        Text {
+            sections,
+            alignment: TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
+            },
+        }
+    }
+
+    /// Generates the bomb counter text 2D Bundle for a given value.
+    pub fn bomb_count_text_bundle(&self, count: u8, size: f32) -> Text2dBundle {
+        Text2dBundle {
+            text: self.bomb_count_text(count, size),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..default()
+        }
+    }
+
+    /// Generates the sprite bundle of a tile cover, above the tile and its content
+    pub fn covered_tile_bundle(&self, size: f32) -> SpriteBundle {
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(size)),
+                color: self.covered_tile_material.colour,
+                ..default()
+            },
+            texture: self.covered_tile_material.texture.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 2.0),
+            ..default()
+        }
+    }
 }
@@ -0,0 +1,66 @@
+use bevy::prelude::Entity;
+
+use crate::components::Coordinates;
+
+/// Highlighted tile for keyboard and gamepad play.
+#[derive(Debug)]
+pub struct BoardCursor {
+    /// Highlight sprite entity
+    pub entity: Entity,
+    pub coordinates: Coordinates,
+    /// The cursor is only shown once it has been moved
+    pub visible: bool,
+}
+
+impl BoardCursor {
+    #[inline]
+    #[must_use]
+    pub const fn new(entity: Entity, coordinates: Coordinates) -> Self {
+        Self {
+            entity,
+            coordinates,
+            visible: false,
+        }
+    }
+
+    /// Moves the cursor by `(x, y)` tiles, staying within a `width` by `height` board
+    #[inline]
+    pub fn move_by(&mut self, (x, y): (i8, i8), (width, height): (usize, usize)) {
+        let clamp = |value: u16, delta: i8, size: usize| {
+            (i32::from(value) + i32::from(delta)).clamp(0, size as i32 - 1) as u16
+        };
+        self.coordinates = Coordinates::new(
+            clamp(self.coordinates.x, x, width),
+            clamp(self.coordinates.y, y, height),
+        );
+        self.visible = true;
+    }
+}
+
+/// Held cursor movement, moving once when pressed and then repeating after a delay
+#[derive(Debug, Default)]
+pub struct CursorRepeat {
+    direction: (i8, i8),
+    timer: f32,
+}
+
+impl CursorRepeat {
+    /// Updates the held direction, returning if the cursor moves this frame
+    #[inline]
+    pub fn tick(&mut self, direction: (i8, i8), delta: f32, delay: f32, interval: f32) -> bool {
+        if direction != self.direction {
+            self.direction = direction;
+            self.timer = delay;
+            return direction != (0, 0);
+        }
+        if direction == (0, 0) {
+            return false;
+        }
+        self.timer -= delta;
+        if self.timer > 0.0 {
+            return false;
+        }
+        self.timer += interval;
+        true
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use bevy::{log, prelude::Entity, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::Coordinates,
+    resources::{Board, BoardState, BoardStatus, Mark},
+};
+
+/// Which moves can be taken back, for practice and puzzle games
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UndoPolicy {
+    /// Every move can be undone and redone.
+    Unlimited,
+    /// Only marks can be undone and redone, back to the last reveal.
+    FlagsOnly,
+    /// The move that lost the game can be undone once per game, adding `penalty` seconds to the
+    /// game time.
+    AfterDeath { penalty: f32 },
+}
+
+impl UndoPolicy {
+    /// Penalty of the after death policy picked from the menu or the game settings, in seconds
+    pub const DEATH_PENALTY: f32 = 30.0;
+
+    /// Policy following `policy` in the menu, `None` disabling undo
+    #[inline]
+    #[must_use]
+    pub const fn cycle(policy: Option<Self>) -> Option<Self> {
+        match policy {
+            None => Some(Self::Unlimited),
+            Some(Self::Unlimited) => Some(Self::FlagsOnly),
+            Some(Self::FlagsOnly) => Some(Self::AfterDeath {
+                penalty: Self::DEATH_PENALTY,
+            }),
+            Some(Self::AfterDeath { .. }) => None,
+        }
+    }
+}
+
+impl Display for UndoPolicy {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Unlimited => write!(f, "Unlimited"),
+            Self::FlagsOnly => write!(f, "Flags only"),
+            Self::AfterDeath { penalty } => write!(f, "After death (+{}s)", penalty),
+        }
+    }
+}
+
+/// Tiles and marks changed on the board by a single move, and the game state around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardChange {
+    /// Tiles uncovered by the move, flood fill included.
+    pub uncovered: Vec<Coordinates>,
+    /// Marks changed by the move, before and after it, uncovering removing marks.
+    pub marks: Vec<(Coordinates, Option<Mark>, Option<Mark>)>,
+    /// Game state before the move.
+    pub state_before: BoardState,
+    /// Game state after the move.
+    pub state_after: BoardState,
+}
+
+impl BoardChange {
+    /// Change made by uncovering `uncovered` on `board`, `marks` being the board marks before the
+    /// move. Uncovering a bomb loses the game and uncovering the last safe tile wins it.
+    #[inline]
+    #[must_use]
+    pub fn reveal(
+        board: &Board,
+        uncovered: Vec<Coordinates>,
+        marks: &HashMap<Coordinates, Mark>,
+        state_before: BoardState,
+    ) -> Self {
+        let state_after = if uncovered.iter().any(|&c| board.tile_map.is_bomb_at(c)) {
+            BoardState::Lost
+        } else if board.is_completed() {
+            BoardState::Won
+        } else {
+            state_before
+        };
+        let marks = uncovered
+            .iter()
+            .filter_map(|c| marks.get(c).map(|&mark| (*c, Some(mark), None)))
+            .collect();
+        Self {
+            uncovered,
+            marks,
+            state_before,
+            state_after,
+        }
+    }
+
+    /// Change made by setting the mark of the tile at `coords`
+    #[inline]
+    #[must_use]
+    pub fn mark(
+        coords: Coordinates,
+        before: Option<Mark>,
+        after: Option<Mark>,
+        state: BoardState,
+    ) -> Self {
+        Self {
+            uncovered: Vec::new(),
+            marks: vec![(coords, before, after)],
+            state_before: state,
+            state_after: state,
+        }
+    }
+
+    /// Does the move uncover tiles
+    #[inline]
+    #[must_use]
+    pub fn is_reveal(&self) -> bool {
+        !self.uncovered.is_empty()
+    }
+
+    /// Covers the uncovered tiles again and restores the marks and game state from before the
+    /// move. `cover` returns the cover entity of a tile from its tile entity, as found in
+    /// `Board::tile_entities`.
+    #[inline]
+    pub fn revert<F>(&self, board: &mut Board, status: &mut BoardStatus, mut cover: F)
+    where
+        F: FnMut(Coordinates, Entity) -> Entity,
+    {
+        for &coords in &self.uncovered {
+            match board.tile_entities.get(&coords) {
+                None => log::error!("Failed to cover tile {}, it has no entity", coords),
+                Some(&tile) => {
+                    board.covered_tiles.insert(coords, cover(coords, tile));
+                }
+            }
+        }
+        for &(coords, before, _) in &self.marks {
+            set_mark(board, coords, before);
+        }
+        set_state(status, self.state_before);
+    }
+
+    /// Uncovers the tiles and sets the marks and game state of the move again, returning the
+    /// uncovered tiles with their cover entity
+    #[inline]
+    pub fn apply(&self, board: &mut Board, status: &mut BoardStatus) -> Vec<(Coordinates, Entity)> {
+        let uncovered = self
+            .uncovered
+            .iter()
+            .filter_map(|&c| board.try_uncover_tile(&c).map(|entity| (c, entity)))
+            .collect();
+        for &(coords, _, after) in &self.marks {
+            set_mark(board, coords, after);
+        }
+        set_state(status, self.state_after);
+        uncovered
+    }
+}
+
+fn set_mark(board: &mut Board, coords: Coordinates, mark: Option<Mark>) {
+    match mark {
+        None => board.marked_tiles.remove(&coords),
+        Some(m) => board.marked_tiles.insert(coords, m),
+    };
+}
+
+/// Sets the game state, running the timer only while playing
+fn set_state(status: &mut BoardStatus, state: BoardState) {
+    status.state = state;
+    if status.is_over() {
+        status.timer.pause();
+    } else {
+        status.timer.unpause();
+    }
+}
+
+/// Undo and redo stacks of the moves of the current game. Must be used as a resource.
+#[derive(Debug, Clone)]
+pub struct BoardHistory {
+    pub policy: UndoPolicy,
+    undo: Vec<BoardChange>,
+    redo: Vec<BoardChange>,
+    /// Set once the losing move was undone under `UndoPolicy::AfterDeath`
+    death_undone: bool,
+}
+
+impl BoardHistory {
+    #[inline]
+    #[must_use]
+    pub const fn new(policy: UndoPolicy) -> Self {
+        Self {
+            policy,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            death_undone: false,
+        }
+    }
+
+    /// Records a move, dropping the moves that were undone.
+    /// Only the moves the policy can take back are kept.
+    #[inline]
+    pub fn record(&mut self, change: BoardChange) {
+        self.redo.clear();
+        match self.policy {
+            UndoPolicy::Unlimited => self.undo.push(change),
+            // Reveals can't be undone, nor the marks before them
+            UndoPolicy::FlagsOnly if change.is_reveal() => self.undo.clear(),
+            UndoPolicy::FlagsOnly => self.undo.push(change),
+            // Marks placed once the game is lost keep the losing move undoable
+            UndoPolicy::AfterDeath { .. } if change.state_before != BoardState::Playing => (),
+            UndoPolicy::AfterDeath { .. } => self.undo = vec![change],
+        }
+    }
+
+    /// Can the last move be undone while the game is in `state`
+    #[inline]
+    #[must_use]
+    pub fn can_undo(&self, state: BoardState) -> bool {
+        match (self.policy, self.undo.last()) {
+            (_, None) => false,
+            (UndoPolicy::Unlimited | UndoPolicy::FlagsOnly, Some(_)) => true,
+            (UndoPolicy::AfterDeath { .. }, Some(change)) => {
+                state == BoardState::Lost
+                    && change.state_after == BoardState::Lost
+                    && !self.death_undone
+            }
+        }
+    }
+
+    /// Can the last undone move be redone
+    #[inline]
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !matches!(self.policy, UndoPolicy::AfterDeath { .. }) && !self.redo.is_empty()
+    }
+
+    /// Takes back the last move if the policy allows it, reverting it on the board and status
+    /// with `cover` as in `BoardChange::revert`, and returns it
+    #[inline]
+    pub fn undo<F>(
+        &mut self,
+        board: &mut Board,
+        status: &mut BoardStatus,
+        cover: F,
+    ) -> Option<BoardChange>
+    where
+        F: FnMut(Coordinates, Entity) -> Entity,
+    {
+        if !self.can_undo(status.state) {
+            return None;
+        }
+        let change = self.undo.pop()?;
+        change.revert(board, status, cover);
+        if let UndoPolicy::AfterDeath { penalty } = self.policy {
+            self.death_undone = true;
+            let elapsed = status.timer.elapsed() + Duration::from_secs_f32(penalty.max(0.0));
+            status.timer.set_elapsed(elapsed);
+        }
+        self.redo.push(change.clone());
+        Some(change)
+    }
+
+    /// Plays the last undone move again if the policy allows it, returning it with the tiles it
+    /// uncovered as in `BoardChange::apply`
+    #[inline]
+    pub fn redo(
+        &mut self,
+        board: &mut Board,
+        status: &mut BoardStatus,
+    ) -> Option<(BoardChange, Vec<(Coordinates, Entity)>)> {
+        if !self.can_redo() {
+            return None;
+        }
+        let change = self.redo.pop()?;
+        let uncovered = change.apply(board, status);
+        self.undo.push(change.clone());
+        Some((change, uncovered))
+    }
+}
@@ -1,8 +1,10 @@
-use bevy::prelude::Vec3;
+use bevy::prelude::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
+use crate::resources::{BoardOptionsError, UndoPolicy};
+
 /// Tile size options.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileSize {
     /// Fixed tile size.
     Fixed(f32),
@@ -10,6 +12,34 @@ pub enum TileSize {
     Adaptive { min: f32, max: f32 },
 }
 
+impl TileSize {
+    /// Computes the tile world size, adaptive sizes match the window according to the tile map size.
+    #[inline]
+    #[must_use]
+    pub fn tile_size(&self, window_size: Vec2, (board_width, board_height): (usize, usize)) -> f32 {
+        match *self {
+            Self::Fixed(v) => v,
+            Self::Adaptive { min, max } => {
+                let max_width = window_size.x / board_width as f32;
+                let max_heigth = window_size.y / board_height as f32;
+                max_width.min(max_heigth).clamp(min, max)
+            }
+        }
+    }
+
+    /// Smallest tile world size, `None` if the size is not strictly positive and finite
+    #[inline]
+    #[must_use]
+    pub fn min_tile_size(&self) -> Option<f32> {
+        let valid = |v: f32| v.is_finite() && v > 0.0;
+        match *self {
+            Self::Fixed(v) if valid(v) => Some(v),
+            Self::Adaptive { min, max } if valid(min) && valid(max) && min <= max => Some(min),
+            Self::Fixed(_) | Self::Adaptive { .. } => None,
+        }
+    }
+}
+
 impl Default for TileSize {
     #[inline]
     fn default() -> Self {
@@ -29,6 +59,18 @@ pub enum BoardPosition {
     Offset(Vec3),
 }
 
+impl BoardPosition {
+    /// Computes the board anchor position (bottom left) for a board of the given world size.
+    #[inline]
+    #[must_use]
+    pub fn board_mins(&self, board_size: Vec2) -> Vec3 {
+        match *self {
+            Self::Centered(offset) => (-board_size * 0.5).extend(0.0) + offset,
+            Self::Offset(p) => p,
+        }
+    }
+}
+
 impl Default for BoardPosition {
     #[inline]
     fn default() -> Self {
@@ -36,6 +78,23 @@ impl Default for BoardPosition {
     }
 }
 
+/// How the tiles of a board are drawn.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TileRendering {
+    /// A sprite per tile with its cover, bomb or counter text as child entities.
+    Sprites,
+    /// A mesh per chunk of tiles textured from a generated atlas, for very large boards.
+    /// Reveals and marks are not animated.
+    Mesh,
+}
+
+impl Default for TileRendering {
+    #[inline]
+    fn default() -> Self {
+        Self::Sprites
+    }
+}
+
 /// Board generation options. Must be used as a resource.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoardOptions {
@@ -51,6 +110,17 @@ pub struct BoardOptions {
     pub tile_padding: f32,
     /// Does the board generate a safe place to start.
     pub safe_start: bool,
+    /// Does marking cycle through a question mark after the flag.
+    pub question_marks: bool,
+    /// How the tiles are drawn.
+    #[serde(default)]
+    pub rendering: TileRendering,
+    /// Seed of the bomb placement, random boards are generated without one.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Which moves can be undone, moves can't be undone without a policy.
+    #[serde(default)]
+    pub undo: Option<UndoPolicy>,
 }
 
 impl Default for BoardOptions {
@@ -63,6 +133,50 @@ impl Default for BoardOptions {
             tile_size: TileSize::default(),
             tile_padding: 0.,
             safe_start: false,
+            question_marks: false,
+            rendering: TileRendering::default(),
+            seed: None,
+            undo: None,
+        }
+    }
+}
+
+impl BoardOptions {
+    /// Checks the options can generate a board
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid option found
+    #[inline]
+    pub fn validate(&self) -> Result<(), BoardOptionsError> {
+        let (width, height) = self.map_size;
+        if width == 0 || height == 0 {
+            return Err(BoardOptionsError::EmptyMap { width, height });
+        }
+        let max_side = usize::from(u16::MAX);
+        if width > max_side || height > max_side {
+            return Err(BoardOptionsError::MapTooLarge { width, height });
+        }
+        let tile_count = width * height;
+        if usize::from(self.bomb_count) >= tile_count {
+            return Err(BoardOptionsError::TooManyBombs {
+                bomb_count: self.bomb_count,
+                tile_count,
+            });
+        }
+        let tile_size = self
+            .tile_size
+            .min_tile_size()
+            .ok_or_else(|| BoardOptionsError::InvalidTileSize(self.tile_size.clone()))?;
+        if !self.tile_padding.is_finite() || self.tile_padding < 0.0 {
+            return Err(BoardOptionsError::InvalidPadding(self.tile_padding));
+        }
+        if self.tile_padding >= tile_size {
+            return Err(BoardOptionsError::PaddingTooLarge {
+                tile_padding: self.tile_padding,
+                tile_size,
+            });
         }
+        Ok(())
     }
 }
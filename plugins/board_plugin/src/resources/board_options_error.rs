@@ -0,0 +1,63 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::resources::TileSize;
+
+/// Reason why `BoardOptions` can't generate a board
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardOptionsError {
+    /// The map has no tiles
+    EmptyMap { width: usize, height: usize },
+    /// The map sides don't fit in tile coordinates
+    MapTooLarge { width: usize, height: usize },
+    /// The bombs leave no safe tile
+    TooManyBombs { bomb_count: u16, tile_count: usize },
+    /// The tile size is not strictly positive and finite, or its bounds are swapped
+    InvalidTileSize(TileSize),
+    /// The tile padding is negative or not finite
+    InvalidPadding(f32),
+    /// The tile padding covers the whole tile
+    PaddingTooLarge { tile_padding: f32, tile_size: f32 },
+}
+
+impl Display for BoardOptionsError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::EmptyMap { width, height } => {
+                write!(f, "the {}x{} map has no tiles", width, height)
+            }
+            Self::MapTooLarge { width, height } => write!(
+                f,
+                "the {}x{} map is larger than {} tiles per side",
+                width,
+                height,
+                u16::MAX
+            ),
+            Self::TooManyBombs {
+                bomb_count,
+                tile_count,
+            } => write!(
+                f,
+                "{} bombs leave no safe tile out of {} tiles",
+                bomb_count, tile_count
+            ),
+            Self::InvalidTileSize(ref tile_size) => {
+                write!(f, "invalid tile size {:?}", tile_size)
+            }
+            Self::InvalidPadding(tile_padding) => {
+                write!(f, "invalid tile padding {}", tile_padding)
+            }
+            Self::PaddingTooLarge {
+                tile_padding,
+                tile_size,
+            } => write!(
+                f,
+                "tile padding {} is not smaller than the tile size {}",
+                tile_padding, tile_size
+            ),
+        }
+    }
+}
+
+impl Error for BoardOptionsError {}
@@ -0,0 +1,119 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::BoardOptions;
+
+/// Board size and bomb count presets. Must be used as a resource.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BoardPreset {
+    /// 9x9 board with 10 bombs.
+    Beginner,
+    /// 16x16 board with 40 bombs.
+    Intermediate,
+    /// 30x16 board with 99 bombs.
+    Expert,
+    /// Player defined board.
+    Custom {
+        width: u16,
+        height: u16,
+        bomb_count: u16,
+    },
+}
+
+impl BoardPreset {
+    /// Smallest custom board side
+    pub const MIN_SIZE: u16 = 2;
+    /// Largest custom board side
+    pub const MAX_SIZE: u16 = 100;
+
+    /// Tile map size of the preset
+    #[inline]
+    #[must_use]
+    pub const fn map_size(&self) -> (usize, usize) {
+        match *self {
+            Self::Beginner => (9, 9),
+            Self::Intermediate => (16, 16),
+            Self::Expert => (30, 16),
+            Self::Custom { width, height, .. } => (width as usize, height as usize),
+        }
+    }
+
+    /// Bomb count of the preset
+    #[inline]
+    #[must_use]
+    pub const fn bomb_count(&self) -> u16 {
+        match *self {
+            Self::Beginner => 10,
+            Self::Intermediate => 40,
+            Self::Expert => 99,
+            Self::Custom { bomb_count, .. } => bomb_count,
+        }
+    }
+
+    /// Most bombs a board can hold while keeping at least one safe tile
+    #[inline]
+    #[must_use]
+    pub const fn max_bomb_count(width: u16, height: u16) -> u16 {
+        let cells = width as u32 * height as u32;
+        if cells > u16::MAX as u32 {
+            u16::MAX
+        } else {
+            (cells as u16).saturating_sub(1)
+        }
+    }
+
+    /// Builds a custom preset, clamping the size and bomb count to valid values
+    #[inline]
+    #[must_use]
+    pub fn custom(width: u16, height: u16, bomb_count: u16) -> Self {
+        let width = width.clamp(Self::MIN_SIZE, Self::MAX_SIZE);
+        let height = height.clamp(Self::MIN_SIZE, Self::MAX_SIZE);
+        Self::Custom {
+            width,
+            height,
+            bomb_count: bomb_count.clamp(1, Self::max_bomb_count(width, height)),
+        }
+    }
+
+    /// Is the preset playable: a valid size with at least one bomb and one safe tile
+    #[inline]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let (width, height) = self.map_size();
+        let size = Self::MIN_SIZE as usize..=Self::MAX_SIZE as usize;
+        size.contains(&width)
+            && size.contains(&height)
+            && (1..=Self::max_bomb_count(width as u16, height as u16)).contains(&self.bomb_count())
+    }
+
+    /// Applies the preset size and bomb count to board options
+    #[inline]
+    pub fn apply(&self, options: &mut BoardOptions) {
+        options.map_size = self.map_size();
+        options.bomb_count = self.bomb_count();
+    }
+}
+
+impl Default for BoardPreset {
+    #[inline]
+    fn default() -> Self {
+        Self::Intermediate
+    }
+}
+
+impl Display for BoardPreset {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Beginner => write!(f, "Beginner"),
+            Self::Intermediate => write!(f, "Intermediate"),
+            Self::Expert => write!(f, "Expert"),
+            Self::Custom {
+                width,
+                height,
+                bomb_count,
+            } => write!(f, "Custom {}x{} ({})", width, height, bomb_count),
+        }
+    }
+}
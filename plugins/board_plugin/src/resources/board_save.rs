@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::Coordinates,
+    resources::{Board, BoardOptions, BoardStatus, Mark, TileMap},
+};
+
+/// Saved classic board game. Inserted as a resource, the next board is restored from it instead
+/// of being generated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardSave {
+    /// Tile map size.
+    pub map_size: (u16, u16),
+    /// Bomb tiles.
+    pub bombs: Vec<Coordinates>,
+    /// Uncovered tiles.
+    pub uncovered: Vec<Coordinates>,
+    /// Marks on covered tiles.
+    pub marks: Vec<(Coordinates, Mark)>,
+    /// Time spent playing, in seconds.
+    pub elapsed: f32,
+}
+
+impl BoardSave {
+    /// Saves the current game
+    #[inline]
+    #[must_use]
+    pub fn new(board: &Board, status: &BoardStatus) -> Self {
+        let mut bombs = Vec::with_capacity(board.tile_map.bomb_count().into());
+        let mut uncovered = Vec::new();
+        for ((x, y), tile) in board.tile_map.map().indexed_iter() {
+            let coords = Coordinates::new(x as u16, y as u16);
+            if tile.is_bomb() {
+                bombs.push(coords);
+            }
+            if !board.covered_tiles.contains_key(&coords) {
+                uncovered.push(coords);
+            }
+        }
+        let mut marks: Vec<_> = board
+            .marked_tiles
+            .iter()
+            .map(|(&coords, &mark)| (coords, mark))
+            .collect();
+        marks.sort_unstable_by_key(|&(coords, _)| coords);
+        Self {
+            map_size: (
+                board.tile_map.width() as u16,
+                board.tile_map.height() as u16,
+            ),
+            bombs,
+            uncovered,
+            marks,
+            elapsed: status.timer.elapsed_secs(),
+        }
+    }
+
+    /// Sets the board size and bomb count of the saved game
+    #[inline]
+    pub fn apply(&self, options: &mut BoardOptions) {
+        options.map_size = (self.map_size.0.into(), self.map_size.1.into());
+        options.bomb_count = self.bombs.len().min(u16::MAX.into()) as u16;
+    }
+
+    /// Tile map of the saved game
+    #[inline]
+    #[must_use]
+    pub fn tile_map(&self) -> TileMap {
+        let mut tile_map = TileMap::empty((self.map_size.0.into(), self.map_size.1.into()));
+        tile_map.set_bombs_at(self.bombs.iter().copied());
+        tile_map
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::core::Stopwatch;
+
+/// Outcome of the current game
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BoardState {
+    /// The game is running
+    Playing,
+    /// Every safe tile was uncovered
+    Won,
+    /// A bomb exploded
+    Lost,
+}
+
+/// Progress of the current game. Must be used as a resource.
+#[derive(Debug, Clone)]
+pub struct BoardStatus {
+    /// Outcome of the game so far.
+    pub state: BoardState,
+    /// Time spent playing, paused once the game is over.
+    pub timer: Stopwatch,
+}
+
+impl BoardStatus {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: BoardState::Playing,
+            timer: Stopwatch::new(),
+        }
+    }
+
+    /// Is the game over, won or lost
+    #[inline]
+    #[must_use]
+    pub fn is_over(&self) -> bool {
+        self.state != BoardState::Playing
+    }
+}
+
+impl Default for BoardStatus {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    components::Coordinates,
+    resources::{BoardSave, BoardTextError, BoardTextErrorKind, Mark},
+};
+
+/// Builds an error at a line and column starting from 1
+const fn error(line: usize, column: usize, kind: BoardTextErrorKind) -> BoardTextError {
+    BoardTextError { line, column, kind }
+}
+
+impl BoardSave {
+    /// Reads a board written as a text grid, one line per tile row from the top of the board.
+    /// Trailing whitespace is ignored, and the time spent playing is zero.
+    ///
+    /// ```text
+    /// board   = { ignored } row { row | ignored }
+    /// ignored = blank line | "#" comment line
+    /// row     = tile { tile }        every row being as wide as the first one
+    /// tile    = "." covered safe tile   | "*" covered bomb
+    ///         | "f" flagged safe tile   | "F" flagged bomb
+    ///         | "?" questioned safe tile | "!" questioned bomb
+    ///         | "0" to "8" uncovered safe tile, with its neighbouring bomb count
+    ///         | "X" uncovered bomb
+    /// ```
+    ///
+    /// For example, a 4x3 board uncovered but for its flagged bomb in the bottom left corner:
+    ///
+    /// ```text
+    /// # Flag test
+    /// 0000
+    /// 1100
+    /// F100
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the line and column of the first unexpected character, ragged row, counter not
+    /// matching the neighbouring bombs or bomb past the bomb count limit
+    #[inline]
+    pub fn from_text(text: &str) -> Result<Self, BoardTextError> {
+        let rows: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim_end()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        let width = match rows.first() {
+            Some((_, row)) => row.chars().count(),
+            None => {
+                let line = text.lines().count().max(1);
+                return Err(error(line, 1, BoardTextErrorKind::Empty));
+            }
+        };
+        let height = rows.len();
+        if width > u16::MAX.into() {
+            return Err(error(rows[0].0, width, BoardTextErrorKind::TooLarge));
+        }
+        if height > u16::MAX.into() {
+            let line = rows[usize::from(u16::MAX)].0;
+            return Err(error(line, 1, BoardTextErrorKind::TooLarge));
+        }
+
+        let mut save = Self {
+            map_size: (width as u16, height as u16),
+            bombs: Vec::new(),
+            uncovered: Vec::new(),
+            marks: Vec::new(),
+            elapsed: 0.0,
+        };
+        let mut counters = Vec::new();
+        for (row, &(line, tiles)) in rows.iter().enumerate() {
+            let found = tiles.chars().count();
+            if found != width {
+                let kind = BoardTextErrorKind::RaggedRow {
+                    expected: width,
+                    found,
+                };
+                return Err(error(line, found.min(width) + 1, kind));
+            }
+            let y = (height - 1 - row) as u16;
+            for (x, tile) in tiles.chars().enumerate() {
+                let coords = Coordinates::new(x as u16, y);
+                match tile {
+                    '.' => (),
+                    '*' => save.bombs.push(coords),
+                    'f' => save.marks.push((coords, Mark::Flag)),
+                    'F' => {
+                        save.bombs.push(coords);
+                        save.marks.push((coords, Mark::Flag));
+                    }
+                    '?' => save.marks.push((coords, Mark::Question)),
+                    '!' => {
+                        save.bombs.push(coords);
+                        save.marks.push((coords, Mark::Question));
+                    }
+                    'X' => {
+                        save.bombs.push(coords);
+                        save.uncovered.push(coords);
+                    }
+                    '0'..='8' => {
+                        save.uncovered.push(coords);
+                        counters.push((line, x + 1, coords, tile as u8 - b'0'));
+                    }
+                    c => {
+                        let kind = BoardTextErrorKind::UnexpectedChar(c);
+                        return Err(error(line, x + 1, kind));
+                    }
+                }
+                if save.bombs.len() > usize::from(u16::MAX) {
+                    return Err(error(line, x + 1, BoardTextErrorKind::TooManyBombs));
+                }
+            }
+        }
+
+        // Counters are checked once every bomb is known
+        let tile_map = save.tile_map();
+        for (line, column, coords, found) in counters {
+            let expected = tile_map.bomb_count_at(coords);
+            if found != expected {
+                let kind = BoardTextErrorKind::WrongCounter { expected, found };
+                return Err(error(line, column, kind));
+            }
+        }
+        // Same order as the saves of a running game
+        save.bombs.sort_unstable();
+        save.uncovered.sort_unstable();
+        save.marks.sort_unstable_by_key(|&(coords, _)| coords);
+        Ok(save)
+    }
+
+    /// Writes the board as a text grid, as read by `from_text`
+    #[inline]
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let tile_map = self.tile_map();
+        let uncovered: HashSet<_> = self.uncovered.iter().copied().collect();
+        let marks: HashMap<_, _> = self.marks.iter().copied().collect();
+        let (width, height) = self.map_size;
+        let mut text = String::with_capacity((usize::from(width) + 1) * usize::from(height));
+        for y in (0..height).rev() {
+            for x in 0..width {
+                let coords = Coordinates::new(x, y);
+                let bomb = tile_map.is_bomb_at(coords);
+                text.push(
+                    match (uncovered.contains(&coords), marks.get(&coords), bomb) {
+                        (true, _, true) => 'X',
+                        (true, _, false) => char::from(b'0' + tile_map.bomb_count_at(coords)),
+                        (false, Some(Mark::Flag), true) => 'F',
+                        (false, Some(Mark::Flag), false) => 'f',
+                        (false, Some(Mark::Question), true) => '!',
+                        (false, Some(Mark::Question), false) => '?',
+                        (false, None, true) => '*',
+                        (false, None, false) => '.',
+                    },
+                );
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Reason why a text board was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardTextErrorKind {
+    /// The text has no board row
+    Empty,
+    /// The character is not a tile
+    UnexpectedChar(char),
+    /// The row is not as wide as the first one
+    RaggedRow { expected: usize, found: usize },
+    /// The board sides don't fit in tile coordinates
+    TooLarge,
+    /// The bomb doesn't fit in the bomb count
+    TooManyBombs,
+    /// The uncovered counter doesn't match the neighbouring bombs
+    WrongCounter { expected: u8, found: u8 },
+}
+
+/// Text board error, at a line and column starting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardTextError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: BoardTextErrorKind,
+}
+
+impl Display for BoardTextError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match self.kind {
+            BoardTextErrorKind::Empty => write!(f, "no board row"),
+            BoardTextErrorKind::UnexpectedChar(c) => write!(
+                f,
+                "unexpected {:?}, expected a tile out of . * F f ? ! X or 0 to 8",
+                c
+            ),
+            BoardTextErrorKind::RaggedRow { expected, found } => write!(
+                f,
+                "row of {} tiles, expected {} as the first row",
+                found, expected
+            ),
+            BoardTextErrorKind::TooLarge => {
+                write!(f, "the board is larger than {} tiles per side", u16::MAX)
+            }
+            BoardTextErrorKind::TooManyBombs => write!(f, "more than {} bombs", u16::MAX),
+            BoardTextErrorKind::WrongCounter { expected, found } => write!(
+                f,
+                "counter {} doesn't match the {} neighbouring bombs",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl Error for BoardTextError {}
@@ -0,0 +1,38 @@
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+/// Sprite material as written in a theme file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeMaterial {
+    /// Sprite colour, white when omitted.
+    #[serde(default = "white")]
+    pub colour: Color,
+    /// Texture path relative to the assets folder, plain colour when omitted.
+    #[serde(default)]
+    pub texture: Option<String>,
+}
+
+const fn white() -> Color {
+    Color::WHITE
+}
+
+/// Theme pack file format, loaded from `*.theme.ron` assets into `BoardAssets`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardTheme {
+    /// Theme id, used to select the theme at runtime.
+    pub label: String,
+    pub board: ThemeMaterial,
+    pub tile: ThemeMaterial,
+    pub covered_tile: ThemeMaterial,
+    /// Font path relative to the assets folder.
+    pub bomb_counter_font: String,
+    /// Bomb counter colours, from one neighbouring bomb upwards.
+    pub bomb_counter_colours: Vec<Color>,
+    /// Draws a distinct glyph next to every bomb counter.
+    #[serde(default)]
+    pub bomb_counter_glyphs: bool,
+    pub flag: ThemeMaterial,
+    pub question: ThemeMaterial,
+    pub bomb: ThemeMaterial,
+    pub cursor: ThemeMaterial,
+}
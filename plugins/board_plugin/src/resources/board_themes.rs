@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::resources::BoardAssets;
+
+/// Available theme packs and the selected one. Must be used as a resource.
+#[derive(Debug, Clone, Default)]
+pub struct BoardThemes {
+    /// Theme pack handles, in switching order.
+    pub themes: Vec<Handle<BoardAssets>>,
+    /// Index of the selected theme.
+    pub current: usize,
+}
+
+impl BoardThemes {
+    /// Loads the theme packs at the given asset paths, selecting the first one
+    #[inline]
+    #[must_use]
+    pub fn load(asset_server: &AssetServer, paths: &[&str]) -> Self {
+        Self {
+            themes: paths.iter().map(|path| asset_server.load(*path)).collect(),
+            current: 0,
+        }
+    }
+
+    /// Handle of the selected theme
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<&Handle<BoardAssets>> {
+        self.themes.get(self.current)
+    }
+
+    /// Selects the next theme, wrapping around
+    #[inline]
+    pub const fn next(&mut self) {
+        if !self.themes.is_empty() {
+            self.current = (self.current + 1) % self.themes.len();
+        }
+    }
+
+    /// Selects a loaded theme by its label, returns false if no such theme is loaded
+    #[inline]
+    pub fn select(&mut self, label: &str, themes: &Assets<BoardAssets>) -> bool {
+        let index = self
+            .themes
+            .iter()
+            .position(|handle| themes.get(handle).is_some_and(|theme| theme.label == label));
+        match index {
+            None => false,
+            Some(i) => {
+                self.current = i;
+                true
+            }
+        }
+    }
+}
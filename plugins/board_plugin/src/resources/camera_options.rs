@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Camera control options. Must be used as a resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraOptions {
+    /// Keyboard panning speed, in window pixels per second.
+    pub pan_speed: f32,
+    /// Relative zoom change per mouse wheel line.
+    pub zoom_speed: f32,
+    /// Smallest projection scale (closest zoom).
+    pub min_scale: f32,
+    /// Largest projection scale (furthest zoom).
+    pub max_scale: f32,
+    /// Empty space left around the board when fitting it to the window, relative to its size.
+    pub fit_margin: f32,
+}
+
+impl Default for CameraOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pan_speed: 600.0,
+            zoom_speed: 0.1,
+            min_scale: 0.25,
+            max_scale: 10.0,
+            fit_margin: 0.05,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use bevy::{
+    log,
+    prelude::Entity,
+    utils::{HashMap, HashSet},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    components::{ChunkCoordinates, Coordinates},
+    resources::{EndlessOptions, Tile, TileMap},
+};
+
+/// A square section of an endless board.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    /// Chunk entity, if the chunk is currently spawned
+    pub entity: Option<Entity>,
+    pub tile_map: TileMap,
+    pub covered_tiles: HashMap<Coordinates, Entity>,
+    pub uncovered_tiles: HashSet<Coordinates>,
+    pub marked_tiles: Vec<Coordinates>,
+}
+
+impl Chunk {
+    /// Generates the chunk at `coords` from the world seed.
+    /// Bomb neighbor counts take the bombs of the surrounding chunks into account.
+    #[must_use]
+    pub fn generate(options: &EndlessOptions, coords: ChunkCoordinates) -> Self {
+        let size = i32::from(options.chunk_size);
+        let layouts: HashMap<ChunkCoordinates, TileMap> = (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| coords + (x, y)))
+            .map(|c| (c, Self::bomb_layout(options, c)))
+            .collect();
+        let is_bomb_at = |x: i32, y: i32| {
+            let chunk = coords + (x.div_euclid(size), y.div_euclid(size));
+            let local = Coordinates::new(x.rem_euclid(size) as u16, y.rem_euclid(size) as u16);
+            layouts[&chunk].is_bomb_at(local)
+        };
+
+        let mut tile_map = layouts[&coords].clone();
+        for y in 0..size {
+            for x in 0..size {
+                if is_bomb_at(x, y) {
+                    continue;
+                }
+                let count = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .filter(|&(dx, dy)| (dx, dy) != (0, 0) && is_bomb_at(x + dx, y + dy))
+                    .count() as u8;
+                let tile = if count == 0 {
+                    Tile::Empty
+                } else {
+                    Tile::BombNeighbor(count)
+                };
+                tile_map.set_tile(Coordinates::new(x as u16, y as u16), tile);
+            }
+        }
+
+        Self {
+            entity: None,
+            tile_map,
+            covered_tiles: HashMap::default(),
+            uncovered_tiles: HashSet::default(),
+            marked_tiles: vec![],
+        }
+    }
+
+    /// Places the bombs of a chunk, deterministically from the world seed
+    fn bomb_layout(options: &EndlessOptions, coords: ChunkCoordinates) -> TileMap {
+        let seed = options.seed
+            ^ (coords.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (coords.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        let size = options.chunk_size as usize;
+        let mut tile_map = TileMap::empty((size, size));
+        tile_map.set_bombs_with_rng(options.bombs_per_chunk, &mut StdRng::seed_from_u64(seed));
+        tile_map
+    }
+
+    /// Is the chunk currently spawned
+    #[inline]
+    #[must_use]
+    pub const fn is_spawned(&self) -> bool {
+        self.entity.is_some()
+    }
+
+    /// Forgets the chunk entities, returning the chunk entity to despawn.
+    /// The uncovered and marked tiles are kept for when the chunk is spawned again.
+    #[inline]
+    pub fn despawn(&mut self) -> Option<Entity> {
+        self.covered_tiles.clear();
+        self.entity.take()
+    }
+
+    /// We try to mark or unmark a tile, returning the entity and if the tile is marked
+    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
+        let entity = *self.covered_tiles.get(coords)?;
+        let mark = if self.marked_tiles.contains(coords) {
+            self.unmark_tile(coords)?;
+            false
+        } else {
+            self.marked_tiles.push(*coords);
+            true
+        };
+        Some((entity, mark))
+    }
+
+    /// Retrieves a covered tile entity
+    #[must_use]
+    #[inline]
+    pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
+        if self.marked_tiles.contains(coords) {
+            None
+        } else {
+            self.covered_tiles.get(coords)
+        }
+    }
+
+    /// We try to uncover a tile, returning the entity
+    #[inline]
+    pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
+        if self.marked_tiles.contains(coords) {
+            self.unmark_tile(coords)?;
+        }
+        self.uncovered_tiles.insert(*coords);
+        self.covered_tiles.remove(coords)
+    }
+
+    /// Removes the `coords` from `marked_tiles`
+    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
+        let pos = match self.marked_tiles.iter().position(|a| a == coords) {
+            None => {
+                log::error!("Failed to unmark tile at {}", coords);
+                return None;
+            }
+            Some(p) => p,
+        };
+        Some(self.marked_tiles.remove(pos))
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::{
+    prelude::{Entity, Vec2},
+    utils::HashMap,
+};
+
+use crate::{
+    components::{ChunkCoordinates, Coordinates},
+    resources::{Chunk, EndlessOptions},
+};
+
+/// Endless board, generated chunk by chunk around the camera.
+#[derive(Debug)]
+pub struct EndlessBoard {
+    pub entity: Entity,
+    pub options: EndlessOptions,
+    /// Every chunk visited so far, spawned or not
+    pub chunks: HashMap<ChunkCoordinates, Chunk>,
+}
+
+impl EndlessBoard {
+    #[inline]
+    #[must_use]
+    pub fn new(entity: Entity, options: EndlessOptions) -> Self {
+        Self {
+            entity,
+            options,
+            chunks: HashMap::default(),
+        }
+    }
+
+    /// World size of a single chunk side
+    #[inline]
+    #[must_use]
+    pub fn chunk_world_size(&self) -> f32 {
+        f32::from(self.options.chunk_size) * self.options.tile_size
+    }
+
+    /// World position of the bottom left corner of a chunk
+    #[inline]
+    #[must_use]
+    pub fn chunk_position(&self, coords: ChunkCoordinates) -> Vec2 {
+        Vec2::new(coords.x as f32, coords.y as f32) * self.chunk_world_size()
+    }
+
+    /// Chunk containing a world position
+    #[inline]
+    #[must_use]
+    pub fn chunk_at(&self, position: Vec2) -> ChunkCoordinates {
+        let chunk = (position / self.chunk_world_size()).floor();
+        ChunkCoordinates::new(chunk.x as i32, chunk.y as i32)
+    }
+
+    /// Translates a world position to chunk and tile coordinates
+    #[inline]
+    #[must_use]
+    pub fn world_to_tile(&self, position: Vec2) -> (ChunkCoordinates, Coordinates) {
+        let chunk = self.chunk_at(position);
+        let local = ((position - self.chunk_position(chunk)) / self.options.tile_size).floor();
+        let max = self.options.chunk_size.saturating_sub(1);
+        (
+            chunk,
+            Coordinates::new((local.x as u16).min(max), (local.y as u16).min(max)),
+        )
+    }
+
+    /// Every chunk overlapping the world rectangle, extended by `margin` chunks
+    #[must_use]
+    pub fn chunks_in_rect(&self, mins: Vec2, maxs: Vec2, margin: i32) -> Vec<ChunkCoordinates> {
+        let mins = self.chunk_at(mins) + (-margin, -margin);
+        let maxs = self.chunk_at(maxs) + (margin, margin);
+        (mins.y..=maxs.y)
+            .flat_map(|y| (mins.x..=maxs.x).map(move |x| ChunkCoordinates::new(x, y)))
+            .collect()
+    }
+
+    /// The 8 neighbours of a tile, crossing chunk borders where needed
+    pub fn adjacent_tiles(
+        &self,
+        chunk: ChunkCoordinates,
+        coords: Coordinates,
+    ) -> impl Iterator<Item = (ChunkCoordinates, Coordinates)> {
+        let size = i32::from(self.options.chunk_size);
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| (x, y)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(x, y)| {
+                let x = i32::from(coords.x) + x;
+                let y = i32::from(coords.y) + y;
+                (
+                    chunk + (x.div_euclid(size), y.div_euclid(size)),
+                    Coordinates::new(x.rem_euclid(size) as u16, y.rem_euclid(size) as u16),
+                )
+            })
+    }
+
+    /// Retrieves a chunk, generating it if it was never visited
+    #[inline]
+    pub fn chunk_mut(&mut self, coords: ChunkCoordinates) -> &mut Chunk {
+        let options = &self.options;
+        self.chunks
+            .entry(coords)
+            .or_insert_with(|| Chunk::generate(options, coords))
+    }
+
+    /// Is the chunk currently spawned
+    #[inline]
+    #[must_use]
+    pub fn is_spawned(&self, coords: ChunkCoordinates) -> bool {
+        self.chunks.get(&coords).is_some_and(Chunk::is_spawned)
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{BoardOptionsError, TileSize};
+
+/// Endless board generation options. Must be used as a resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndlessOptions {
+    /// World seed, the same seed always generates the same world.
+    pub seed: u64,
+    /// Number of tiles along each side of a chunk.
+    pub chunk_size: u16,
+    /// bomb count of every chunk.
+    pub bombs_per_chunk: u16,
+    /// Number of extra chunks kept around the visible area.
+    pub view_distance: u16,
+    /// Tile world size.
+    pub tile_size: f32,
+    /// Padding between tiles.
+    pub tile_padding: f32,
+    /// Does the board generate a safe place to start.
+    pub safe_start: bool,
+}
+
+impl Default for EndlessOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            chunk_size: 16,
+            bombs_per_chunk: 40,
+            view_distance: 1,
+            tile_size: 32.0,
+            tile_padding: 1.0,
+            safe_start: true,
+        }
+    }
+}
+
+impl EndlessOptions {
+    /// Checks the options can generate chunks
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid option found
+    #[inline]
+    pub fn validate(&self) -> Result<(), BoardOptionsError> {
+        let side = usize::from(self.chunk_size);
+        if side == 0 {
+            return Err(BoardOptionsError::EmptyMap {
+                width: side,
+                height: side,
+            });
+        }
+        let tile_count = side * side;
+        if usize::from(self.bombs_per_chunk) >= tile_count {
+            return Err(BoardOptionsError::TooManyBombs {
+                bomb_count: self.bombs_per_chunk,
+                tile_count,
+            });
+        }
+        if !self.tile_size.is_finite() || self.tile_size <= 0.0 {
+            return Err(BoardOptionsError::InvalidTileSize(TileSize::Fixed(
+                self.tile_size,
+            )));
+        }
+        if !self.tile_padding.is_finite() || self.tile_padding < 0.0 {
+            return Err(BoardOptionsError::InvalidPadding(self.tile_padding));
+        }
+        if self.tile_padding >= self.tile_size {
+            return Err(BoardOptionsError::PaddingTooLarge {
+                tile_padding: self.tile_padding,
+                tile_size: self.tile_size,
+            });
+        }
+        Ok(())
+    }
+}
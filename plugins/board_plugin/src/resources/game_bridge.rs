@@ -0,0 +1,160 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    components::Coordinates,
+    resources::{BoardOptions, BoardOptionsError, BoardPreset, BoardSave, BoardState},
+};
+
+/// Request of the host, handled by the app on a following frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum BridgeRequest {
+    /// Starts a new game
+    Start {
+        preset: BoardPreset,
+        /// Seed of the bomb placement, random without one
+        seed: Option<u64>,
+    },
+    /// Restores a saved game
+    Restore(BoardSave),
+}
+
+/// Game notification for the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeEvent {
+    /// Every safe tile was uncovered
+    Won,
+    /// A bomb exploded
+    Lost,
+    /// Tiles were uncovered
+    Revealed(Vec<Coordinates>),
+}
+
+/// State of the current game as seen by the host
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GameSnapshot {
+    /// Outcome of the game so far.
+    pub state: BoardState,
+    /// Bomb count minus flag count.
+    pub mines_left: i64,
+    /// Time spent playing, in seconds.
+    pub elapsed: f32,
+    /// Tile map size.
+    pub map_size: (u16, u16),
+}
+
+#[derive(Debug, Default)]
+struct BridgeState {
+    request: Option<BridgeRequest>,
+    snapshot: Option<GameSnapshot>,
+    save: Option<BoardSave>,
+    events: Vec<BridgeEvent>,
+}
+
+/// Handle shared between the app and a host embedding it, such as a web page. Requests are
+/// queued by the host and the game state is published back every frame. Must be used as a
+/// resource, the host keeping a clone.
+#[derive(Debug, Clone, Default)]
+pub struct GameBridge(Arc<Mutex<BridgeState>>);
+
+impl GameBridge {
+    fn state(&self) -> MutexGuard<'_, BridgeState> {
+        // The state stays consistent even if a holder panicked
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Requests a new game, replacing any pending request
+    ///
+    /// # Errors
+    ///
+    /// Returns the board options error if the preset can't generate a board
+    #[inline]
+    pub fn start(&self, preset: BoardPreset, seed: Option<u64>) -> Result<(), BoardOptionsError> {
+        let mut options = BoardOptions::default();
+        preset.apply(&mut options);
+        options.validate()?;
+        self.state().request = Some(BridgeRequest::Start { preset, seed });
+        Ok(())
+    }
+
+    /// Requests the restoration of a saved game, replacing any pending request
+    ///
+    /// # Errors
+    ///
+    /// Returns the board options error if the save can't generate a board
+    #[inline]
+    pub fn restore(&self, save: BoardSave) -> Result<(), BoardOptionsError> {
+        let mut options = BoardOptions::default();
+        save.apply(&mut options);
+        options.validate()?;
+        self.state().request = Some(BridgeRequest::Restore(save));
+        Ok(())
+    }
+
+    /// State of the current game, `None` while no board exists
+    #[inline]
+    #[must_use]
+    pub fn snapshot(&self) -> Option<GameSnapshot> {
+        self.state().snapshot
+    }
+
+    /// Saves the current game, `None` while no board exists
+    #[inline]
+    #[must_use]
+    pub fn save(&self) -> Option<BoardSave> {
+        let state = self.state();
+        let mut save = state.save.clone()?;
+        if let Some(snapshot) = state.snapshot {
+            save.elapsed = snapshot.elapsed;
+        }
+        Some(save)
+    }
+
+    /// Takes the notifications published since the last call
+    #[inline]
+    #[must_use]
+    pub fn drain_events(&self) -> Vec<BridgeEvent> {
+        std::mem::take(&mut self.state().events)
+    }
+
+    /// Pending request of the host
+    #[inline]
+    #[must_use]
+    pub fn has_request(&self) -> bool {
+        self.state().request.is_some()
+    }
+
+    /// Takes the pending request of the host
+    #[inline]
+    #[must_use]
+    pub fn take_request(&self) -> Option<BridgeRequest> {
+        self.state().request.take()
+    }
+
+    /// Publishes the state of the current game, notifying the end of the game
+    #[inline]
+    pub fn publish(&self, snapshot: Option<GameSnapshot>) {
+        let mut state = self.state();
+        let was_playing = state
+            .snapshot
+            .map_or(false, |s| s.state == BoardState::Playing);
+        match snapshot.map(|s| s.state) {
+            Some(BoardState::Won) if was_playing => state.events.push(BridgeEvent::Won),
+            Some(BoardState::Lost) if was_playing => state.events.push(BridgeEvent::Lost),
+            None => state.save = None,
+            Some(_) => (),
+        }
+        state.snapshot = snapshot;
+    }
+
+    /// Publishes the saved current game
+    #[inline]
+    pub fn publish_save(&self, save: BoardSave) {
+        self.state().save = Some(save);
+    }
+
+    /// Notifies the host
+    #[inline]
+    pub fn notify(&self, event: BridgeEvent) {
+        self.state().events.push(event);
+    }
+}
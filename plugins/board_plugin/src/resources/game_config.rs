@@ -0,0 +1,236 @@
+use std::str::{from_utf8, FromStr};
+
+use crate::resources::{BoardOptions, BoardPreset, EndlessOptions, GameConfigError, UndoPolicy};
+
+/// Game mode started from the hosting environment
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameMode {
+    /// Board of a fixed size
+    Classic,
+    /// Endless board streamed in chunks
+    Endless,
+}
+
+/// Game settings from the hosting environment, the page URL query on the web and the command
+/// line natively. Must be used as a resource.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameConfig {
+    /// Board preset, the other board settings override its values.
+    pub preset: Option<BoardPreset>,
+    /// Board width.
+    pub width: Option<u16>,
+    /// Board height.
+    pub height: Option<u16>,
+    /// Bomb count.
+    pub mines: Option<u16>,
+    /// Seed of the bomb placement or of the endless world.
+    pub seed: Option<u64>,
+    /// Game mode, started without going through the menu.
+    pub mode: Option<GameMode>,
+    /// Moves that can be taken back, undo staying disabled if unset.
+    pub undo: Option<UndoPolicy>,
+}
+
+/// Parses a setting value
+fn parse<T: FromStr>(key: &str, value: &str, expected: &'static str) -> Result<T, GameConfigError> {
+    value.parse().map_err(|_| GameConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+        expected,
+    })
+}
+
+/// Decodes a percent-encoded URL query component, `+` standing for a space. Invalid escapes are
+/// kept as they are.
+fn percent_decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            None => {
+                decoded.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+                i += 1;
+            }
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+impl GameConfig {
+    /// Sets a setting from its key and value. Keys are `w` or `width`, `h` or `height`, `mines`,
+    /// `seed`, `mode` (`classic` or `endless`), `preset` (`beginner`, `intermediate` or
+    /// `expert`) and `undo` (`unlimited`, `flags` or `death`, the losing move being undone once
+    /// for `UndoPolicy::DEATH_PENALTY` seconds). No-guess boards are not generated yet, the `noguess`
+    /// mode falls back to `classic`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown keys and invalid values, leaving the config untouched, and
+    /// for unsupported values, after setting their fallback
+    #[inline]
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), GameConfigError> {
+        match key {
+            "w" | "width" => self.width = Some(parse(key, value, "a tile count")?),
+            "h" | "height" => self.height = Some(parse(key, value, "a tile count")?),
+            "mines" => self.mines = Some(parse(key, value, "a bomb count")?),
+            "seed" => self.seed = Some(parse(key, value, "an unsigned integer")?),
+            "mode" => {
+                self.mode = Some(match value.to_lowercase().as_str() {
+                    "classic" => GameMode::Classic,
+                    "endless" => GameMode::Endless,
+                    "noguess" => {
+                        self.mode = Some(GameMode::Classic);
+                        return Err(GameConfigError::Unsupported {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            fallback: "classic",
+                        });
+                    }
+                    _ => {
+                        return Err(GameConfigError::InvalidValue {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            expected: "classic or endless",
+                        })
+                    }
+                });
+            }
+            "preset" => {
+                self.preset = Some(match value.to_lowercase().as_str() {
+                    "beginner" => BoardPreset::Beginner,
+                    "intermediate" => BoardPreset::Intermediate,
+                    "expert" => BoardPreset::Expert,
+                    _ => {
+                        return Err(GameConfigError::InvalidValue {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            expected: "beginner, intermediate or expert",
+                        })
+                    }
+                });
+            }
+            "undo" => {
+                self.undo = Some(match value.to_lowercase().as_str() {
+                    "unlimited" => UndoPolicy::Unlimited,
+                    "flags" => UndoPolicy::FlagsOnly,
+                    "death" => UndoPolicy::AfterDeath {
+                        penalty: UndoPolicy::DEATH_PENALTY,
+                    },
+                    _ => {
+                        return Err(GameConfigError::InvalidValue {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            expected: "unlimited, flags or death",
+                        })
+                    }
+                });
+            }
+            _ => return Err(GameConfigError::UnknownSetting(key.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Parses a URL query such as `?w=30&h=16&mines=99&seed=123&mode=classic&undo=flags`, keys
+    /// and values being percent-decoded and trimmed. Invalid settings are skipped, and an invalid
+    /// or too large board falls back to the default one, the errors being returned along the
+    /// config.
+    #[must_use]
+    pub fn from_query(query: &str) -> (Self, Vec<GameConfigError>) {
+        let mut config = Self::default();
+        let mut errors = Vec::new();
+        let pairs = query
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|pair| !pair.is_empty());
+        for pair in pairs {
+            let result = match pair.split_once('=') {
+                None => Err(GameConfigError::MissingValue(pair.to_string())),
+                Some((key, value)) => {
+                    let (key, value) = (percent_decode(key), percent_decode(value));
+                    config.set(key.trim(), value.trim())
+                }
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+        if let Err(e) = config.validate() {
+            errors.push(e);
+            config.clear_board();
+        }
+        (config, errors)
+    }
+
+    /// Board requested, `None` if no board setting was given
+    #[inline]
+    #[must_use]
+    pub fn preset(&self) -> Option<BoardPreset> {
+        if self.width.is_none() && self.height.is_none() && self.mines.is_none() {
+            return self.preset;
+        }
+        let base = self.preset.unwrap_or_default();
+        let (width, height) = base.map_size();
+        Some(BoardPreset::Custom {
+            width: self.width.unwrap_or(width as u16),
+            height: self.height.unwrap_or(height as u16),
+            bomb_count: self.mines.unwrap_or_else(|| base.bomb_count()),
+        })
+    }
+
+    /// Checks the requested board can be generated and is at most `BoardPreset::MAX_SIZE` tiles
+    /// on a side, as the menu allows
+    ///
+    /// # Errors
+    ///
+    /// Returns the board options error of the requested board, or `BoardTooLarge`
+    #[inline]
+    pub fn validate(&self) -> Result<(), GameConfigError> {
+        let mut options = BoardOptions::default();
+        self.apply(&mut options);
+        let (width, height) = options.map_size;
+        let max = usize::from(BoardPreset::MAX_SIZE);
+        if width > max || height > max {
+            return Err(GameConfigError::BoardTooLarge { width, height });
+        }
+        options.validate().map_err(GameConfigError::InvalidBoard)
+    }
+
+    /// Removes the board settings, falling back to the default board
+    #[inline]
+    pub fn clear_board(&mut self) {
+        self.preset = None;
+        self.width = None;
+        self.height = None;
+        self.mines = None;
+    }
+
+    /// Applies the requested board, seed and undo policy to board options
+    #[inline]
+    pub fn apply(&self, options: &mut BoardOptions) {
+        if let Some(preset) = self.preset() {
+            preset.apply(options);
+        }
+        options.seed = self.seed;
+        if self.undo.is_some() {
+            options.undo = self.undo;
+        }
+    }
+
+    /// Applies the requested seed to endless options
+    #[inline]
+    pub fn apply_endless(&self, options: &mut EndlessOptions) {
+        if let Some(seed) = self.seed {
+            options.seed = seed;
+        }
+    }
+}
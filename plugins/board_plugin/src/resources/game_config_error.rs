@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::resources::{BoardOptionsError, BoardPreset};
+
+/// Reason why a `GameConfig` setting was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameConfigError {
+    /// The setting doesn't exist
+    UnknownSetting(String),
+    /// The setting has no value
+    MissingValue(String),
+    /// The value can't be parsed
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: &'static str,
+    },
+    /// The value is valid but not supported yet, the fallback being used instead
+    Unsupported {
+        key: String,
+        value: String,
+        fallback: &'static str,
+    },
+    /// The requested board is larger than `BoardPreset::MAX_SIZE` tiles on a side
+    BoardTooLarge { width: usize, height: usize },
+    /// The requested board can't be generated
+    InvalidBoard(BoardOptionsError),
+}
+
+impl Display for GameConfigError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnknownSetting(ref key) => write!(f, "unknown setting \"{}\"", key),
+            Self::MissingValue(ref key) => write!(f, "missing value for \"{}\"", key),
+            Self::InvalidValue {
+                ref key,
+                ref value,
+                expected,
+            } => write!(
+                f,
+                "invalid value \"{}\" for \"{}\", expected {}",
+                value, key, expected
+            ),
+            Self::Unsupported {
+                ref key,
+                ref value,
+                fallback,
+            } => write!(
+                f,
+                "\"{}\" for \"{}\" is not supported yet, using {}",
+                value, key, fallback
+            ),
+            Self::BoardTooLarge { width, height } => write!(
+                f,
+                "board of {}x{} tiles is too large, the maximum is {} tiles on a side",
+                width,
+                height,
+                BoardPreset::MAX_SIZE
+            ),
+            Self::InvalidBoard(ref e) => write!(f, "invalid board: {}", e),
+        }
+    }
+}
+
+impl Error for GameConfigError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            Self::InvalidBoard(ref e) => Some(e),
+            Self::UnknownSetting(_)
+            | Self::MissingValue(_)
+            | Self::InvalidValue { .. }
+            | Self::Unsupported { .. }
+            | Self::BoardTooLarge { .. } => None,
+        }
+    }
+}
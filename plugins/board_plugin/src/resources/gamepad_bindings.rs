@@ -0,0 +1,101 @@
+use bevy::prelude::{
+    Axis, Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Input,
+};
+
+/// Gamepad bindings for cursor based play. Must be used as a resource.
+#[derive(Debug, Clone)]
+pub struct GamepadBindings {
+    /// Moves the cursor up.
+    pub up: Vec<GamepadButtonType>,
+    /// Moves the cursor down.
+    pub down: Vec<GamepadButtonType>,
+    /// Moves the cursor left.
+    pub left: Vec<GamepadButtonType>,
+    /// Moves the cursor right.
+    pub right: Vec<GamepadButtonType>,
+    /// Horizontal axes moving the cursor.
+    pub horizontal_axes: Vec<GamepadAxisType>,
+    /// Vertical axes moving the cursor.
+    pub vertical_axes: Vec<GamepadAxisType>,
+    /// How far an axis must be pushed to move the cursor.
+    pub axis_threshold: f32,
+    /// Uncovers the tile under the cursor.
+    pub reveal: Vec<GamepadButtonType>,
+    /// Marks or unmarks the tile under the cursor.
+    pub flag: Vec<GamepadButtonType>,
+    /// Uncovers the neighbours of the revealed number under the cursor.
+    pub chord: Vec<GamepadButtonType>,
+    /// Seconds a direction is held before the cursor starts repeating.
+    pub repeat_delay: f32,
+    /// Seconds between two repeated cursor moves.
+    pub repeat_interval: f32,
+}
+
+impl GamepadBindings {
+    /// Movement direction of the held buttons and pushed axes of a gamepad
+    #[inline]
+    #[must_use]
+    pub fn direction(
+        &self,
+        gamepad: Gamepad,
+        buttons: &Input<GamepadButton>,
+        axes: &Axis<GamepadAxis>,
+    ) -> (i8, i8) {
+        let pressed = |types: &[GamepadButtonType]| {
+            buttons.any_pressed(types.iter().map(|&t| GamepadButton(gamepad, t)))
+        };
+        let tilted = |types: &[GamepadAxisType]| {
+            types
+                .iter()
+                .filter_map(|&t| axes.get(GamepadAxis(gamepad, t)))
+                .find(|value| value.abs() >= self.axis_threshold)
+                .map_or(0, |value| if value > 0.0 { 1 } else { -1 })
+        };
+        let along = |negative: &[GamepadButtonType], positive: &[GamepadButtonType], types| {
+            let buttons = i8::from(pressed(positive)) - i8::from(pressed(negative));
+            if buttons == 0 {
+                tilted(types)
+            } else {
+                buttons
+            }
+        };
+        (
+            along(&self.left, &self.right, &self.horizontal_axes),
+            along(&self.down, &self.up, &self.vertical_axes),
+        )
+    }
+
+    /// Was any of the `types` buttons of a gamepad pressed this frame
+    #[inline]
+    #[must_use]
+    pub fn just_pressed(
+        gamepad: Gamepad,
+        buttons: &Input<GamepadButton>,
+        types: &[GamepadButtonType],
+    ) -> bool {
+        buttons.any_just_pressed(types.iter().map(|&t| GamepadButton(gamepad, t)))
+    }
+}
+
+impl Default for GamepadBindings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            up: vec![GamepadButtonType::DPadUp],
+            down: vec![GamepadButtonType::DPadDown],
+            left: vec![GamepadButtonType::DPadLeft],
+            right: vec![GamepadButtonType::DPadRight],
+            horizontal_axes: vec![GamepadAxisType::LeftStickX, GamepadAxisType::DPadX],
+            vertical_axes: vec![GamepadAxisType::LeftStickY, GamepadAxisType::DPadY],
+            axis_threshold: 0.5,
+            reveal: vec![GamepadButtonType::South],
+            flag: vec![GamepadButtonType::East, GamepadButtonType::West],
+            chord: vec![
+                GamepadButtonType::LeftTrigger,
+                GamepadButtonType::RightTrigger,
+            ],
+            repeat_delay: 0.3,
+            repeat_interval: 0.08,
+        }
+    }
+}
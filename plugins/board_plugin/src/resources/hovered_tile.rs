@@ -0,0 +1,6 @@
+use crate::resources::TilePick;
+
+/// Tile under the mouse cursor, if any.
+/// Kept up to date by `systems::input::track_hover`, which sends a `TileHoverEvent` on changes.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HoveredTile(pub Option<TilePick>);
@@ -0,0 +1,72 @@
+use bevy::prelude::{Input, KeyCode};
+
+/// Keyboard bindings for cursor based play. Must be used as a resource.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    /// Moves the cursor (or the camera on endless boards) up.
+    pub up: Vec<KeyCode>,
+    /// Moves the cursor (or the camera on endless boards) down.
+    pub down: Vec<KeyCode>,
+    /// Moves the cursor (or the camera on endless boards) left.
+    pub left: Vec<KeyCode>,
+    /// Moves the cursor (or the camera on endless boards) right.
+    pub right: Vec<KeyCode>,
+    /// Uncovers the tile under the cursor.
+    pub reveal: Vec<KeyCode>,
+    /// Marks or unmarks the tile under the cursor.
+    pub flag: Vec<KeyCode>,
+    /// Uncovers the neighbours of the revealed number under the cursor.
+    pub chord: Vec<KeyCode>,
+    /// Takes back the last move, when the board has an undo policy.
+    pub undo: Vec<KeyCode>,
+    /// Plays the last undone move again.
+    pub redo: Vec<KeyCode>,
+    /// Switches to the next theme pack.
+    pub theme: Vec<KeyCode>,
+    /// Switches to the next accessibility palette.
+    pub palette: Vec<KeyCode>,
+    /// Toggles the bomb counter glyphs.
+    pub glyphs: Vec<KeyCode>,
+    /// Fits the camera to the board.
+    pub fit: Vec<KeyCode>,
+    /// Seconds a movement key is held before the cursor starts repeating.
+    pub repeat_delay: f32,
+    /// Seconds between two repeated cursor moves.
+    pub repeat_interval: f32,
+}
+
+impl KeyBindings {
+    /// Movement direction of the held movement keys
+    #[inline]
+    #[must_use]
+    pub fn direction(&self, input: &Input<KeyCode>) -> (i8, i8) {
+        let axis = |negative: &[KeyCode], positive: &[KeyCode]| {
+            i8::from(input.any_pressed(positive.iter().copied()))
+                - i8::from(input.any_pressed(negative.iter().copied()))
+        };
+        (axis(&self.left, &self.right), axis(&self.down, &self.up))
+    }
+}
+
+impl Default for KeyBindings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            up: vec![KeyCode::Up, KeyCode::W],
+            down: vec![KeyCode::Down, KeyCode::S],
+            left: vec![KeyCode::Left, KeyCode::A],
+            right: vec![KeyCode::Right, KeyCode::D],
+            reveal: vec![KeyCode::Space, KeyCode::Return],
+            flag: vec![KeyCode::X],
+            chord: vec![KeyCode::Z],
+            undo: vec![KeyCode::U, KeyCode::Back],
+            redo: vec![KeyCode::Y],
+            theme: vec![KeyCode::T],
+            palette: vec![KeyCode::P],
+            glyphs: vec![KeyCode::G],
+            fit: vec![KeyCode::F],
+            repeat_delay: 0.25,
+            repeat_interval: 0.06,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Mark placed by the player on a covered tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Mark {
+    /// The tile is believed to be a bomb, it can't be uncovered
+    Flag,
+    /// The tile is uncertain, it can still be uncovered
+    Question,
+}
+
+impl Mark {
+    /// Next mark in the right click cycle, `None` being unmarked
+    #[inline]
+    #[must_use]
+    pub const fn cycle(mark: Option<Self>, question_marks: bool) -> Option<Self> {
+        match mark {
+            None => Some(Self::Flag),
+            Some(Self::Flag) if question_marks => Some(Self::Question),
+            Some(Self::Flag | Self::Question) => None,
+        }
+    }
+}
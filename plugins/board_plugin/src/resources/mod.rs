@@ -1,11 +1,66 @@
-pub use board::Board;
+pub use accessibility_options::AccessibilityOptions;
+pub use animation_options::AnimationOptions;
+pub use board::{Board, TilePick};
 pub use board_assets::{BoardAssets, SpriteMaterial};
-pub use board_options::{BoardOptions, BoardPosition, TileSize};
+pub use board_cursor::{BoardCursor, CursorRepeat};
+pub use board_history::{BoardChange, BoardHistory, UndoPolicy};
+pub use board_options::{BoardOptions, BoardPosition, TileRendering, TileSize};
+pub use board_options_error::BoardOptionsError;
+pub use board_preset::BoardPreset;
+pub use board_save::BoardSave;
+pub use board_status::{BoardState, BoardStatus};
+pub use board_text_error::{BoardTextError, BoardTextErrorKind};
+pub use board_theme::{BoardTheme, ThemeMaterial};
+pub use board_themes::BoardThemes;
+pub use camera_options::CameraOptions;
+pub use chunk::Chunk;
+pub use endless_board::EndlessBoard;
+pub use endless_options::EndlessOptions;
+pub use game_bridge::{BridgeEvent, BridgeRequest, GameBridge, GameSnapshot};
+pub use game_config::{GameConfig, GameMode};
+pub use game_config_error::GameConfigError;
+pub use gamepad_bindings::GamepadBindings;
+pub use hovered_tile::HoveredTile;
+pub use key_bindings::KeyBindings;
+pub use mark::Mark;
+pub use palette::{contrast_ratio, relative_luminance, Palette};
+pub use replay::{Move, Replay, ReplayPlayer, ReplayRecorder};
 pub use tile::Tile;
+pub use tile_atlas::{AtlasCell, TileAtlas};
 pub use tile_map::TileMap;
+pub use tile_press::TilePress;
+pub use touch_options::TouchOptions;
 
+mod accessibility_options;
+mod animation_options;
 mod board;
 mod board_assets;
+mod board_cursor;
+mod board_history;
 mod board_options;
+mod board_options_error;
+mod board_preset;
+mod board_save;
+mod board_status;
+mod board_text;
+mod board_text_error;
+mod board_theme;
+mod board_themes;
+mod camera_options;
+mod chunk;
+mod endless_board;
+mod endless_options;
+mod game_bridge;
+mod game_config;
+mod game_config_error;
+mod gamepad_bindings;
+mod hovered_tile;
+mod key_bindings;
+mod mark;
+mod palette;
+mod replay;
 mod tile;
+mod tile_atlas;
 mod tile_map;
+mod tile_press;
+mod touch_options;
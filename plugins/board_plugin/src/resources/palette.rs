@@ -0,0 +1,152 @@
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::BoardAssets;
+
+/// Bomb counter and tile colour palettes, including colour-blind friendly and high contrast
+/// variants. Every palette has one colour per possible bomb count.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Palette {
+    Standard,
+    /// Tuned for missing green cones
+    Deuteranopia,
+    /// Tuned for missing red cones
+    Protanopia,
+    /// Tuned for missing blue cones
+    Tritanopia,
+    /// Bright colours on black tiles
+    HighContrast,
+}
+
+impl Default for Palette {
+    #[inline]
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+impl Palette {
+    /// Every palette, in switching order
+    pub const ALL: [Self; 5] = [
+        Self::Standard,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+        Self::HighContrast,
+    ];
+
+    /// Bomb counter colours, from one neighbouring bomb to eight
+    #[inline]
+    #[must_use]
+    pub const fn colours(self) -> [Color; 8] {
+        match self {
+            Self::Standard => [
+                Color::rgb(0.4, 0.7, 1.0),
+                Color::rgb(0.3, 0.9, 0.3),
+                Color::rgb(1.0, 0.4, 0.4),
+                Color::rgb(0.75, 0.55, 1.0),
+                Color::rgb(1.0, 0.65, 0.0),
+                Color::rgb(0.2, 0.9, 0.9),
+                Color::rgb(1.0, 1.0, 1.0),
+                Color::rgb(0.75, 0.75, 0.75),
+            ],
+            Self::Deuteranopia => [
+                Color::rgb(0.0, 0.75, 1.0),
+                Color::rgb(1.0, 0.75, 0.0),
+                Color::rgb(0.75, 0.75, 0.75),
+                Color::rgb(0.75, 0.5, 0.75),
+                Color::rgb(0.75, 1.0, 1.0),
+                Color::rgb(0.0, 0.75, 0.25),
+                Color::rgb(1.0, 1.0, 0.75),
+                Color::rgb(0.75, 1.0, 0.5),
+            ],
+            Self::Protanopia => [
+                Color::rgb(0.5, 0.75, 1.0),
+                Color::rgb(0.25, 1.0, 0.25),
+                Color::rgb(1.0, 0.25, 0.25),
+                Color::rgb(1.0, 0.25, 0.75),
+                Color::rgb(0.5, 0.75, 0.25),
+                Color::rgb(0.5, 1.0, 0.75),
+                Color::rgb(1.0, 1.0, 1.0),
+                Color::rgb(1.0, 0.75, 0.75),
+            ],
+            Self::Tritanopia => [
+                Color::rgb(0.75, 1.0, 1.0),
+                Color::rgb(0.0, 1.0, 0.25),
+                Color::rgb(1.0, 0.25, 0.5),
+                Color::rgb(0.75, 0.5, 0.75),
+                Color::rgb(1.0, 0.75, 0.25),
+                Color::rgb(0.5, 0.75, 0.0),
+                Color::rgb(1.0, 1.0, 0.75),
+                Color::rgb(0.75, 1.0, 0.0),
+            ],
+            Self::HighContrast => [
+                Color::rgb(0.4, 0.8, 1.0),
+                Color::rgb(0.3, 1.0, 0.3),
+                Color::rgb(1.0, 0.55, 0.55),
+                Color::rgb(1.0, 1.0, 0.2),
+                Color::rgb(1.0, 0.6, 1.0),
+                Color::rgb(0.3, 1.0, 1.0),
+                Color::rgb(1.0, 1.0, 1.0),
+                Color::rgb(1.0, 0.75, 0.3),
+            ],
+        }
+    }
+
+    /// Colour of revealed tiles, the counters are drawn on it
+    #[inline]
+    #[must_use]
+    pub const fn tile_colour(self) -> Color {
+        match self {
+            Self::HighContrast => Color::BLACK,
+            _ => Color::DARK_GRAY,
+        }
+    }
+
+    /// Colour of covered tiles
+    #[inline]
+    #[must_use]
+    pub const fn covered_tile_colour(self) -> Color {
+        match self {
+            Self::HighContrast => Color::rgb(0.85, 0.85, 0.85),
+            _ => Color::GRAY,
+        }
+    }
+
+    /// Next palette, wrapping around
+    #[inline]
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Standard => Self::Deuteranopia,
+            Self::Deuteranopia => Self::Protanopia,
+            Self::Protanopia => Self::Tritanopia,
+            Self::Tritanopia => Self::HighContrast,
+            Self::HighContrast => Self::Standard,
+        }
+    }
+
+    /// Overrides the counter and tile colours of the board assets
+    #[inline]
+    pub fn apply(self, board_assets: &mut BoardAssets) {
+        board_assets.bomb_counter_colours = self.colours().to_vec();
+        board_assets.tile_material.colour = self.tile_colour();
+        board_assets.covered_tile_material.colour = self.covered_tile_colour();
+    }
+}
+
+/// Relative luminance of a colour, as defined by WCAG 2
+#[inline]
+#[must_use]
+pub fn relative_luminance(colour: Color) -> f32 {
+    let [red, green, blue, _] = colour.as_linear_rgba_f32();
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+/// WCAG 2 contrast ratio between two colours, from 1 (none) to 21 (black on white)
+#[inline]
+#[must_use]
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::Coordinates,
+    resources::{BoardSave, UndoPolicy},
+};
+
+/// Player action on a board tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Move {
+    /// The tile is uncovered
+    Trigger(Coordinates),
+    /// The tile mark is cycled
+    Mark(Coordinates),
+    /// The covered neighbours of the tile are uncovered
+    Chord(Coordinates),
+    /// The last move is taken back
+    Undo,
+    /// The last undone move is played again
+    Redo,
+}
+
+impl Move {
+    /// Undo and redo moves, played once the previous moves are handled, even if the game is over
+    #[inline]
+    #[must_use]
+    pub const fn is_history(self) -> bool {
+        matches!(self, Self::Undo | Self::Redo)
+    }
+}
+
+/// Recorded classic board game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// Board at the start of the recording.
+    pub board: BoardSave,
+    /// Moves with the game time they were played at, in seconds.
+    pub moves: Vec<(f32, Move)>,
+    /// Undo policy of the recorded game, needed to play back its undo and redo moves.
+    #[serde(default)]
+    pub undo: Option<UndoPolicy>,
+}
+
+/// Records the moves of the current game. Must be used as a resource.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    /// Recording of the current game, `None` until a board is created.
+    pub replay: Option<Replay>,
+}
+
+/// Plays back the moves of a replay on the current game. Must be used as a resource, along the
+/// replay board inserted as a `BoardSave`.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    moves: VecDeque<(f32, Move)>,
+    /// Plays the moves at their recorded time, or one per frame.
+    pub realtime: bool,
+    /// Frames since the last move when not in real time
+    idle_frames: u32,
+}
+
+/// Frames for a move to be handled before an undo or redo is played out of real time, the
+/// trigger, uncover and status updates each taking one
+const SETTLE_FRAMES: u32 = 3;
+
+impl ReplayPlayer {
+    #[inline]
+    #[must_use]
+    pub fn new(replay: &Replay, realtime: bool) -> Self {
+        Self {
+            moves: replay.moves.iter().copied().collect(),
+            realtime,
+            idle_frames: 0,
+        }
+    }
+
+    /// Takes the next move due at the `elapsed` game time. Out of real time, a move is due each
+    /// call and undo or redo moves wait for the previous move to settle.
+    #[inline]
+    pub fn next_move(&mut self, elapsed: f32) -> Option<Move> {
+        let (time, next) = *self.moves.front()?;
+        if self.realtime {
+            if time > elapsed {
+                return None;
+            }
+        } else if next.is_history() && self.idle_frames < SETTLE_FRAMES {
+            self.idle_frames += 1;
+            return None;
+        }
+        self.idle_frames = 0;
+        self.moves.pop_front().map(|(_, m)| m)
+    }
+
+    /// Next move to play, without taking it
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<Move> {
+        self.moves.front().map(|&(_, m)| m)
+    }
+
+    /// Every move was played
+    #[inline]
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    Extent3d, FilterMode, SamplerDescriptor, TextureDimension, TextureFormat,
+};
+
+use crate::{
+    components::Coordinates,
+    resources::{Board, BoardAssets, Mark, Tile},
+};
+
+/// Side of an atlas cell in pixels
+const CELL_PIXELS: usize = 32;
+/// Atlas cells per row and column
+const CELLS: usize = 4;
+/// Scale of the 5x7 glyphs drawn in the cells
+const GLYPH_SCALE: usize = 3;
+
+/// 5x7 glyphs of the bomb counters from 1 to 8, then the question mark, top row first
+const GLYPHS: [[&str; 7]; 9] = [
+    [
+        "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+    ],
+    [
+        ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+    ],
+    [
+        ".###.", "#...#", "....#", "..##.", "....#", "#...#", ".###.",
+    ],
+    [
+        "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+    ],
+    [
+        "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+    ],
+    [
+        ".###.", "#....", "#....", "####.", "#...#", "#...#", ".###.",
+    ],
+    [
+        "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+    ],
+    [
+        ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+    ],
+    [
+        ".###.", "#...#", "....#", "...#.", "..#..", ".....", "..#..",
+    ],
+];
+
+/// Cell of the tile atlas, the look of a tile in a given state
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AtlasCell {
+    Covered,
+    Flag,
+    Question,
+    Empty,
+    BombCounter(u8),
+    Bomb,
+}
+
+impl AtlasCell {
+    /// Look of a board tile
+    #[inline]
+    #[must_use]
+    pub fn of(board: &Board, coords: Coordinates) -> Self {
+        if board.covered_tiles.contains_key(&coords) {
+            return match board.marked_tiles.get(&coords) {
+                None => Self::Covered,
+                Some(Mark::Flag) => Self::Flag,
+                Some(Mark::Question) => Self::Question,
+            };
+        }
+        match board.tile_map.map()[(coords.x as usize, coords.y as usize)] {
+            Tile::Bomb => Self::Bomb,
+            Tile::BombNeighbor(count) => Self::BombCounter(count),
+            Tile::Empty => Self::Empty,
+        }
+    }
+
+    /// Index of the cell in the atlas, row by row from the top left
+    #[inline]
+    #[must_use]
+    pub fn index(self) -> usize {
+        match self {
+            Self::Covered => 0,
+            Self::Flag => 1,
+            Self::Question => 2,
+            Self::Empty => 3,
+            Self::BombCounter(count) => 3 + usize::from(count.clamp(1, 8)),
+            Self::Bomb => 12,
+        }
+    }
+
+    /// Texture coordinates of the top left and bottom right corners of the cell, inset by half a
+    /// pixel so neighbouring cells never bleed in
+    #[inline]
+    #[must_use]
+    pub fn uv_rect(self) -> (Vec2, Vec2) {
+        let index = self.index();
+        let size = (CELLS * CELL_PIXELS) as f32;
+        let min = Vec2::new(
+            ((index % CELLS) * CELL_PIXELS) as f32 + 0.5,
+            ((index / CELLS) * CELL_PIXELS) as f32 + 0.5,
+        );
+        let max = min + Vec2::splat(CELL_PIXELS as f32 - 1.0);
+        (min / size, max / size)
+    }
+}
+
+/// Generated tile atlas texture and material of the mesh rendered boards. Must be used as a
+/// resource.
+#[derive(Debug, Clone)]
+pub struct TileAtlas {
+    pub image: Handle<Image>,
+    pub material: Handle<ColorMaterial>,
+}
+
+impl TileAtlas {
+    /// Draws the atlas image from the board asset colours
+    #[must_use]
+    pub fn image(board_assets: &BoardAssets) -> Image {
+        let side = CELLS * CELL_PIXELS;
+        let mut data = vec![0; side * side * 4];
+        let mut cell =
+            |cell: AtlasCell, background: Color, draw: &dyn Fn(usize, usize) -> Option<Color>| {
+                let (x0, y0) = (
+                    (cell.index() % CELLS) * CELL_PIXELS,
+                    (cell.index() / CELLS) * CELL_PIXELS,
+                );
+                for y in 0..CELL_PIXELS {
+                    for x in 0..CELL_PIXELS {
+                        let colour = draw(x, y).unwrap_or(background);
+                        let i = ((y0 + y) * side + x0 + x) * 4;
+                        data[i..i + 4].copy_from_slice(&colour.as_rgba_u32().to_le_bytes());
+                    }
+                }
+            };
+        let covered = board_assets.covered_tile_material.colour;
+        let tile = board_assets.tile_material.colour;
+        let glyph = |index: usize, colour: Color| {
+            move |x: usize, y: usize| {
+                let (width, height) = (5 * GLYPH_SCALE, 7 * GLYPH_SCALE);
+                let (left, top) = ((CELL_PIXELS - width) / 2, (CELL_PIXELS - height) / 2);
+                if x < left || y < top || x >= left + width || y >= top + height {
+                    return None;
+                }
+                let row = GLYPHS[index][(y - top) / GLYPH_SCALE].as_bytes();
+                (row[(x - left) / GLYPH_SCALE] == b'#').then_some(colour)
+            }
+        };
+
+        cell(AtlasCell::Covered, covered, &|_, _| None);
+        cell(AtlasCell::Flag, covered, &|x, y| {
+            let flag = board_assets.flag_material.colour;
+            // Pole, then a triangle pointing right from its top
+            let pole = (10..12).contains(&x) && (6..26).contains(&y);
+            let cloth =
+                x >= 12 && y >= 6 && y < 16 && x - 12 < 10 - (y as isize - 11).unsigned_abs() * 2;
+            (pole || cloth).then_some(flag)
+        });
+        cell(
+            AtlasCell::Question,
+            covered,
+            &glyph(8, board_assets.question_material.colour),
+        );
+        cell(AtlasCell::Empty, tile, &|_, _| None);
+        for count in 1..=8 {
+            cell(
+                AtlasCell::BombCounter(count),
+                tile,
+                &glyph(
+                    usize::from(count) - 1,
+                    board_assets.bomb_counter_color(count),
+                ),
+            );
+        }
+        cell(AtlasCell::Bomb, tile, &|x, y| {
+            let centre = CELL_PIXELS as f32 * 0.5;
+            let offset = Vec2::new(x as f32 + 0.5 - centre, y as f32 + 0.5 - centre);
+            (offset.length() < centre * 0.6).then_some(board_assets.bomb_material.colour)
+        });
+
+        let mut image = Image::new(
+            Extent3d {
+                width: side as u32,
+                height: side as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        image.sampler_descriptor = SamplerDescriptor {
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..SamplerDescriptor::default()
+        };
+        image
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::VecDeque;
+
+use bevy::log;
 use ndarray::Array2;
 use rand::{thread_rng, Rng};
 
@@ -37,9 +40,26 @@ impl TileMap {
     /// Places bombs and bomb neighbor tiles.
     #[inline]
     pub fn set_bombs(&mut self, bomb_count: u16) {
+        self.set_bombs_with_rng(bomb_count, &mut thread_rng());
+    }
+
+    /// Places bombs and bomb neighbor tiles using the given random generator.
+    /// The bomb count is capped to the number of tiles.
+    #[inline]
+    pub fn set_bombs_with_rng<R: Rng>(&mut self, bomb_count: u16, rng: &mut R) {
+        let cells = self.width() * self.height();
+        let bomb_count = if usize::from(bomb_count) > cells {
+            log::warn!(
+                "{} bombs don't fit on {} tiles, filling the board",
+                bomb_count,
+                cells
+            );
+            cells as u16
+        } else {
+            bomb_count
+        };
         self.bomb_count = bomb_count;
         let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
 
         // Place bombs
         while remaining_bombs > 0 {
@@ -53,7 +73,37 @@ impl TileMap {
             }
         }
 
-        // Place bomb neighbors
+        self.set_bomb_neighbours();
+    }
+
+    /// Places bombs at the given coordinates and bomb neighbor tiles.
+    /// Coordinates outside of the map and duplicates are skipped, and so are the bombs past the
+    /// bomb count limit.
+    #[inline]
+    pub fn set_bombs_at<I: IntoIterator<Item = Coordinates>>(&mut self, bombs: I) {
+        for coords in bombs {
+            if !self.contains(coords) {
+                log::warn!("Skipping bomb {} outside of the map", coords);
+                continue;
+            }
+            let (x, y) = (coords.x as usize, coords.y as usize);
+            if self.map[(x, y)] == Tile::Bomb {
+                continue;
+            }
+            self.bomb_count = match self.bomb_count.checked_add(1) {
+                None => {
+                    log::warn!("More than {} bombs, skipping the others", u16::MAX);
+                    break;
+                }
+                Some(count) => count,
+            };
+            self.map[(x, y)] = Tile::Bomb;
+        }
+        self.set_bomb_neighbours();
+    }
+
+    /// Places bomb neighbor tiles around the bombs
+    fn set_bomb_neighbours(&mut self) {
         for y in 0..self.height() {
             for x in 0..self.width() {
                 let coords = Coordinates::new(x as u16, y as u16);
@@ -119,21 +169,43 @@ impl TileMap {
         &self.map
     }
 
+    /// First empty tile row by row from the bottom left, used as the safe start
+    #[inline]
+    #[must_use]
+    pub fn first_empty_tile(&self) -> Option<Coordinates> {
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| (x, y)))
+            .find(|&(x, y)| self.map[(x, y)] == Tile::Empty)
+            .map(|(x, y)| Coordinates::new(x as u16, y as u16))
+    }
+
+    /// Overwrites a single tile, leaving the bomb count untouched.
     #[inline]
-    pub fn safe_square_at(coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
+    pub fn set_tile(&mut self, coordinates: Coordinates, tile: Tile) {
+        self.map[(coordinates.x as usize, coordinates.y as usize)] = tile;
+    }
+
+    /// Are the `coordinates` inside the map
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, coordinates: Coordinates) -> bool {
+        usize::from(coordinates.x) < self.width() && usize::from(coordinates.y) < self.height()
+    }
+
+    /// The up to 8 neighbours of `coordinates` that are inside the map
+    #[inline]
+    pub fn neighbours(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
         SQUARE_COORDINATES
             .iter()
-            .copied()
-            .map(move |offset| coordinates + offset)
+            .filter_map(move |&offset| coordinates.checked_offset(offset))
+            .filter(move |&neighbour| self.contains(neighbour))
     }
 
     #[inline]
     #[must_use]
     pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
-        if coordinates.x as usize >= self.width() || coordinates.y as usize >= self.height() {
-            return false;
-        };
-        self.map[(coordinates.x as usize, coordinates.y as usize)].is_bomb()
+        self.contains(coordinates)
+            && self.map[(coordinates.x as usize, coordinates.y as usize)].is_bomb()
     }
 
     #[inline]
@@ -143,8 +215,47 @@ impl TileMap {
             return 0;
         }
 
-        Self::safe_square_at(coordinates)
+        self.neighbours(coordinates)
             .filter(|coord| self.is_bomb_at(*coord))
             .count() as u8
     }
+
+    /// Tiles revealed by uncovering `starts`, with their distance in rings from the nearest start.
+    /// Reveals spread from empty tiles to the neighbours for which `covered` holds, in a single
+    /// breadth first pass. Starts that are out of bounds or not covered are skipped.
+    #[inline]
+    #[must_use]
+    pub fn flood_fill<I, F>(&self, starts: I, covered: F) -> Vec<(Coordinates, u32)>
+    where
+        I: IntoIterator<Item = Coordinates>,
+        F: Fn(Coordinates) -> bool,
+    {
+        let mut visited = Array2::from_elem((self.width(), self.height()), false);
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if self.contains(start)
+                && covered(start)
+                && !visited[(start.x as usize, start.y as usize)]
+            {
+                visited[(start.x as usize, start.y as usize)] = true;
+                queue.push_back((start, 0));
+            }
+        }
+
+        let mut revealed = Vec::with_capacity(queue.len());
+        while let Some((coords, depth)) = queue.pop_front() {
+            revealed.push((coords, depth));
+            if self.map[(coords.x as usize, coords.y as usize)] != Tile::Empty {
+                continue;
+            }
+            for neighbour in self.neighbours(coords) {
+                let index = (neighbour.x as usize, neighbour.y as usize);
+                if !visited[index] && covered(neighbour) {
+                    visited[index] = true;
+                    queue.push_back((neighbour, depth + 1));
+                }
+            }
+        }
+        revealed
+    }
 }
@@ -0,0 +1,50 @@
+use bevy::prelude::MouseButton;
+
+use crate::{components::Coordinates, resources::Board};
+
+/// Mouse button held down over a tile. The tile is only acted upon when the button is released
+/// over it, dragging off the tile cancels the press.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct TilePress {
+    pub press: Option<(MouseButton, Coordinates)>,
+}
+
+impl TilePress {
+    /// Is the press a chord: a middle click, or a left click on an uncovered tile
+    #[inline]
+    #[must_use]
+    pub fn is_chord(&self, board: &Board) -> bool {
+        match self.press {
+            Some((MouseButton::Middle, _)) => true,
+            Some((MouseButton::Left, coords)) => {
+                board.tile_map.contains(coords) && !board.covered_tiles.contains_key(&coords)
+            }
+            _ => false,
+        }
+    }
+
+    /// Tiles shown pressed while the cursor is over `hovered`: the pressed tile when it can be
+    /// uncovered, or every neighbour that can be uncovered when chording.
+    /// Nothing is shown pressed while the cursor is off the pressed tile.
+    #[inline]
+    #[must_use]
+    pub fn preview(&self, board: &Board, hovered: Option<Coordinates>) -> Vec<Coordinates> {
+        let coords = match self.press {
+            Some((_, coords)) if Some(coords) == hovered => coords,
+            _ => return vec![],
+        };
+        if self.is_chord(board) {
+            board
+                .tile_map
+                .neighbours(coords)
+                .filter(|c| board.tile_to_uncover(c).is_some())
+                .collect()
+        } else {
+            board
+                .tile_to_uncover(&coords)
+                .map(|_| coords)
+                .into_iter()
+                .collect()
+        }
+    }
+}
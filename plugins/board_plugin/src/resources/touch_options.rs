@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Touch screen gesture options. Must be used as a resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TouchOptions {
+    /// Seconds a finger is held still on a tile before it is marked.
+    pub long_press_duration: f32,
+    /// Distance in window pixels a finger may move before a press stops counting as a tap.
+    pub tap_distance: f32,
+}
+
+impl Default for TouchOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            long_press_duration: 0.5,
+            tap_distance: 10.0,
+        }
+    }
+}
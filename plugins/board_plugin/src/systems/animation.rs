@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{ExplosionAnimation, MarkAnimation, RevealAnimation, Uncover},
+    resources::AnimationOptions,
+};
+
+/// Colour of the explosion flash
+const EXPLOSION_COLOUR: Color = Color::rgba(1.0, 0.5, 0.0, 0.8);
+
+/// Progress of an animation from 0 to 1, finished straight away when animations are disabled
+fn progress(elapsed: f32, duration: f32, options: &AnimationOptions) -> f32 {
+    if !options.enabled || duration <= 0.0 {
+        return 1.0;
+    }
+    (elapsed / duration).clamp(0.0, 1.0)
+}
+
+/// Removes the cover of an uncovered tile, `depth` being its distance in rings from the tile the
+/// reveal started on
+#[inline]
+pub fn reveal(commands: &mut Commands, cover: Entity, depth: u32, options: &AnimationOptions) {
+    if options.enabled {
+        commands
+            .entity(cover)
+            .remove::<Uncover>()
+            .insert(RevealAnimation {
+                delay: depth as f32 * options.reveal_stagger,
+                elapsed: 0.0,
+            });
+    } else {
+        commands.entity(cover).despawn_recursive();
+    }
+}
+
+/// Spawns an explosion flash as a child of `parent`, at `position` in its space
+#[inline]
+pub fn explode(
+    commands: &mut Commands,
+    parent: Entity,
+    position: Vec3,
+    size: f32,
+    options: &AnimationOptions,
+) {
+    if !options.enabled {
+        return;
+    }
+    commands.entity(parent).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(size)),
+                    color: EXPLOSION_COLOUR,
+                    ..default()
+                },
+                transform: Transform::from_translation(position),
+                ..default()
+            })
+            .insert(Name::new("Explosion"))
+            .insert(ExplosionAnimation::default());
+    });
+}
+
+/// Starts a mark sprite bundle at zero scale and returns its animation, if animations are enabled
+#[inline]
+pub fn pop_in(bundle: &mut SpriteBundle, options: &AnimationOptions) -> Option<MarkAnimation> {
+    options.enabled.then(|| {
+        bundle.transform.scale = Vec3::ZERO;
+        MarkAnimation::default()
+    })
+}
+
+/// Shrinks and fades out the covers of uncovered tiles, despawning them when done
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn animate_reveal(
+    mut commands: Commands,
+    time: Res<Time>,
+    animation_options: Option<Res<AnimationOptions>>,
+    mut covers: Query<(Entity, &mut RevealAnimation, &mut Transform, &mut Sprite)>,
+) {
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    for (entity, mut animation, mut transform, mut sprite) in covers.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        let t = progress(
+            animation.elapsed - animation.delay,
+            options.reveal_duration,
+            &options,
+        );
+        if t >= 1.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.scale = Vec3::splat(1.0 - t);
+        sprite.color.set_a(1.0 - t);
+    }
+}
+
+/// Grows and fades out explosion flashes, despawning them when done
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn animate_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    animation_options: Option<Res<AnimationOptions>>,
+    mut flashes: Query<(Entity, &mut ExplosionAnimation, &mut Transform, &mut Sprite)>,
+) {
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    for (entity, mut animation, mut transform, mut sprite) in flashes.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        let t = progress(animation.elapsed, options.explosion_duration, &options);
+        if t >= 1.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.scale = Vec3::splat(1.0 + 2.0 * t);
+        sprite.color.set_a(EXPLOSION_COLOUR.a() * (1.0 - t));
+    }
+}
+
+/// Pops mark sprites in with a slight overshoot
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn animate_marks(
+    mut commands: Commands,
+    time: Res<Time>,
+    animation_options: Option<Res<AnimationOptions>>,
+    mut marks: Query<(Entity, &mut MarkAnimation, &mut Transform)>,
+) {
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    for (entity, mut animation, mut transform) in marks.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        let t = progress(animation.elapsed, options.mark_duration, &options);
+        if t >= 1.0 {
+            commands.entity(entity).remove::<MarkAnimation>();
+            transform.scale = Vec3::ONE;
+            continue;
+        }
+        // Ease out back
+        let u = t - 1.0;
+        transform.scale = Vec3::splat(1.0 + 2.70158 * u * u * u + 1.70158 * u * u);
+    }
+}
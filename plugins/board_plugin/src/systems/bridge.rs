@@ -0,0 +1,91 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+
+use crate::{
+    bridge::BridgeTarget,
+    events::TilesUncoveredEvent,
+    resources::{
+        BoardPreset, BoardSave, BoardStatus, BridgeEvent, BridgeRequest, GameBridge, GameSnapshot,
+    },
+    Board, BoardAssets, BoardOptions,
+};
+
+/// Starts the games requested by the host, once the board assets are loaded
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_requests<T: StateData>(
+    mut commands: Commands,
+    bridge: Res<GameBridge>,
+    target: Res<BridgeTarget<T>>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Option<Res<BoardAssets>>,
+    mut state: ResMut<State<T>>,
+) {
+    if board_assets.is_none() || !bridge.has_request() {
+        return;
+    }
+    let mut options = board_options.as_deref().cloned().unwrap_or_default();
+    match bridge.take_request() {
+        None => return,
+        Some(BridgeRequest::Start { preset, seed }) => {
+            log::info!("Starting {} game from the host", preset);
+            preset.apply(&mut options);
+            options.seed = seed;
+            commands.insert_resource(preset);
+            commands.remove_resource::<BoardSave>();
+        }
+        Some(BridgeRequest::Restore(save)) => {
+            log::info!("Restoring game from the host");
+            save.apply(&mut options);
+            options.seed = None;
+            commands.insert_resource(BoardPreset::Custom {
+                width: save.map_size.0,
+                height: save.map_size.1,
+                bomb_count: options.bomb_count,
+            });
+            commands.insert_resource(save);
+        }
+    }
+    commands.insert_resource(options);
+    let result = if state.current() == &target.0 {
+        state.restart()
+    } else {
+        state.set(target.0.clone())
+    };
+    if let Err(e) = result {
+        log::error!("Failed to start the requested game: {}", e);
+    }
+}
+
+/// Publishes the current game state, save and reveals to the host
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn publish_state(
+    bridge: Res<GameBridge>,
+    board: Option<Res<Board>>,
+    status: Option<Res<BoardStatus>>,
+    mut tiles_uncovered_evr: EventReader<TilesUncoveredEvent>,
+) {
+    for event in tiles_uncovered_evr.iter() {
+        bridge.notify(BridgeEvent::Revealed(event.0.clone()));
+    }
+    let (board, status) = match (board, status) {
+        (Some(b), Some(s)) => (b, s),
+        _ => {
+            bridge.publish(None);
+            return;
+        }
+    };
+    // Saves are only rebuilt when the board changes, the time is taken from the snapshot
+    if board.is_changed() {
+        bridge.publish_save(BoardSave::new(&board, &status));
+    }
+    bridge.publish(Some(GameSnapshot {
+        state: status.state,
+        mines_left: board.remaining_bombs(),
+        elapsed: status.timer.elapsed_secs(),
+        map_size: (
+            board.tile_map.width() as u16,
+            board.tile_map.height() as u16,
+        ),
+    }));
+}
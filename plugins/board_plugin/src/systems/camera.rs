@@ -0,0 +1,209 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::render::camera::Camera2d;
+use bevy::window::WindowResized;
+use bevy::{log, prelude::*};
+
+use crate::resources::{Board, BoardCursor, CameraOptions, KeyBindings};
+
+/// Translates a window cursor position to world space through the camera transform and projection
+#[inline]
+#[must_use]
+pub fn cursor_to_world(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    cursor: Vec2,
+) -> Vec2 {
+    let window_size = Vec2::new(window.width(), window.height());
+    let ndc = cursor / window_size * 2.0 - Vec2::ONE;
+    let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix.inverse();
+    ndc_to_world.project_point3(ndc.extend(0.0)).truncate()
+}
+
+/// Pans the 2D camera with the movement keys
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn keyboard_pan(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    key_bindings: Option<Res<KeyBindings>>,
+    camera_options: Option<Res<CameraOptions>>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let (x, y) = match key_bindings {
+        None => KeyBindings::default().direction(&keys),
+        Some(b) => b.direction(&keys),
+    };
+    if (x, y) == (0, 0) {
+        return;
+    }
+    let direction = Vec2::new(f32::from(x), f32::from(y));
+
+    let options = match camera_options {
+        None => CameraOptions::default(),
+        Some(o) => o.clone(),
+    };
+    for (mut transform, projection) in cameras.iter_mut() {
+        let delta =
+            direction.normalize() * options.pan_speed * projection.scale * time.delta_seconds();
+        transform.translation += delta.extend(0.0);
+    }
+}
+
+/// Pans the 2D camera so the board cursor stays in view
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn follow_cursor(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    cursor: Res<BoardCursor>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    if !cursor.is_changed() || !cursor.visible {
+        return;
+    }
+
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let tile_size = board.tile_size;
+    let position = board.tile_to_world(cursor.coordinates);
+    for (mut transform, projection) in cameras.iter_mut() {
+        // Keep a one tile margin between the cursor and the window border
+        let half_extents =
+            (window_size * 0.5 * projection.scale - Vec2::splat(tile_size)).max(Vec2::ZERO);
+        let centre = transform
+            .translation
+            .truncate()
+            .clamp(position - half_extents, position + half_extents);
+        transform.translation.x = centre.x;
+        transform.translation.y = centre.y;
+    }
+}
+
+/// Zooms the 2D camera with the mouse wheel, keeping the world point under the cursor in place
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn wheel_zoom(
+    windows: Res<Windows>,
+    mut wheel_evr: EventReader<MouseWheel>,
+    camera_options: Option<Res<CameraOptions>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let lines: f32 = wheel_evr
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    if lines == 0.0 {
+        return;
+    }
+
+    let options = match camera_options {
+        None => CameraOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    // Offset of the cursor from the window centre, zooming around the centre without a cursor
+    let offset = window
+        .cursor_position()
+        .map_or(Vec2::ZERO, |cursor| cursor - window_size * 0.5);
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        let scale = (projection.scale * (1.0 - options.zoom_speed).powf(lines))
+            .clamp(options.min_scale, options.max_scale);
+        let delta = offset * (projection.scale - scale);
+        transform.translation += delta.extend(0.0);
+        projection.scale = scale;
+    }
+}
+
+/// Pans the 2D camera while dragging with the middle mouse button
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn drag_pan(
+    windows: Res<Windows>,
+    buttons: Res<Input<MouseButton>>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let cursor = window.cursor_position();
+    if buttons.pressed(MouseButton::Middle) {
+        if let (Some(last), Some(cursor)) = (*last_cursor, cursor) {
+            for (mut transform, projection) in cameras.iter_mut() {
+                let delta = (last - cursor) * projection.scale;
+                transform.translation += delta.extend(0.0);
+            }
+        }
+        *last_cursor = cursor;
+    } else {
+        *last_cursor = None;
+    }
+}
+
+/// Centres the 2D camera on the board and zooms so the whole board fits in the window.
+/// Runs when a board is created, when the window is resized and when the fit key is pressed.
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn fit_to_window(
+    windows: Res<Windows>,
+    keys: Res<Input<KeyCode>>,
+    key_bindings: Option<Res<KeyBindings>>,
+    mut resize_evr: EventReader<WindowResized>,
+    board: Res<Board>,
+    camera_options: Option<Res<CameraOptions>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let resized = resize_evr.iter().count() > 0;
+    let fit = match key_bindings {
+        None => keys.any_just_pressed(KeyBindings::default().fit),
+        Some(b) => keys.any_just_pressed(b.fit.iter().copied()),
+    };
+    if !board.is_added() && !resized && !fit {
+        return;
+    }
+
+    let options = match camera_options {
+        None => CameraOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let board_size = board.bounds.size * (1.0 + options.fit_margin);
+    let scale = (board_size / window_size)
+        .max_element()
+        .clamp(options.min_scale, options.max_scale);
+    let centre = board.bounds.mins + board.bounds.size * 0.5;
+    log::debug!("Fitting camera on {} with scale {}", centre, scale);
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        transform.translation.x = centre.x;
+        transform.translation.y = centre.y;
+        projection.scale = scale;
+    }
+}
+
+/// Moves the 2D camera back to its initial position and zoom
+#[inline]
+pub fn reset_camera(
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        projection.scale = 1.0;
+    }
+}
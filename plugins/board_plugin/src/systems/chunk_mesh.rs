@@ -0,0 +1,151 @@
+use bevy::{
+    log,
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+    sprite::Mesh2dHandle,
+};
+
+use crate::{
+    components::{ChunkDirty, Coordinates, TileChunk},
+    resources::{AtlasCell, TileAtlas},
+    Board, BoardAssets, BoardOptions,
+};
+
+/// Builds the mesh of a chunk in tile units, a quad per tile inset by `padding` on every side,
+/// textured from the tile atlas according to the board state
+#[inline]
+#[must_use]
+pub fn chunk_mesh(board: &Board, chunk: &TileChunk, padding: f32) -> Mesh {
+    let tile_count = usize::from(chunk.width) * usize::from(chunk.height);
+    let mut positions = Vec::with_capacity(tile_count * 4);
+    let mut indices = Vec::with_capacity(tile_count * 6);
+    for y in 0..chunk.height {
+        for x in 0..chunk.width {
+            let (min_x, min_y) = (f32::from(x) + padding, f32::from(y) + padding);
+            let (max_x, max_y) = (f32::from(x + 1) - padding, f32::from(y + 1) - padding);
+            let first = positions.len() as u32;
+            positions.extend([
+                [min_x, min_y, 0.0],
+                [max_x, min_y, 0.0],
+                [max_x, max_y, 0.0],
+                [min_x, max_y, 0.0],
+            ]);
+            indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0, 0.0, 1.0]; positions.len()],
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, chunk_uvs(board, chunk));
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// Texture coordinates of the quads of a chunk mesh, in the vertex order of `chunk_mesh`
+#[inline]
+#[must_use]
+pub fn chunk_uvs(board: &Board, chunk: &TileChunk) -> Vec<[f32; 2]> {
+    let mut uvs = Vec::with_capacity(usize::from(chunk.width) * usize::from(chunk.height) * 4);
+    for y in 0..chunk.height {
+        for x in 0..chunk.width {
+            let coords = Coordinates::new(chunk.origin.x + x, chunk.origin.y + y);
+            let (min, max) = AtlasCell::of(board, coords).uv_rect();
+            // Texture rows go top to bottom
+            uvs.extend([
+                [min.x, max.y],
+                [max.x, max.y],
+                [max.x, min.y],
+                [min.x, min.y],
+            ]);
+        }
+    }
+    uvs
+}
+
+/// Rebuilds the meshes of the dirty tile chunks, creating the tile atlas on first use.
+/// Meshes already built only get their texture coordinates replaced.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn build_chunk_meshes(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    atlas: Option<Res<TileAtlas>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<ColorMaterial>>>,
+    images: Option<ResMut<Assets<Image>>>,
+    mut chunks: Query<
+        (
+            Entity,
+            &TileChunk,
+            &mut Mesh2dHandle,
+            &mut Handle<ColorMaterial>,
+        ),
+        With<ChunkDirty>,
+    >,
+) {
+    if chunks.is_empty() {
+        return;
+    }
+    // Headless apps have no render assets
+    let (mut meshes, mut materials, mut images) = match (meshes, materials, images) {
+        (Some(m), Some(c), Some(i)) => (m, c, i),
+        _ => {
+            log::error!("Tile chunks need the render assets");
+            return;
+        }
+    };
+    let material = match atlas {
+        Some(atlas) => atlas.material.clone(),
+        None => {
+            let image = images.add(TileAtlas::image(&board_assets));
+            let material = materials.add(ColorMaterial {
+                color: Color::WHITE,
+                texture: Some(image.clone()),
+            });
+            commands.insert_resource(TileAtlas {
+                image,
+                material: material.clone(),
+            });
+            material
+        }
+    };
+    let padding = match board_options {
+        None => BoardOptions::default().tile_padding,
+        Some(o) => o.tile_padding,
+    } * 0.5
+        / board.tile_size;
+
+    for (entity, chunk, mut mesh, mut chunk_material) in chunks.iter_mut() {
+        match meshes.get_mut(&mesh.0) {
+            Some(m) => m.insert_attribute(Mesh::ATTRIBUTE_UV_0, chunk_uvs(&board, chunk)),
+            None => mesh.0 = meshes.add(chunk_mesh(&board, chunk, padding)),
+        }
+        if *chunk_material != material {
+            *chunk_material = material.clone();
+        }
+        commands.entity(entity).remove::<ChunkDirty>();
+    }
+}
+
+/// Redraws the tile atlas when the board assets change, such as on a theme switch
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn update_tile_atlas(
+    board_assets: Res<BoardAssets>,
+    atlas: Option<Res<TileAtlas>>,
+    images: Option<ResMut<Assets<Image>>>,
+) {
+    if !board_assets.is_changed() || board_assets.is_added() {
+        return;
+    }
+    if let (Some(atlas), Some(mut images)) = (atlas, images) {
+        if let Some(image) = images.get_mut(&atlas.image) {
+            *image = TileAtlas::image(&board_assets);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use bevy::input::{mouse::MouseButtonInput, ElementState};
+use bevy::render::camera::Camera2d;
+use bevy::{log, prelude::*, utils::HashSet};
+
+use crate::{
+    components::{ChunkCoordinates, Themed, Uncover},
+    events::{BombExplosionEvent, ChunkTileMarkEvent, ChunkTileTriggerEvent},
+    resources::{AnimationOptions, BoardAssets, EndlessBoard, Tile},
+    systems::{animation, camera::cursor_to_world},
+    Bomb, BombNeighbour, Coordinates,
+};
+
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn input_handling(
+    windows: Res<Windows>,
+    board: Res<EndlessBoard>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut button_evr: EventReader<MouseButtonInput>,
+    mut tile_trigger_ewr: EventWriter<ChunkTileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<ChunkTileMarkEvent>,
+) {
+    let window = windows.get_primary().unwrap();
+    let (camera, camera_transform) = match cameras.get_single() {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to retrieve the 2D camera: {}", e);
+            return;
+        }
+    };
+
+    for event in button_evr.iter() {
+        if ElementState::Pressed == event.state {
+            if let Some(pos) = window.cursor_position() {
+                log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
+                let world_position = cursor_to_world(window, camera, camera_transform, pos);
+                let (chunk, coordinates) = board.world_to_tile(world_position);
+                match event.button {
+                    MouseButton::Left => {
+                        log::info!("Trying to uncover tile on {} {}", chunk, coordinates);
+                        tile_trigger_ewr.send(ChunkTileTriggerEvent(chunk, coordinates));
+                    }
+                    MouseButton::Right => {
+                        log::info!("Trying to mark tile on {} {}", chunk, coordinates);
+                        tile_mark_ewr.send(ChunkTileMarkEvent(chunk, coordinates));
+                    }
+                    MouseButton::Middle | MouseButton::Other(_) => (),
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[inline]
+pub fn trigger_event_handler(
+    mut commands: Commands,
+    board: Res<EndlessBoard>,
+    mut tile_trigger_evr: EventReader<ChunkTileTriggerEvent>,
+) {
+    for trigger_event in tile_trigger_evr.iter() {
+        if let Some(entity) = board
+            .chunks
+            .get(&trigger_event.0)
+            .and_then(|chunk| chunk.tile_to_uncover(&trigger_event.1))
+        {
+            commands.entity(*entity).insert(Uncover);
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[inline]
+pub fn uncover_tiles(
+    mut commands: Commands,
+    mut board: ResMut<EndlessBoard>,
+    board_assets: Res<BoardAssets>,
+    animation_options: Option<Res<AnimationOptions>>,
+    children: Query<(Entity, &Parent), With<Uncover>>,
+    parents: Query<(
+        &ChunkCoordinates,
+        &Coordinates,
+        Option<&Bomb>,
+        Option<&BombNeighbour>,
+    )>,
+    mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
+) {
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    for (entity, parent) in children.iter() {
+        let (chunk, coords, bomb, bomb_counter) = match parents.get(parent.0) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("{}", e);
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        // Flood fills spread one ring per frame here, as neighbouring chunks may not be spawned
+        // yet, so every cover starts its reveal straight away
+        animation::reveal(&mut commands, entity, 0, &options);
+
+        // We remove the entity from the chunk covered tile map
+        match board.chunk_mut(*chunk).try_uncover_tile(coords) {
+            None => log::debug!("Tried to uncover an already uncovered tile"),
+            Some(e) => log::debug!("Uncovered tile {} {} (entity: {:?})", chunk, coords, e),
+        }
+        if bomb.is_some() {
+            log::info!("Boom!");
+            animation::explode(
+                &mut commands,
+                parent.0,
+                Vec3::new(0.0, 0.0, 3.0),
+                board.options.tile_size,
+                &options,
+            );
+            bomb_explosion_event_wr.send(BombExplosionEvent);
+        }
+        // If the tile is empty..
+        else if bomb_counter.is_none() {
+            // .. We propagate the uncovering to adjacent tiles, spawning the neighbouring
+            // chunks on demand when the flood fill crosses a chunk border
+            let adjacent: Vec<_> = board.adjacent_tiles(*chunk, *coords).collect();
+            for (adjacent_chunk, adjacent_coords) in adjacent {
+                if !board.is_spawned(adjacent_chunk) {
+                    spawn_chunk(&mut commands, &mut board, &board_assets, adjacent_chunk);
+                }
+                if let Some(entity) = board
+                    .chunk_mut(adjacent_chunk)
+                    .tile_to_uncover(&adjacent_coords)
+                {
+                    commands.entity(*entity).insert(Uncover);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+pub fn mark_tiles(
+    mut commands: Commands,
+    mut board: ResMut<EndlessBoard>,
+    board_assets: Res<BoardAssets>,
+    animation_options: Option<Res<AnimationOptions>>,
+    mut tile_mark_event_rdr: EventReader<ChunkTileMarkEvent>,
+    query: Query<&Children>,
+) {
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let size = board.options.tile_size;
+    for event in tile_mark_event_rdr.iter() {
+        let toggled = match board.chunks.get_mut(&event.0) {
+            None => continue,
+            Some(chunk) => chunk.try_toggle_mark(&event.1),
+        };
+        if let Some((entity, mark)) = toggled {
+            if mark {
+                let mut bundle = flag_bundle(&board_assets, size);
+                let animation = animation::pop_in(&mut bundle, &options);
+                commands.entity(entity).with_children(|parent| {
+                    let mut flag = parent.spawn_bundle(bundle);
+                    flag.insert(Name::new("Flag")).insert(Themed::Flag);
+                    if let Some(animation) = animation {
+                        flag.insert(animation);
+                    }
+                });
+            } else {
+                let children = match query.get(entity) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        log::error!("Failed to retrieve flag entity components: {}", e);
+                        continue;
+                    }
+                };
+                for child in children.iter() {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// Spawns the chunks around the camera and despawns the ones that went out of view
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn stream_chunks(
+    mut commands: Commands,
+    mut board: ResMut<EndlessBoard>,
+    board_assets: Res<BoardAssets>,
+    windows: Res<Windows>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<Camera2d>>,
+    pending: Query<&Parent, With<Uncover>>,
+    tiles: Query<&ChunkCoordinates>,
+) {
+    let window = windows.get_primary().unwrap();
+    let (camera_transform, projection) = match cameras.get_single() {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to retrieve the 2D camera: {}", e);
+            return;
+        }
+    };
+    let centre = camera_transform.translation.truncate();
+    let half_extents = Vec2::new(window.width(), window.height()) * 0.5 * projection.scale;
+    let (mins, maxs) = (centre - half_extents, centre + half_extents);
+    let view_distance = i32::from(board.options.view_distance);
+
+    for coords in board.chunks_in_rect(mins, maxs, view_distance) {
+        if !board.is_spawned(coords) {
+            log::debug!("Spawning chunk {}", coords);
+            spawn_chunk(&mut commands, &mut board, &board_assets, coords);
+        }
+    }
+
+    // Chunks are kept one extra chunk further than they are spawned to avoid flickering on
+    // borders, and chunks with tiles still being uncovered are kept until the reveal completes
+    let kept: HashSet<ChunkCoordinates> = board
+        .chunks_in_rect(mins, maxs, view_distance + 1)
+        .into_iter()
+        .chain(pending.iter().filter_map(|p| tiles.get(p.0).ok()).copied())
+        .collect();
+    for (coords, chunk) in &mut board.chunks {
+        if chunk.is_spawned() && !kept.contains(coords) {
+            log::debug!("Despawning chunk {}", coords);
+            if let Some(entity) = chunk.despawn() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Spawns a chunk under the board entity, generating it first if it was never visited
+#[inline]
+pub fn spawn_chunk(
+    commands: &mut Commands,
+    board: &mut EndlessBoard,
+    board_assets: &BoardAssets,
+    coords: ChunkCoordinates,
+) {
+    let position = board.chunk_position(coords);
+    let chunk_size = board.chunk_world_size();
+    let tile_size = board.options.tile_size;
+    let tile_padding = board.options.tile_padding;
+    let board_entity = board.entity;
+    let chunk = board.chunk_mut(coords);
+
+    let tile_map = &chunk.tile_map;
+    let uncovered_tiles = &chunk.uncovered_tiles;
+    let marked_tiles = &chunk.marked_tiles;
+    let covered_tiles = &mut chunk.covered_tiles;
+    let entity = commands
+        .spawn()
+        .insert(Name::new(format!("Chunk {}", coords)))
+        .insert(coords)
+        .insert(Transform::from_translation(position.extend(0.0)))
+        .insert(GlobalTransform::default())
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: board_assets.board_material.colour,
+                        custom_size: Some(Vec2::splat(chunk_size)),
+                        ..default()
+                    },
+                    texture: board_assets.board_material.texture.clone(),
+                    transform: Transform::from_xyz(chunk_size * 0.5, chunk_size * 0.5, 0.0),
+                    ..default()
+                })
+                .insert(Name::new("Background"))
+                .insert(Themed::Board);
+
+            for y in 0..tile_map.height() {
+                for x in 0..tile_map.width() {
+                    let coordinates = Coordinates::new(x as u16, y as u16);
+                    let mut cmd = parent.spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: board_assets.tile_material.colour,
+                            custom_size: Some(Vec2::splat(tile_size - tile_padding)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            (x as f32).mul_add(tile_size, tile_size * 0.5),
+                            (y as f32).mul_add(tile_size, tile_size * 0.5),
+                            1.0,
+                        ),
+                        texture: board_assets.tile_material.texture.clone(),
+                        ..default()
+                    });
+                    cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
+                        .insert(Themed::Tile)
+                        .insert(coordinates)
+                        .insert(coords);
+
+                    if !uncovered_tiles.contains(&coordinates) {
+                        cmd.with_children(|parent| {
+                            let marked = marked_tiles.contains(&coordinates);
+                            let cover =
+                                spawn_cover(parent, board_assets, tile_size, tile_padding, marked);
+                            covered_tiles.insert(coordinates, cover);
+                        });
+                    }
+
+                    match tile_map.map()[(x, y)] {
+                        Tile::Bomb => {
+                            cmd.insert(Bomb);
+                            cmd.with_children(|parent| {
+                                parent
+                                    .spawn_bundle(SpriteBundle {
+                                        sprite: Sprite {
+                                            custom_size: Some(Vec2::splat(
+                                                tile_size - tile_padding,
+                                            )),
+                                            color: board_assets.bomb_material.colour,
+                                            ..default()
+                                        },
+                                        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                                        texture: board_assets.bomb_material.texture.clone(),
+                                        ..default()
+                                    })
+                                    .insert(Themed::Bomb);
+                            });
+                        }
+                        Tile::BombNeighbor(count) => {
+                            cmd.insert(BombNeighbour::new(count));
+                            cmd.with_children(|parent| {
+                                parent
+                                    .spawn_bundle(
+                                        board_assets.bomb_count_text_bundle(
+                                            count,
+                                            tile_size - tile_padding,
+                                        ),
+                                    )
+                                    .insert(Themed::BombCounter(count));
+                            });
+                        }
+                        Tile::Empty => (),
+                    }
+                }
+            }
+        })
+        .id();
+    commands.entity(board_entity).add_child(entity);
+    chunk.entity = Some(entity);
+}
+
+/// Spawns a tile cover, flagged if the tile is marked
+fn spawn_cover(
+    parent: &mut ChildBuilder,
+    board_assets: &BoardAssets,
+    tile_size: f32,
+    tile_padding: f32,
+    marked: bool,
+) -> Entity {
+    let mut cover = parent.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(tile_size - tile_padding)),
+            color: board_assets.covered_tile_material.colour,
+            ..default()
+        },
+        texture: board_assets.covered_tile_material.texture.clone(),
+        transform: Transform::from_xyz(0.0, 0.0, 2.0),
+        ..default()
+    });
+    cover
+        .insert(Name::new("Tile Cover"))
+        .insert(Themed::CoveredTile);
+    if marked {
+        cover.with_children(|parent| {
+            parent
+                .spawn_bundle(flag_bundle(board_assets, tile_size))
+                .insert(Name::new("Flag"))
+                .insert(Themed::Flag);
+        });
+    }
+    cover.id()
+}
+
+/// Flag sprite spawned on top of marked tile covers
+fn flag_bundle(board_assets: &BoardAssets, size: f32) -> SpriteBundle {
+    SpriteBundle {
+        texture: board_assets.flag_material.texture.clone(),
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(size)),
+            color: board_assets.flag_material.colour,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+        ..default()
+    }
+}
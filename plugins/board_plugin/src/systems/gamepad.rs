@@ -0,0 +1,65 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{BoardCursor, CursorRepeat, GamepadBindings},
+    Board,
+};
+
+/// Moves the board cursor and acts on the tile under it with any connected gamepad
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn gamepad_handling(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    gamepad_bindings: Option<Res<GamepadBindings>>,
+    board: Res<Board>,
+    mut cursor: ResMut<BoardCursor>,
+    mut repeat: Local<CursorRepeat>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    let bindings = match gamepad_bindings {
+        None => GamepadBindings::default(),
+        Some(b) => b.clone(),
+    };
+
+    // The first gamepad pushed in a direction drives the cursor
+    let direction = gamepads
+        .iter()
+        .map(|&gamepad| bindings.direction(gamepad, &buttons, &axes))
+        .find(|&direction| direction != (0, 0))
+        .unwrap_or((0, 0));
+    if repeat.tick(
+        direction,
+        time.delta_seconds(),
+        bindings.repeat_delay,
+        bindings.repeat_interval,
+    ) {
+        let size = (board.tile_map.width(), board.tile_map.height());
+        cursor.move_by(direction, size);
+        log::trace!("Cursor moved to {}", cursor.coordinates);
+    }
+
+    let coordinates = cursor.coordinates;
+    for &gamepad in gamepads.iter() {
+        if GamepadBindings::just_pressed(gamepad, &buttons, &bindings.reveal) {
+            log::info!("Trying to uncover tile on {}", coordinates);
+            cursor.visible = true;
+            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+        }
+        if GamepadBindings::just_pressed(gamepad, &buttons, &bindings.flag) {
+            log::info!("Trying to mark tile on {}", coordinates);
+            cursor.visible = true;
+            tile_mark_ewr.send(TileMarkEvent(coordinates));
+        }
+        if GamepadBindings::just_pressed(gamepad, &buttons, &bindings.chord) {
+            log::info!("Trying to chord tile on {}", coordinates);
+            cursor.visible = true;
+            tile_chord_ewr.send(TileChordEvent(coordinates));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::TileHighlight,
+    resources::{Board, HoveredTile, TilePress},
+};
+
+/// Colour of the highlight over the tile under the mouse cursor
+const HOVER_COLOUR: Color = Color::rgba(1.0, 1.0, 1.0, 0.2);
+/// Colour of the tiles previewed as pressed, darkening their covers
+const PRESSED_COLOUR: Color = Color::rgba(0.0, 0.0, 0.0, 0.3);
+
+/// Respawns the hover highlight and the pressed tiles preview over the board when the hovered
+/// tile, the held press or the board change
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn highlight_tiles(
+    mut commands: Commands,
+    board: Res<Board>,
+    hovered: Res<HoveredTile>,
+    tile_press: Res<TilePress>,
+    highlights: Query<Entity, With<TileHighlight>>,
+) {
+    if !(board.is_changed() || hovered.is_changed() || tile_press.is_changed()) {
+        return;
+    }
+    for entity in highlights.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let hovered = hovered.0.map(|pick| pick.coordinates);
+    let pressed = tile_press.preview(&board, hovered);
+    // Pressed tiles are not highlighted on top
+    let hover = hovered.filter(|coords| !pressed.contains(coords));
+    let tiles = hover
+        .map(|coords| (coords, TileHighlight::Hover))
+        .into_iter()
+        .chain(pressed.into_iter().map(|c| (c, TileHighlight::Pressed)));
+    commands.entity(board.entity).with_children(|parent| {
+        for (coords, highlight) in tiles {
+            let (colour, name) = match highlight {
+                TileHighlight::Hover => (HOVER_COLOUR, "Hover Highlight"),
+                TileHighlight::Pressed => (PRESSED_COLOUR, "Pressed Tile"),
+            };
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(board.tile_size)),
+                        color: colour,
+                        ..default()
+                    },
+                    // Above the tile covers and their marks, below the explosions
+                    transform: Transform::from_translation(board.tile_centre(coords).extend(3.5)),
+                    ..default()
+                })
+                .insert(Name::new(name))
+                .insert(highlight);
+        }
+    });
+}
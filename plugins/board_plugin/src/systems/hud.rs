@@ -0,0 +1,196 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+
+use crate::{
+    components::{Hud, HudFace, HudMineCounter, HudPreset, HudTimer, Themed},
+    resources::{BoardPreset, BoardState, BoardStatus},
+    Board, BoardAssets, BoardOptions,
+};
+
+/// HUD bar height, taller on the web where it is mostly used with touch screens
+#[cfg(target_arch = "wasm32")]
+const HUD_HEIGHT: f32 = 64.0;
+#[cfg(not(target_arch = "wasm32"))]
+const HUD_HEIGHT: f32 = 40.0;
+
+/// Text of the face button for a game state
+const fn face(state: BoardState) -> &'static str {
+    match state {
+        BoardState::Playing => ":)",
+        BoardState::Won => "B)",
+        BoardState::Lost => ":(",
+    }
+}
+
+/// Spawns the HUD bar along the top of the window
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn spawn_hud(
+    mut commands: Commands,
+    board_options: Option<Res<BoardOptions>>,
+    board_preset: Option<Res<BoardPreset>>,
+    board_assets: Res<BoardAssets>,
+) {
+    let options = match board_options {
+        None => BoardOptions::default(),
+        Some(o) => o.clone(),
+    };
+    // Boards built from hand written options may not match their preset
+    let description = match board_preset {
+        Some(preset)
+            if preset.map_size() == options.map_size
+                && preset.bomb_count() == options.bomb_count =>
+        {
+            preset.to_string()
+        }
+        _ => format!("{}x{}", options.map_size.0, options.map_size.1),
+    };
+    let text_style = TextStyle {
+        font: board_assets.bomb_counter_font.clone(),
+        font_size: HUD_HEIGHT * 0.6,
+        color: Color::WHITE,
+    };
+    let text = |value: String| TextBundle {
+        text: Text::with_section(value, text_style.clone(), TextAlignment::default()),
+        ..default()
+    };
+    let slot = |justify_content: JustifyContent| NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0 / 3.0), Val::Percent(100.0)),
+            justify_content,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        color: UiColor(Color::NONE),
+        ..default()
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(HUD_HEIGHT)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    ..default()
+                },
+                padding: Rect::all(Val::Px(HUD_HEIGHT * 0.2)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+            ..default()
+        })
+        .insert(Name::new("HUD"))
+        .insert(Hud)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(slot(JustifyContent::FlexStart))
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(text(format!("{:03}", options.bomb_count)))
+                        .insert(HudMineCounter)
+                        .insert(Themed::Label);
+                });
+            parent
+                .spawn_bundle(slot(JustifyContent::Center))
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(HUD_HEIGHT), Val::Px(HUD_HEIGHT)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            color: UiColor(Color::DARK_GRAY),
+                            ..default()
+                        })
+                        .insert(HudFace)
+                        .with_children(|parent| {
+                            parent
+                                .spawn_bundle(text(face(BoardState::Playing).to_string()))
+                                .insert(Themed::Label);
+                        });
+                });
+            parent
+                .spawn_bundle(slot(JustifyContent::FlexEnd))
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(text(format!("{}  ", description)))
+                        .insert(HudPreset)
+                        .insert(Themed::Label);
+                    parent
+                        .spawn_bundle(text("000".to_string()))
+                        .insert(HudTimer)
+                        .insert(Themed::Label);
+                });
+        });
+}
+
+/// Refreshes the HUD texts from the board and its status
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+pub fn update_hud(
+    board: Res<Board>,
+    status: Res<BoardStatus>,
+    faces: Query<&Children, With<HudFace>>,
+    mut texts: ParamSet<(
+        Query<&mut Text, With<HudMineCounter>>,
+        Query<&mut Text, With<HudTimer>>,
+        Query<&mut Text>,
+    )>,
+) {
+    if board.is_changed() {
+        let remaining = board.remaining_bombs();
+        for mut text in texts.p0().iter_mut() {
+            text.sections[0].value = format!("{:03}", remaining);
+        }
+    }
+    let seconds = status.timer.elapsed_secs().min(999.0) as u32;
+    for mut text in texts.p1().iter_mut() {
+        let value = format!("{:03}", seconds);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+    if status.is_changed() {
+        for children in faces.iter() {
+            for &child in children.iter() {
+                if let Ok(mut text) = texts.p2().get_mut(child) {
+                    text.sections[0].value = face(status.state).to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Restarts the running state, and so the game, when the face button is clicked
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn face_button<T: StateData>(
+    mut state: ResMut<State<T>>,
+    mut faces: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<HudFace>)>,
+) {
+    for (interaction, mut colour) in faces.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                log::info!("Restarting game");
+                if let Err(e) = state.restart() {
+                    log::error!("Failed to restart game: {}", e);
+                }
+            }
+            Interaction::Hovered => *colour = UiColor(Color::GRAY),
+            Interaction::None => *colour = UiColor(Color::DARK_GRAY),
+        }
+    }
+}
+
+/// Despawns the HUD
+#[inline]
+pub fn despawn_hud(mut commands: Commands, huds: Query<Entity, With<Hud>>) {
+    for entity in huds.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,43 +1,123 @@
 use bevy::input::{mouse::MouseButtonInput, ElementState};
 use bevy::log;
 use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
 
 use crate::{
-    events::{TileMarkEvent, TileTriggerEvent},
+    events::{TileChordEvent, TileHoverEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{HoveredTile, TilePick, TilePress},
+    systems::camera::cursor_to_world,
     Board,
 };
 
+/// Picks the tile under the mouse cursor of `window` through the camera transform and projection
+#[inline]
+#[must_use]
+pub fn cursor_tile(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    board: &Board,
+) -> Option<TilePick> {
+    let position = window.cursor_position()?;
+    board.pick(cursor_to_world(window, camera, camera_transform, position))
+}
+
+/// Tracks the tile under the mouse cursor, sending a `TileHoverEvent` when it changes.
+/// The cursor is off the board while it is over a UI element or outside of the window.
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
+pub fn track_hover(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    interactions: Query<&Interaction>,
+    mut hovered: ResMut<HoveredTile>,
+    mut tile_hover_ewr: EventWriter<TileHoverEvent>,
+) {
+    let over_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let tile = match (windows.get_primary(), cameras.get_single()) {
+        (Some(window), Ok((camera, camera_transform))) if !over_ui => {
+            cursor_tile(window, camera, camera_transform, &board)
+        }
+        _ => None,
+    };
+    if tile != hovered.0 {
+        tile_hover_ewr.send(TileHoverEvent {
+            left: hovered.0,
+            entered: tile,
+        });
+        hovered.0 = tile;
+    }
+}
+
+/// Marks tiles on a right button press. Left and middle presses are held in `TilePress`, and
+/// uncover or chord the pressed tile once released over it, a left press on an uncovered tile
+/// chording as well. Releasing elsewhere cancels the press.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 pub fn input_handling(
     windows: Res<Windows>,
     board: Res<Board>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    interactions: Query<&Interaction>,
+    mut tile_press: ResMut<TilePress>,
     mut button_evr: EventReader<MouseButtonInput>,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
 ) {
-    let window = windows.get_primary().unwrap();
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let (camera, camera_transform) = match cameras.get_single() {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to retrieve the 2D camera: {}", e);
+            return;
+        }
+    };
+    // Clicks on UI elements, like the HUD, don't reach the board
+    let over_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let tile = if over_ui {
+        None
+    } else {
+        cursor_tile(window, camera, camera_transform, &board).map(|pick| pick.coordinates)
+    };
 
     for event in button_evr.iter() {
-        if ElementState::Pressed == event.state {
-            let position = window.cursor_position();
-            if let Some(pos) = position {
-                log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
-                if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            log::info!("Trying to uncover tile on {}", coordinates);
-                            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
-                        }
-                        MouseButton::Right => {
-                            log::info!("Trying to mark tile on {}", coordinates);
-                            tile_mark_ewr.send(TileMarkEvent(coordinates));
-                        }
-                        MouseButton::Middle | MouseButton::Other(_) => (),
-                    }
+        log::trace!("Mouse button {:?}: {:?}", event.button, event.state);
+        match (event.state, event.button, tile) {
+            (ElementState::Pressed, MouseButton::Right, Some(coordinates)) => {
+                log::info!("Trying to mark tile on {}", coordinates);
+                tile_mark_ewr.send(TileMarkEvent(coordinates));
+            }
+            (ElementState::Pressed, MouseButton::Left | MouseButton::Middle, Some(coordinates)) => {
+                tile_press.press = Some((event.button, coordinates));
+            }
+            (ElementState::Released, button, _) => {
+                let coordinates = match tile_press.press {
+                    Some((pressed, coordinates)) if pressed == button => coordinates,
+                    _ => continue,
+                };
+                let chord = tile_press.is_chord(&board);
+                tile_press.press = None;
+                if tile != Some(coordinates) {
+                    log::debug!("Cancelled the press on {}", coordinates);
+                } else if chord {
+                    log::info!("Trying to chord on {}", coordinates);
+                    tile_chord_ewr.send(TileChordEvent(coordinates));
+                } else {
+                    log::info!("Trying to uncover tile on {}", coordinates);
+                    tile_trigger_ewr.send(TileTriggerEvent(coordinates));
                 }
             }
+            _ => (),
         }
     }
 }
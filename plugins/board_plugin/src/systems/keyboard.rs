@@ -0,0 +1,87 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{RedoEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent, UndoEvent},
+    resources::{BoardCursor, CursorRepeat, KeyBindings},
+    Board,
+};
+
+/// Moves the board cursor and acts on the tile under it
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn keyboard_handling(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    key_bindings: Option<Res<KeyBindings>>,
+    board: Res<Board>,
+    mut cursor: ResMut<BoardCursor>,
+    mut repeat: Local<CursorRepeat>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+    mut undo_ewr: EventWriter<UndoEvent>,
+    mut redo_ewr: EventWriter<RedoEvent>,
+) {
+    let bindings = match key_bindings {
+        None => KeyBindings::default(),
+        Some(b) => b.clone(),
+    };
+
+    let direction = bindings.direction(&keys);
+    if repeat.tick(
+        direction,
+        time.delta_seconds(),
+        bindings.repeat_delay,
+        bindings.repeat_interval,
+    ) {
+        let size = (board.tile_map.width(), board.tile_map.height());
+        cursor.move_by(direction, size);
+        log::trace!("Cursor moved to {}", cursor.coordinates);
+    }
+
+    let coordinates = cursor.coordinates;
+    if keys.any_just_pressed(bindings.reveal.iter().copied()) {
+        log::info!("Trying to uncover tile on {}", coordinates);
+        cursor.visible = true;
+        tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+    }
+    if keys.any_just_pressed(bindings.flag.iter().copied()) {
+        log::info!("Trying to mark tile on {}", coordinates);
+        cursor.visible = true;
+        tile_mark_ewr.send(TileMarkEvent(coordinates));
+    }
+    if keys.any_just_pressed(bindings.chord.iter().copied()) {
+        log::info!("Trying to chord tile on {}", coordinates);
+        cursor.visible = true;
+        tile_chord_ewr.send(TileChordEvent(coordinates));
+    }
+    if keys.any_just_pressed(bindings.undo.iter().copied()) {
+        undo_ewr.send(UndoEvent);
+    }
+    if keys.any_just_pressed(bindings.redo.iter().copied()) {
+        redo_ewr.send(RedoEvent);
+    }
+}
+
+/// Places the cursor highlight on the tile under the cursor
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn update_cursor(
+    board: Res<Board>,
+    cursor: Res<BoardCursor>,
+    mut highlights: Query<(&mut Transform, &mut Sprite, &mut Visibility)>,
+) {
+    let (mut transform, mut sprite, mut visibility) = match highlights.get_mut(cursor.entity) {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!("Failed to retrieve the cursor highlight: {}", e);
+            return;
+        }
+    };
+    let tile_size = board.tile_size;
+    let position = board.tile_to_world(cursor.coordinates);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+    sprite.custom_size = Some(Vec2::splat(tile_size));
+    visibility.is_visible = cursor.visible;
+}
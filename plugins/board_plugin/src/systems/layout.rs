@@ -0,0 +1,114 @@
+use bevy::window::{WindowId, WindowResized};
+use bevy::{log, math::Vec3Swizzles, prelude::*, sprite::Mesh2dHandle};
+
+use crate::{
+    bounds::Bounds,
+    components::{ChunkDirty, Themed, TileChunk},
+    Board, BoardOptions, Coordinates,
+};
+
+/// Recomputes the tile size and bounds of the board when the window is resized.
+/// The existing tile entities are moved and resized in place, keeping the game state.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn relayout_board(
+    mut commands: Commands,
+    mut resize_evr: EventReader<WindowResized>,
+    board_options: Option<Res<BoardOptions>>,
+    mut board: ResMut<Board>,
+    children: Query<&Children>,
+    coordinates: Query<&Coordinates>,
+    themed: Query<&Themed>,
+    chunks: Query<&TileChunk>,
+    mut transforms: Query<&mut Transform>,
+    mut sprites: Query<&mut Sprite>,
+    mut texts: Query<&mut Text>,
+) {
+    let window_size = match resize_evr
+        .iter()
+        .rfind(|event| event.id == WindowId::primary())
+    {
+        None => return,
+        Some(event) => Vec2::new(event.width, event.height),
+    };
+    let options = match board_options {
+        None => BoardOptions::default(),
+        Some(o) => o.clone(),
+    };
+
+    let map_size = (board.tile_map.width(), board.tile_map.height());
+    let tile_size = options.tile_size.tile_size(window_size, map_size);
+    let delta = tile_size - board.tile_size;
+    if delta == 0.0 {
+        return;
+    }
+    let board_size = Vec2::new(map_size.0 as f32, map_size.1 as f32) * tile_size;
+    let board_mins = options.position.board_mins(board_size);
+    log::info!("Window resized, new board size: {}", board_size);
+    board.tile_size = tile_size;
+    board.bounds = Bounds::new(board_mins.xy(), board_size);
+
+    if let Ok(mut transform) = transforms.get_mut(board.entity) {
+        transform.translation = board_mins;
+    }
+    let board_children = match children.get(board.entity) {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to retrieve board children: {}", e);
+            return;
+        }
+    };
+    for &child in board_children.iter() {
+        let mut transform = match transforms.get_mut(child) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        if let Ok(coords) = coordinates.get(child) {
+            // Tile
+            transform.translation.x = f32::from(coords.x).mul_add(tile_size, tile_size * 0.5);
+            transform.translation.y = f32::from(coords.y).mul_add(tile_size, tile_size * 0.5);
+            resize_descendants(child, delta, &children, &mut sprites, &mut texts);
+        } else if let Ok(chunk) = chunks.get(child) {
+            // Chunk meshes are in tile units, fully rebuilt to keep the padding in world units
+            transform.translation.x = f32::from(chunk.origin.x) * tile_size;
+            transform.translation.y = f32::from(chunk.origin.y) * tile_size;
+            transform.scale = Vec3::new(tile_size, tile_size, 1.0);
+            commands
+                .entity(child)
+                .insert(Mesh2dHandle::default())
+                .insert(ChunkDirty);
+        } else if matches!(themed.get(child), Ok(Themed::Board)) {
+            // Background
+            transform.translation.x = board_size.x * 0.5;
+            transform.translation.y = board_size.y * 0.5;
+            if let Ok(mut sprite) = sprites.get_mut(child) {
+                sprite.custom_size = Some(board_size);
+            }
+        }
+    }
+}
+
+/// Grows the sprites and texts of an entity and all its descendants by `delta`
+fn resize_descendants(
+    entity: Entity,
+    delta: f32,
+    children: &Query<&Children>,
+    sprites: &mut Query<&mut Sprite>,
+    texts: &mut Query<&mut Text>,
+) {
+    if let Ok(mut sprite) = sprites.get_mut(entity) {
+        if let Some(size) = sprite.custom_size.as_mut() {
+            *size += Vec2::splat(delta);
+        }
+    }
+    if let Ok(mut text) = texts.get_mut(entity) {
+        for section in &mut text.sections {
+            section.style.font_size += delta;
+        }
+    }
+    if let Ok(entity_children) = children.get(entity) {
+        for &child in entity_children.iter() {
+            resize_descendants(child, delta, children, sprites, texts);
+        }
+    }
+}
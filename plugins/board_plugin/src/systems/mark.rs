@@ -1,43 +1,117 @@
 use bevy::{log, prelude::*};
 
-use crate::{events::TileMarkEvent, Board, BoardAssets};
+use crate::{
+    components::{ChunkDirty, Themed, TileChunk},
+    events::TileMarkEvent,
+    resources::{AnimationOptions, BoardChange, BoardHistory, BoardStatus, Mark},
+    systems::animation,
+    Board, BoardAssets, BoardOptions,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn mark_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
     board_assets: Res<BoardAssets>,
+    board_options: Option<Res<BoardOptions>>,
+    animation_options: Option<Res<AnimationOptions>>,
+    status: Option<Res<BoardStatus>>,
+    mut history: Option<ResMut<BoardHistory>>,
     mut tile_mark_event_rdr: EventReader<TileMarkEvent>,
     query: Query<&Children>,
+    chunks: Query<(), With<TileChunk>>,
 ) {
-    for event in tile_mark_event_rdr.iter() {
-        if let Some((entity, mark)) = board.try_toggle_mark(&event.0) {
-            if mark {
-                commands.entity(entity).with_children(|parent| {
-                    parent
-                        .spawn_bundle(SpriteBundle {
-                            texture: board_assets.flag_material.texture.clone(),
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(board.tile_size)),
-                                color: board_assets.flag_material.colour,
-                                ..default()
-                            },
-                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
-                            ..default()
-                        })
-                        .insert(Name::new("Flag"));
-                });
-            } else {
-                let children = match query.get(entity) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        log::error!("Failed to retrieve flag entity components: {}", e);
-                        continue;
-                    }
-                };
-                for child in children.iter() {
-                    commands.entity(*child).despawn_recursive();
-                }
+    let question_marks = board_options.is_some_and(|o| o.question_marks);
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    // Marks are dropped once the game is over, as triggers are
+    let over = status.as_deref().is_some_and(BoardStatus::is_over);
+    for event in tile_mark_event_rdr.iter().filter(|_| !over) {
+        let before = board.marked_tiles.get(&event.0).copied();
+        if let Some((entity, mark)) = board.try_toggle_mark(&event.0, question_marks) {
+            if let (Some(history), Some(status)) = (history.as_mut(), status.as_ref()) {
+                history.record(BoardChange::mark(event.0, before, mark, status.state));
             }
+            if mark.is_some() {
+                log::debug!("Marked tile {} with {:?}", event.0, mark);
+            }
+            redraw_mark(
+                &mut commands,
+                entity,
+                mark,
+                &query,
+                &chunks,
+                &board_assets,
+                board.tile_size,
+                &options,
+            );
+        }
+    }
+}
+
+/// Replaces the mark sprite over the tile cover `entity` with the sprite of `mark`, if any.
+/// Chunk meshes draw the marks from the board state, so they are only marked for a rebuild.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn redraw_mark(
+    commands: &mut Commands,
+    entity: Entity,
+    mark: Option<Mark>,
+    children: &Query<&Children>,
+    chunks: &Query<(), With<TileChunk>>,
+    board_assets: &BoardAssets,
+    tile_size: f32,
+    options: &AnimationOptions,
+) {
+    if chunks.contains(entity) {
+        commands.entity(entity).insert(ChunkDirty);
+        return;
+    }
+    // We remove the previous mark sprite, if any
+    if let Ok(children) = children.get(entity) {
+        for child in children.iter() {
+            commands.entity(*child).despawn_recursive();
         }
     }
+    if let Some(mark) = mark {
+        spawn_mark(commands, entity, mark, board_assets, tile_size, options);
+    }
+}
+
+/// Spawns the sprite of a mark over the tile cover `entity`
+#[inline]
+pub fn spawn_mark(
+    commands: &mut Commands,
+    entity: Entity,
+    mark: Mark,
+    board_assets: &BoardAssets,
+    tile_size: f32,
+    options: &AnimationOptions,
+) {
+    let material = board_assets.mark_material(mark);
+    let mut bundle = SpriteBundle {
+        texture: material.texture.clone(),
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(tile_size)),
+            color: material.colour,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+        ..default()
+    };
+    let animation = animation::pop_in(&mut bundle, options);
+    commands.entity(entity).with_children(|parent| {
+        let mut sprite = parent.spawn_bundle(bundle);
+        sprite
+            .insert(Name::new(format!("{:?}", mark)))
+            .insert(match mark {
+                Mark::Flag => Themed::Flag,
+                Mark::Question => Themed::Question,
+            });
+        if let Some(animation) = animation {
+            sprite.insert(animation);
+        }
+    });
 }
@@ -0,0 +1,306 @@
+use bevy::{ecs::schedule::StateData, log, prelude::*};
+
+use crate::{
+    components::{CustomField, Menu, MenuButton, MenuFieldText, MenuHint, MenuUndoText, Themed},
+    menu::MenuTarget,
+    resources::{BoardPreset, UndoPolicy},
+    BoardAssets, BoardOptions,
+};
+
+const BUTTON_COLOUR: Color = Color::DARK_GRAY;
+const HOVERED_BUTTON_COLOUR: Color = Color::GRAY;
+
+/// Spawns a menu button with a text label
+fn spawn_button(parent: &mut ChildBuilder, label: &str, action: MenuButton, style: &TextStyle) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                padding: Rect::all(Val::Px(8.0)),
+                margin: Rect::all(Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: UiColor(BUTTON_COLOUR),
+            ..default()
+        })
+        .insert(action)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(label, style.clone(), TextAlignment::default()),
+                    ..default()
+                })
+                .insert(Themed::Label);
+        });
+}
+
+/// Transparent row of menu elements
+fn row() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        color: UiColor(Color::NONE),
+        ..default()
+    }
+}
+
+/// Spawns a custom board field label, value and adjustment buttons
+fn spawn_field(parent: &mut ChildBuilder, label: &str, field: CustomField, style: &TextStyle) {
+    parent
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(label, style.clone(), TextAlignment::default()),
+            style: Style {
+                size: Size::new(Val::Px(120.0), Val::Auto),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Themed::Label);
+    spawn_button(parent, "-", MenuButton::Adjust(field, -1), style);
+    parent
+        .spawn_bundle(TextBundle {
+            text: Text::with_section("", style.clone(), TextAlignment::default()),
+            style: Style {
+                size: Size::new(Val::Px(80.0), Val::Auto),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(MenuFieldText(field))
+        .insert(Themed::Label);
+    spawn_button(parent, "+", MenuButton::Adjust(field, 1), style);
+}
+
+/// Menu text of an undo policy
+fn undo_label(policy: Option<UndoPolicy>) -> String {
+    policy.map_or_else(|| "Off".to_string(), |p| p.to_string())
+}
+
+/// Spawns the undo policy label, value and switch button
+fn spawn_undo(parent: &mut ChildBuilder, policy: Option<UndoPolicy>, style: &TextStyle) {
+    parent
+        .spawn_bundle(TextBundle {
+            text: Text::with_section("Undo", style.clone(), TextAlignment::default()),
+            style: Style {
+                size: Size::new(Val::Px(120.0), Val::Auto),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Themed::Label);
+    parent
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(undo_label(policy), style.clone(), TextAlignment::default()),
+            style: Style {
+                size: Size::new(Val::Px(280.0), Val::Auto),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(MenuUndoText)
+        .insert(Themed::Label);
+    spawn_button(parent, "Change", MenuButton::CycleUndo, style);
+}
+
+/// Spawns the preset menu, the custom board form starts from the current board options
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn spawn_menu(
+    mut commands: Commands,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+) {
+    let options = match board_options {
+        None => BoardOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let style = TextStyle {
+        font: board_assets.bomb_counter_font.clone(),
+        font_size: 28.0,
+        color: Color::WHITE,
+    };
+    let form = Menu::new(
+        options.map_size.0 as u16,
+        options.map_size.1 as u16,
+        options.bomb_count,
+    );
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                // Children are laid out bottom to top
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+            ..default()
+        })
+        .insert(Name::new("Menu"))
+        .insert(form)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "Minesweeper",
+                        TextStyle {
+                            font_size: 48.0,
+                            ..style.clone()
+                        },
+                        TextAlignment::default(),
+                    ),
+                    ..default()
+                })
+                .insert(Themed::Label);
+            parent.spawn_bundle(row()).with_children(|parent| {
+                for preset in [
+                    BoardPreset::Beginner,
+                    BoardPreset::Intermediate,
+                    BoardPreset::Expert,
+                ] {
+                    spawn_button(
+                        parent,
+                        &preset.to_string(),
+                        MenuButton::Preset(preset),
+                        &style,
+                    );
+                }
+            });
+            for (label, field) in [
+                ("Width", CustomField::Width),
+                ("Height", CustomField::Height),
+                ("Mines", CustomField::BombCount),
+            ] {
+                parent.spawn_bundle(row()).with_children(|parent| {
+                    spawn_field(parent, label, field, &style);
+                });
+            }
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::GRAY,
+                            ..style.clone()
+                        },
+                        TextAlignment::default(),
+                    ),
+                    ..default()
+                })
+                .insert(MenuHint)
+                .insert(Themed::Label);
+            parent.spawn_bundle(row()).with_children(|parent| {
+                spawn_button(parent, "Play custom", MenuButton::StartCustom, &style);
+            });
+            parent.spawn_bundle(row()).with_children(|parent| {
+                spawn_undo(parent, options.undo, &style);
+            });
+        });
+}
+
+/// Handles the menu buttons: presets start a game, the custom form and the undo policy are
+/// edited in place
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+pub fn menu_buttons<T: StateData>(
+    mut commands: Commands,
+    mut state: ResMut<State<T>>,
+    target: Res<MenuTarget<T>>,
+    board_options: Option<Res<BoardOptions>>,
+    mut buttons: Query<(&Interaction, &MenuButton, &mut UiColor), Changed<Interaction>>,
+    mut forms: Query<&mut Menu>,
+    mut undo_texts: Query<&mut Text, With<MenuUndoText>>,
+) {
+    for (interaction, button, mut colour) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Hovered => *colour = UiColor(HOVERED_BUTTON_COLOUR),
+            Interaction::None => *colour = UiColor(BUTTON_COLOUR),
+            Interaction::Clicked => {
+                let preset = match *button {
+                    MenuButton::Preset(preset) => preset,
+                    MenuButton::StartCustom => match forms.get_single() {
+                        Ok(form) => form.preset(),
+                        Err(e) => {
+                            log::error!("Failed to retrieve the custom board form: {}", e);
+                            continue;
+                        }
+                    },
+                    MenuButton::Adjust(field, delta) => {
+                        for mut form in forms.iter_mut() {
+                            form.adjust(field, delta);
+                        }
+                        continue;
+                    }
+                    MenuButton::CycleUndo => {
+                        // Kept by the games started from the menu
+                        let mut options = board_options.as_deref().cloned().unwrap_or_default();
+                        options.undo = UndoPolicy::cycle(options.undo);
+                        for mut text in undo_texts.iter_mut() {
+                            text.sections[0].value = undo_label(options.undo);
+                        }
+                        commands.insert_resource(options);
+                        continue;
+                    }
+                };
+                log::info!("Starting {} game", preset);
+                let mut options = board_options.as_deref().cloned().unwrap_or_default();
+                preset.apply(&mut options);
+                // Games started from the menu are random
+                options.seed = None;
+                commands.insert_resource(options);
+                commands.insert_resource(preset);
+                if let Err(e) = state.set(target.0.clone()) {
+                    log::error!("Failed to start game: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Refreshes the custom board form texts
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn update_form(
+    forms: Query<&Menu, Changed<Menu>>,
+    mut texts: ParamSet<(
+        Query<(&mut Text, &MenuFieldText)>,
+        Query<&mut Text, With<MenuHint>>,
+    )>,
+) {
+    let form = match forms.iter().next() {
+        None => return,
+        Some(f) => *f,
+    };
+    for (mut text, field) in texts.p0().iter_mut() {
+        let value = match field.0 {
+            CustomField::Width => form.width,
+            CustomField::Height => form.height,
+            CustomField::BombCount => form.bomb_count,
+        };
+        text.sections[0].value = value.to_string();
+    }
+    for mut text in texts.p1().iter_mut() {
+        text.sections[0].value = format!(
+            "{} to {} tiles per side, 1 to {} mines",
+            BoardPreset::MIN_SIZE,
+            BoardPreset::MAX_SIZE,
+            BoardPreset::max_bomb_count(form.width, form.height)
+        );
+    }
+}
+
+/// Despawns the menu
+#[inline]
+pub fn despawn_menu(mut commands: Commands, menus: Query<Entity, With<Menu>>) {
+    for entity in menus.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,3 +1,54 @@
+use bevy::ecs::schedule::{RunCriteriaLabel, ShouldRun, SystemLabel};
+use bevy::prelude::{In, Res};
+
+use crate::Board;
+
+pub mod animation;
+pub mod bridge;
+pub mod camera;
+pub mod chunk_mesh;
+pub mod endless;
+pub mod gamepad;
+pub mod highlight;
+pub mod hud;
 pub mod input;
+pub mod keyboard;
+pub mod layout;
 pub mod mark;
+pub mod menu;
+pub mod replay;
+pub mod status;
+pub mod theme;
+pub mod touch;
 pub mod uncover;
+pub mod undo;
+
+/// Labels used to order the board systems
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, SystemLabel)]
+pub enum BoardSystem {
+    /// Board relayout after a window resize
+    Layout,
+    /// Recording of the moves sent in the previous frame
+    Record,
+}
+
+/// Labels of the board state run criteria
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, RunCriteriaLabel)]
+pub enum BoardRunCriteria {
+    /// The running state is active
+    Update,
+    /// The running state is in the state stack
+    InStackUpdate,
+}
+
+/// Run criteria piped after a `BoardRunCriteria`, skipping the board systems while no board
+/// exists, as happens when the board options were refused
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn board_exists(In(should_run): In<ShouldRun>, board: Option<Res<Board>>) -> ShouldRun {
+    match (should_run, board) {
+        (ShouldRun::Yes, None) => ShouldRun::No,
+        (ShouldRun::YesAndCheckAgain, None) => ShouldRun::NoAndCheckAgain,
+        (should_run, _) => should_run,
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{RedoEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent, UndoEvent},
+    resources::{BoardHistory, BoardSave, BoardStatus, Move, Replay, ReplayPlayer, ReplayRecorder},
+    Board,
+};
+
+/// Records the moves played on the current board, starting a new recording with each board.
+/// Undo and redo moves are recorded along the undo policy, even once the game is over.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn record_moves(
+    board: Option<Res<Board>>,
+    status: Option<Res<BoardStatus>>,
+    history: Option<Res<BoardHistory>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut undo_evr: EventReader<UndoEvent>,
+    mut redo_evr: EventReader<RedoEvent>,
+) {
+    let (board, status) = match (board, status) {
+        (Some(b), Some(s)) => (b, s),
+        _ => return,
+    };
+    if board.is_added() {
+        // The safe start may not be uncovered yet
+        let mut save = BoardSave::new(&board, &status);
+        save.uncovered.extend(board.pending_uncover.iter().copied());
+        recorder.replay = Some(Replay {
+            board: save,
+            moves: Vec::new(),
+            undo: history.map(|h| h.policy),
+        });
+    }
+    let replay = match recorder.replay.as_mut() {
+        None => return,
+        Some(r) => r,
+    };
+    let elapsed = status.timer.elapsed_secs();
+    let over = status.is_over();
+    let tile_moves = tile_trigger_evr
+        .iter()
+        .map(|e| Move::Trigger(e.0))
+        .chain(tile_mark_evr.iter().map(|e| Move::Mark(e.0)))
+        .chain(tile_chord_evr.iter().map(|e| Move::Chord(e.0)))
+        .filter(|_| !over);
+    // Boards without an undo policy ignore the events
+    let undo = replay.undo.is_some();
+    let history_moves = undo_evr
+        .iter()
+        .map(|_| Move::Undo)
+        .chain(redo_evr.iter().map(|_| Move::Redo))
+        .filter(|_| undo);
+    replay
+        .moves
+        .extend(tile_moves.chain(history_moves).map(|m| (elapsed, m)));
+}
+
+/// Sends the replay moves that are due as tile events
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn play_moves(
+    board: Option<Res<Board>>,
+    player: Option<ResMut<ReplayPlayer>>,
+    status: Option<Res<BoardStatus>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+    mut undo_ewr: EventWriter<UndoEvent>,
+    mut redo_ewr: EventWriter<RedoEvent>,
+) {
+    let (board, mut player, status) = match (board, player, status) {
+        (Some(b), Some(p), Some(s)) => (b, p, s),
+        _ => return,
+    };
+    // Tiles uncovered along a new board are not moves, nor could they be undone
+    if board.is_added() {
+        return;
+    }
+    // Only undo and redo moves follow the end of the game
+    if status.is_over() && !player.peek().is_some_and(Move::is_history) {
+        return;
+    }
+    let elapsed = status.timer.elapsed_secs();
+    while let Some(next) = player.next_move(elapsed) {
+        log::debug!("Replaying {:?}", next);
+        match next {
+            Move::Trigger(coords) => tile_trigger_ewr.send(TileTriggerEvent(coords)),
+            Move::Mark(coords) => tile_mark_ewr.send(TileMarkEvent(coords)),
+            Move::Chord(coords) => tile_chord_ewr.send(TileChordEvent(coords)),
+            Move::Undo => undo_ewr.send(UndoEvent),
+            Move::Redo => redo_ewr.send(RedoEvent),
+        }
+        // Moves are played one per frame when not in real time, and the game state changed by
+        // undo and redo moves is only known on the next frame
+        if !player.realtime || next.is_history() {
+            break;
+        }
+    }
+}
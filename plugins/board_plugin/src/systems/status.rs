@@ -0,0 +1,30 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{BoardCompletedEvent, BombExplosionEvent},
+    resources::{BoardState, BoardStatus},
+};
+
+/// Ticks the game timer and records the game outcome from the board events
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn update_status(
+    time: Res<Time>,
+    mut status: ResMut<BoardStatus>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    if status.is_over() {
+        return;
+    }
+    status.timer.tick(time.delta());
+    if bomb_explosion_evr.iter().count() > 0 {
+        log::info!("Game lost after {:.1}s", status.timer.elapsed_secs());
+        status.state = BoardState::Lost;
+        status.timer.pause();
+    } else if board_completed_evr.iter().count() > 0 {
+        log::info!("Game won after {:.1}s", status.timer.elapsed_secs());
+        status.state = BoardState::Won;
+        status.timer.pause();
+    }
+}
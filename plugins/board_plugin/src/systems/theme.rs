@@ -0,0 +1,133 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::Themed,
+    resources::{AccessibilityOptions, BoardAssets, BoardThemes, KeyBindings},
+};
+
+/// Selects the next theme pack on the theme key
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn switch_theme(
+    keys: Res<Input<KeyCode>>,
+    key_bindings: Option<Res<KeyBindings>>,
+    board_themes: Option<ResMut<BoardThemes>>,
+) {
+    let bindings = match key_bindings {
+        None => KeyBindings::default(),
+        Some(b) => b.clone(),
+    };
+    if let Some(mut board_themes) = board_themes {
+        if keys.any_just_pressed(bindings.theme) {
+            board_themes.next();
+        }
+    }
+}
+
+/// Switches the accessibility palette and toggles the counter glyphs on their keys
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn switch_accessibility(
+    keys: Res<Input<KeyCode>>,
+    key_bindings: Option<Res<KeyBindings>>,
+    mut accessibility: ResMut<AccessibilityOptions>,
+) {
+    let bindings = match key_bindings {
+        None => KeyBindings::default(),
+        Some(b) => b.clone(),
+    };
+    if keys.any_just_pressed(bindings.palette) {
+        accessibility.cycle_palette();
+        log::info!("Switched to palette {:?}", accessibility.palette);
+    }
+    if keys.any_just_pressed(bindings.glyphs) {
+        accessibility.glyphs = !accessibility.glyphs;
+    }
+}
+
+/// Copies the selected theme pack, with the accessibility options applied, into the
+/// `BoardAssets` resource when it is loaded, modified or selected, or the options change
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn apply_theme(
+    mut commands: Commands,
+    board_themes: Option<Res<BoardThemes>>,
+    accessibility: Res<AccessibilityOptions>,
+    themes: Res<Assets<BoardAssets>>,
+    mut theme_evr: EventReader<AssetEvent<BoardAssets>>,
+) {
+    let board_themes = match board_themes {
+        None => return,
+        Some(t) => t,
+    };
+    let current = match board_themes.current() {
+        None => return,
+        Some(h) => h,
+    };
+    let updated = theme_evr.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle == current,
+        AssetEvent::Removed { .. } => false,
+    });
+    if !updated && !board_themes.is_changed() && !accessibility.is_changed() {
+        return;
+    }
+    // Themes selected before they finish loading are applied on their creation event
+    if let Some(theme) = themes.get(current) {
+        log::info!("Applying theme {}", theme.label);
+        let mut board_assets = theme.clone();
+        accessibility.apply(&mut board_assets);
+        commands.insert_resource(board_assets);
+    }
+}
+
+/// Updates the sprites and texts of spawned entities to the current `BoardAssets`
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+pub fn reskin(
+    board_assets: Option<Res<BoardAssets>>,
+    mut themed: Query<(
+        &Themed,
+        Option<&mut Sprite>,
+        Option<&mut Handle<Image>>,
+        Option<&mut Text>,
+    )>,
+) {
+    let board_assets = match board_assets {
+        Some(a) if a.is_changed() && !a.is_added() => a,
+        _ => return,
+    };
+    for (themed, sprite, texture, text) in themed.iter_mut() {
+        let material = match *themed {
+            Themed::Board => &board_assets.board_material,
+            Themed::Tile => &board_assets.tile_material,
+            Themed::CoveredTile => &board_assets.covered_tile_material,
+            Themed::Bomb => &board_assets.bomb_material,
+            Themed::Flag => &board_assets.flag_material,
+            Themed::Question => &board_assets.question_material,
+            Themed::Cursor => &board_assets.cursor_material,
+            // Counters are rebuilt as the glyphs may have been switched on or off
+            Themed::BombCounter(count) => {
+                if let Some(mut text) = text {
+                    let size = text.sections.first().map_or(0.0, |s| s.style.font_size);
+                    *text = board_assets.bomb_count_text(count, size);
+                }
+                continue;
+            }
+            Themed::Label => {
+                for section in text
+                    .into_iter()
+                    .flat_map(|t| t.into_inner().sections.iter_mut())
+                {
+                    section.style.font = board_assets.bomb_counter_font.clone();
+                }
+                continue;
+            }
+        };
+        if let Some(mut sprite) = sprite {
+            sprite.color = material.colour;
+        }
+        if let Some(mut texture) = texture {
+            *texture = material.texture.clone();
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use bevy::input::touch::{TouchInput, TouchPhase, Touches};
+use bevy::render::camera::Camera2d;
+use bevy::utils::HashMap;
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{CameraOptions, TouchOptions},
+    systems::camera::cursor_to_world,
+    Board,
+};
+
+/// A finger currently down on the screen
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TouchPress {
+    /// Seconds since the finger went down
+    held: f32,
+    /// Set once the press moved, was part of a pinch or already marked a tile
+    consumed: bool,
+}
+
+/// Maps touch gestures to board actions: a tap uncovers a covered tile or chords a revealed
+/// number, a long press marks a tile. Presses that move or take part in a pinch are ignored.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn touch_handling(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    touches: Res<Touches>,
+    board: Res<Board>,
+    touch_options: Option<Res<TouchOptions>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut presses: Local<HashMap<u64, TouchPress>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    let options = match touch_options {
+        None => TouchOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let (camera, camera_transform) = match cameras.get_single() {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to retrieve the 2D camera: {}", e);
+            return;
+        }
+    };
+    let tile_at = |position: Vec2| {
+        board.world_to_tile(cursor_to_world(window, camera, camera_transform, position))
+    };
+
+    for touch in touches.iter_just_pressed() {
+        presses.insert(touch.id(), TouchPress::default());
+    }
+    let pinching = touches.iter().count() > 1;
+    for touch in touches.iter() {
+        let press = match presses.get_mut(&touch.id()) {
+            None => continue,
+            Some(p) => p,
+        };
+        press.held += time.delta_seconds();
+        if pinching || touch.distance().length() > options.tap_distance {
+            press.consumed = true;
+        }
+        if !press.consumed && press.held >= options.long_press_duration {
+            press.consumed = true;
+            if let Some(coordinates) = tile_at(touch.position()) {
+                log::info!("Trying to mark tile on {}", coordinates);
+                tile_mark_ewr.send(TileMarkEvent(coordinates));
+            }
+        }
+    }
+    for touch in touches.iter_just_released() {
+        let press = match presses.remove(&touch.id()) {
+            None => continue,
+            Some(p) => p,
+        };
+        if press.consumed || touch.distance().length() > options.tap_distance {
+            continue;
+        }
+        if let Some(coordinates) = tile_at(touch.position()) {
+            if board.covered_tiles.contains_key(&coordinates) {
+                log::info!("Trying to uncover tile on {}", coordinates);
+                tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+            } else {
+                log::info!("Trying to chord tile on {}", coordinates);
+                tile_chord_ewr.send(TileChordEvent(coordinates));
+            }
+        }
+    }
+    for touch in touches.iter_just_cancelled() {
+        presses.remove(&touch.id());
+    }
+}
+
+/// Zooms and pans the 2D camera with two fingers, keeping the world point under the pinch centre
+/// in place
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn pinch_zoom(
+    windows: Res<Windows>,
+    touches: Res<Touches>,
+    mut touch_evr: EventReader<TouchInput>,
+    camera_options: Option<Res<CameraOptions>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    // Previous positions are only refreshed when a finger moves
+    if !touch_evr
+        .iter()
+        .any(|event| event.phase == TouchPhase::Moved)
+    {
+        return;
+    }
+    let mut fingers = touches.iter();
+    let (first, second) = match (fingers.next(), fingers.next(), fingers.next()) {
+        (Some(first), Some(second), None) => (first, second),
+        _ => return,
+    };
+    let previous_distance = first
+        .previous_position()
+        .distance(second.previous_position());
+    let distance = first.position().distance(second.position());
+    if previous_distance <= 0.0 || distance <= 0.0 {
+        return;
+    }
+
+    let options = match camera_options {
+        None => CameraOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let window = match windows.get_primary() {
+        Some(w) => w,
+        None => return,
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let previous_centre = (first.previous_position() + second.previous_position()) * 0.5;
+    let centre = (first.position() + second.position()) * 0.5;
+    // Offset of the pinch centre from the window centre
+    let offset = centre - window_size * 0.5;
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        let scale = (projection.scale * previous_distance / distance)
+            .clamp(options.min_scale, options.max_scale);
+        let delta =
+            (previous_centre - centre) * projection.scale + offset * (projection.scale - scale);
+        transform.translation += delta.extend(0.0);
+        projection.scale = scale;
+    }
+}
@@ -1,66 +1,113 @@
 use bevy::{log, prelude::*};
 
 use crate::{
-    components::Uncover,
-    events::{BoardCompletedEvent, BombExplosionEvent, TileTriggerEvent},
-    Board, Bomb, BombNeighbour, Coordinates,
+    components::{ChunkDirty, TileChunk},
+    events::{
+        BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileTriggerEvent,
+        TilesUncoveredEvent,
+    },
+    resources::{AnimationOptions, BoardChange, BoardHistory, BoardStatus},
+    systems::animation,
+    Board,
 };
 
+/// Queues the triggered tiles for uncovering. Triggers are dropped once the game is over, so only
+/// undo and redo change a finished board.
 #[allow(clippy::needless_pass_by_value)]
 #[inline]
 pub fn trigger_event_handler(
-    mut commands: Commands,
-    board: Res<Board>,
+    mut board: ResMut<Board>,
+    status: Option<Res<BoardStatus>>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
 ) {
-    for trigger_event in tile_trigger_evr.iter() {
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
+    let over = status.as_deref().is_some_and(BoardStatus::is_over);
+    // Dropped events are still read, not to be replayed after an undo
+    for trigger_event in tile_trigger_evr.iter().filter(|_| !over) {
+        if board.tile_to_uncover(&trigger_event.0).is_some() {
+            board.pending_uncover.push(trigger_event.0);
         }
     }
 }
 
+/// Queues the covered neighbours of chorded tiles for uncovering, dropping chords once the game
+/// is over as triggers are.
 #[allow(clippy::needless_pass_by_value)]
 #[inline]
+pub fn chord_event_handler(
+    mut board: ResMut<Board>,
+    status: Option<Res<BoardStatus>>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    let over = status.as_deref().is_some_and(BoardStatus::is_over);
+    for chord_event in tile_chord_evr.iter().filter(|_| !over) {
+        let tiles = board.tiles_to_chord(chord_event.0);
+        board.pending_uncover.extend(tiles);
+    }
+}
+
+/// Uncovers the tiles pending in the board and flood fills from the empty ones, all within the
+/// frame. Covers are then removed by the reveal animation, staggered by flood fill depth, and
+/// tile chunks are marked for a mesh rebuild.
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+#[inline]
 pub fn uncover_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbour>)>,
+    animation_options: Option<Res<AnimationOptions>>,
+    status: Option<Res<BoardStatus>>,
+    history: Option<ResMut<BoardHistory>>,
+    chunks: Query<(), With<TileChunk>>,
     mut board_completed_event_wr: EventWriter<BoardCompletedEvent>,
     mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
+    mut tiles_uncovered_event_wr: EventWriter<TilesUncoveredEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        commands.entity(entity).despawn_recursive();
-
-        let (coords, bomb, bomb_counter) = match parents.get(parent.0) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("{}", e);
-                continue;
-            }
-        };
-
-        // We remove the entity from the board covered tile map
-        match board.try_uncover_tile(coords) {
-            None => log::debug!("Tried to uncover an already uncovered tile"),
-            Some(e) => log::debug!("Uncovered tile {} (entity: {:?})", coords, e),
-        }
-        if board.is_completed() {
-            log::info!("*Board completed*");
-            board_completed_event_wr.send(BoardCompletedEvent);
+    // Checked through `Deref` first so boards are not flagged as changed every frame
+    if board.pending_uncover.is_empty() {
+        return;
+    }
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    // Flood fills stop before bombs, so explosions only happen on requested tiles
+    let starts = std::mem::take(&mut board.pending_uncover);
+    // Tiles uncovered with a new board are restored or opened for the player, not moves
+    let history = history.filter(|_| !board.is_added());
+    let marks = history.as_ref().map(|_| board.marked_tiles.clone());
+    let uncovered = board.uncover(starts);
+    log::debug!("Uncovered {} tiles", uncovered.len());
+    let mut tiles = Vec::with_capacity(uncovered.len());
+    for (coords, entity, depth) in uncovered {
+        tiles.push(coords);
+        if chunks.contains(entity) {
+            commands.entity(entity).insert(ChunkDirty);
+        } else {
+            animation::reveal(&mut commands, entity, depth, &options);
         }
-        if bomb.is_some() {
+        if board.tile_map.is_bomb_at(coords) {
             log::info!("Boom!");
+            // Above the tiles and their covers
+            let position = board.tile_centre(coords);
+            animation::explode(
+                &mut commands,
+                board.entity,
+                position.extend(4.0),
+                board.tile_size,
+                &options,
+            );
             bomb_explosion_event_wr.send(BombExplosionEvent);
         }
-        // If the tile is empty..
-        else if bomb_counter.is_none() {
-            // .. We propagate the uncovering by adding the `Uncover` component to adjacent tiles
-            // which will then be removed next frame
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
-            }
+    }
+    match (history, marks, status) {
+        (Some(mut history), Some(marks), Some(status)) if !tiles.is_empty() => {
+            let change = BoardChange::reveal(&board, tiles.clone(), &marks, status.state);
+            history.record(change);
         }
+        _ => (),
+    }
+    tiles_uncovered_event_wr.send(TilesUncoveredEvent(tiles));
+    if board.is_completed() {
+        log::info!("*Board completed*");
+        board_completed_event_wr.send(BoardCompletedEvent);
     }
 }
@@ -0,0 +1,110 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{ChunkDirty, Themed, TileChunk},
+    events::{RedoEvent, UndoEvent},
+    resources::{AnimationOptions, BoardHistory, BoardStatus},
+    systems::{animation, mark::redraw_mark},
+    Board, BoardAssets, BoardOptions,
+};
+
+/// Takes back and plays again the moves of the board history. Covers are spawned again on the
+/// tile entities of the covered tiles, and the mark sprites of the changed tiles are redrawn.
+#[inline]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn undo_event_handler(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    mut status: ResMut<BoardStatus>,
+    history: Option<ResMut<BoardHistory>>,
+    board_assets: Res<BoardAssets>,
+    board_options: Option<Res<BoardOptions>>,
+    animation_options: Option<Res<AnimationOptions>>,
+    mut undo_evr: EventReader<UndoEvent>,
+    mut redo_evr: EventReader<RedoEvent>,
+    children: Query<&Children>,
+    chunks: Query<(), With<TileChunk>>,
+) {
+    // Boards without an undo policy ignore the events
+    let mut history = match history {
+        None => return,
+        Some(h) => h,
+    };
+    let options = match animation_options {
+        None => AnimationOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let tile_padding = board_options.map_or(0.0, |o| o.tile_padding);
+    let tile_size = board.tile_size;
+
+    for _ in undo_evr.iter() {
+        let change = history.undo(&mut board, &mut status, |_, tile| {
+            // Chunk meshes draw the covers from the board state
+            if chunks.contains(tile) {
+                commands.entity(tile).insert(ChunkDirty);
+                return tile;
+            }
+            let cover = commands
+                .spawn_bundle(board_assets.covered_tile_bundle(tile_size - tile_padding))
+                .insert(Name::new("Tile Cover"))
+                .insert(Themed::CoveredTile)
+                .id();
+            commands.entity(tile).push_children(&[cover]);
+            cover
+        });
+        let change = match change {
+            None => {
+                log::info!("No move to undo");
+                continue;
+            }
+            Some(c) => c,
+        };
+        log::info!("Undid a move uncovering {} tiles", change.uncovered.len());
+        for &(coords, mark, _) in &change.marks {
+            if let Some(&entity) = board.covered_tiles.get(&coords) {
+                redraw_mark(
+                    &mut commands,
+                    entity,
+                    mark,
+                    &children,
+                    &chunks,
+                    &board_assets,
+                    tile_size,
+                    &options,
+                );
+            }
+        }
+    }
+
+    for _ in redo_evr.iter() {
+        let (change, uncovered) = match history.redo(&mut board, &mut status) {
+            None => {
+                log::info!("No move to redo");
+                continue;
+            }
+            Some(r) => r,
+        };
+        log::info!("Redid a move uncovering {} tiles", uncovered.len());
+        for (_, entity) in uncovered {
+            if chunks.contains(entity) {
+                commands.entity(entity).insert(ChunkDirty);
+            } else {
+                animation::reveal(&mut commands, entity, 0, &options);
+            }
+        }
+        for &(coords, _, mark) in &change.marks {
+            if let Some(&entity) = board.covered_tiles.get(&coords) {
+                redraw_mark(
+                    &mut commands,
+                    entity,
+                    mark,
+                    &children,
+                    &chunks,
+                    &board_assets,
+                    tile_size,
+                    &options,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::{
+    asset::{AssetLoader, AssetPath, BoxedFuture, LoadContext, LoadedAsset},
+    log,
+    prelude::*,
+};
+
+use crate::{
+    resources::{AccessibilityOptions, BoardAssets, BoardTheme, SpriteMaterial, ThemeMaterial},
+    systems,
+};
+
+/// Loads `*.theme.ron` theme packs as `BoardAssets`, along with their textures and font
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BoardThemeLoader;
+
+impl BoardThemeLoader {
+    fn material(material: &ThemeMaterial, load_context: &LoadContext) -> SpriteMaterial {
+        SpriteMaterial {
+            colour: material.colour,
+            texture: match &material.texture {
+                None => SpriteMaterial::default().texture,
+                Some(path) => load_context.get_handle(AssetPath::new_ref(path.as_ref(), None)),
+            },
+        }
+    }
+}
+
+impl AssetLoader for BoardThemeLoader {
+    #[inline]
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, anyhow::Result<()>> {
+        Box::pin(async move {
+            let theme: BoardTheme = ron::de::from_bytes(bytes)?;
+            let assets = BoardAssets {
+                label: theme.label.clone(),
+                board_material: Self::material(&theme.board, load_context),
+                tile_material: Self::material(&theme.tile, load_context),
+                covered_tile_material: Self::material(&theme.covered_tile, load_context),
+                bomb_counter_font: load_context
+                    .get_handle(AssetPath::new_ref(theme.bomb_counter_font.as_ref(), None)),
+                bomb_counter_colours: theme.bomb_counter_colours.clone(),
+                bomb_counter_glyphs: theme.bomb_counter_glyphs,
+                flag_material: Self::material(&theme.flag, load_context),
+                question_material: Self::material(&theme.question, load_context),
+                bomb_material: Self::material(&theme.bomb, load_context),
+                cursor_material: Self::material(&theme.cursor, load_context),
+            };
+            let dependencies = [
+                &theme.board,
+                &theme.tile,
+                &theme.covered_tile,
+                &theme.flag,
+                &theme.question,
+                &theme.bomb,
+                &theme.cursor,
+            ]
+            .into_iter()
+            .filter_map(|material| material.texture.clone())
+            .chain(std::iter::once(theme.bomb_counter_font))
+            .map(|path| AssetPath::new(path.into(), None));
+            let mut asset = LoadedAsset::new(assets);
+            for dependency in dependencies {
+                asset = asset.with_dependency(dependency);
+            }
+            load_context.set_default_asset(asset);
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+/// Theme packs for the board, loaded as `BoardAssets` assets. The selected theme of the
+/// `BoardThemes` resource is copied into the `BoardAssets` resource once loaded, and again when
+/// it changes on disk, another theme is selected or the `AccessibilityOptions` change,
+/// re-skinning the spawned board in place.
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        app.add_asset::<BoardAssets>()
+            .init_asset_loader::<BoardThemeLoader>()
+            .init_resource::<AccessibilityOptions>()
+            .add_system(systems::theme::switch_theme)
+            .add_system(systems::theme::switch_accessibility)
+            .add_system(
+                systems::theme::apply_theme
+                    .after(systems::theme::switch_theme)
+                    .after(systems::theme::switch_accessibility),
+            )
+            .add_system(systems::theme::reskin.after(systems::theme::apply_theme));
+
+        log::info!("Loaded Theme Plugin");
+    }
+}
@@ -0,0 +1,83 @@
+//! Checks the board state is updated instantly while the reveal animation plays on
+
+use bevy::prelude::*;
+use board_plugin::{
+    components::{Coordinates, RevealAnimation},
+    events::{BoardCompletedEvent, TileTriggerEvent},
+    resources::{AnimationOptions, Board, BoardOptions},
+};
+
+mod common;
+
+/// Headless app running a `BoardPlugin` on a 5x5 board without bombs
+fn board_app(animation_options: AnimationOptions) -> App {
+    let mut app = common::board_app(BoardOptions {
+        map_size: (5, 5),
+        bomb_count: 0,
+        ..BoardOptions::default()
+    });
+    app.insert_resource(animation_options);
+    common::add_window(&mut app);
+    app.update();
+    app
+}
+
+/// Triggers the centre tile and runs until the trigger is handled
+fn reveal(app: &mut App) -> usize {
+    common::send(app, TileTriggerEvent(Coordinates::new(2, 2)));
+    let mut completed = 0;
+    for _ in 0..3 {
+        app.update();
+        completed += common::drain::<BoardCompletedEvent>(app).len();
+    }
+    completed
+}
+
+fn reveal_animations(app: &mut App) -> Vec<RevealAnimation> {
+    app.world
+        .query::<&RevealAnimation>()
+        .iter(&app.world)
+        .copied()
+        .collect()
+}
+
+#[test]
+fn flood_fill_is_instant_and_animated() {
+    let mut app = board_app(AnimationOptions {
+        reveal_duration: 60.0,
+        ..AnimationOptions::default()
+    });
+    let completed = reveal(&mut app);
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert!(board.covered_tiles.is_empty());
+    assert_eq!(completed, 1);
+    // Every cover is still fading out, the outer ring starting two steps after the centre
+    let animations = reveal_animations(&mut app);
+    assert_eq!(animations.len(), 25);
+    let stagger = AnimationOptions::default().reveal_stagger;
+    let max_delay = animations.iter().map(|a| a.delay).fold(0.0, f32::max);
+    assert!((max_delay - 2.0 * stagger).abs() < 1e-6);
+}
+
+#[test]
+fn disabled_animations_remove_covers_at_once() {
+    let mut app = board_app(AnimationOptions::disabled());
+    let completed = reveal(&mut app);
+
+    assert!(app
+        .world
+        .get_resource::<Board>()
+        .unwrap()
+        .covered_tiles
+        .is_empty());
+    assert_eq!(completed, 1);
+    assert!(reveal_animations(&mut app).is_empty());
+    let covers = app
+        .world
+        .query::<&Name>()
+        .iter(&app.world)
+        .filter(|name| name.as_str() == "Tile Cover")
+        .count();
+    assert_eq!(covers, 0);
+}
@@ -0,0 +1,190 @@
+//! Mark, uncover, chord and completion rules of the board
+
+use bevy::prelude::{Entity, Vec2};
+use bevy::utils::HashMap;
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    resources::{Board, BoardSave, Mark},
+};
+
+/// Fully covered board from a text grid
+fn text_board(text: &str) -> Board {
+    let tile_map = BoardSave::from_text(text).unwrap().tile_map();
+    let (width, height) = (tile_map.width(), tile_map.height());
+    let mut covered_tiles = HashMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            covered_tiles.insert(
+                Coordinates::new(x as u16, y as u16),
+                Entity::from_raw((x + y * width) as u32),
+            );
+        }
+    }
+    Board::new(
+        Entity::from_raw(u32::MAX),
+        tile_map,
+        covered_tiles,
+        Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32)),
+        1.0,
+    )
+}
+
+/// 4x3 board with a bomb in the bottom left corner
+const CORNER_BOMB: &str = "\
+....
+....
+*...
+";
+
+#[test]
+fn marks_cycle_through_question_marks_when_enabled() {
+    let mut board = text_board(CORNER_BOMB);
+    let coords = Coordinates::new(1, 1);
+    let entity = board.covered_tiles[&coords];
+
+    let marks: Vec<_> = (0..4)
+        .map(|_| board.try_toggle_mark(&coords, true).unwrap())
+        .collect();
+    assert_eq!(
+        marks,
+        vec![
+            (entity, Some(Mark::Flag)),
+            (entity, Some(Mark::Question)),
+            (entity, None),
+            (entity, Some(Mark::Flag)),
+        ]
+    );
+
+    let mut board = text_board(CORNER_BOMB);
+    assert_eq!(
+        board.try_toggle_mark(&coords, false),
+        Some((entity, Some(Mark::Flag)))
+    );
+    assert_eq!(board.try_toggle_mark(&coords, false), Some((entity, None)));
+    assert!(board.marked_tiles.is_empty());
+}
+
+#[test]
+fn uncovered_and_outside_tiles_cannot_be_marked() {
+    let mut board = text_board(CORNER_BOMB);
+    let uncovered = Coordinates::new(3, 2);
+    assert!(!board.uncover([uncovered]).is_empty());
+    assert_eq!(board.try_toggle_mark(&uncovered, true), None);
+    assert_eq!(board.try_toggle_mark(&Coordinates::new(4, 0), true), None);
+    assert!(board.marked_tiles.is_empty());
+}
+
+#[test]
+fn flags_protect_tiles_and_count_against_bombs() {
+    let mut board = text_board(CORNER_BOMB);
+    let bomb = Coordinates::new(0, 0);
+    let safe = Coordinates::new(1, 0);
+    board.try_toggle_mark(&bomb, true);
+    assert!(board.is_flagged(&bomb));
+    assert_eq!(board.tile_to_uncover(&bomb), None);
+    assert_eq!(board.remaining_bombs(), 0);
+
+    // Extra flags and question marks
+    board.try_toggle_mark(&safe, true);
+    board.try_toggle_mark(&Coordinates::new(2, 0), true);
+    board.try_toggle_mark(&Coordinates::new(2, 0), true);
+    assert_eq!(board.flag_count(), 2);
+    assert_eq!(board.remaining_bombs(), -1);
+
+    // Questioned tiles can still be uncovered, losing their mark
+    let questioned = Coordinates::new(2, 0);
+    assert!(board.tile_to_uncover(&questioned).is_some());
+    assert!(board.try_uncover_tile(&questioned).is_some());
+    assert!(!board.marked_tiles.contains_key(&questioned));
+}
+
+#[test]
+fn chords_need_as_many_flags_as_bombs() {
+    let mut board = text_board(CORNER_BOMB);
+    let counter = Coordinates::new(1, 1);
+    // Covered tiles don't chord
+    assert!(board.tiles_to_chord(counter).is_empty());
+    assert!(board.try_uncover_tile(&counter).is_some());
+    assert!(board.tiles_to_chord(counter).is_empty());
+
+    board.try_toggle_mark(&Coordinates::new(0, 0), false);
+    let mut chord = board.tiles_to_chord(counter);
+    chord.sort_unstable();
+    assert_eq!(chord.len(), 7);
+    assert!(!chord.contains(&Coordinates::new(0, 0)));
+
+    // Wrong flags still chord, the count is all that matters
+    board.try_toggle_mark(&Coordinates::new(0, 0), false);
+    board.try_toggle_mark(&Coordinates::new(2, 2), false);
+    assert!(board
+        .tiles_to_chord(counter)
+        .contains(&Coordinates::new(0, 0)));
+}
+
+#[test]
+fn boards_complete_when_only_bombs_are_covered() {
+    let mut board = text_board(CORNER_BOMB);
+    assert!(!board.is_completed());
+    // Flood fill from the far corner uncovers every safe tile
+    let uncovered = board.uncover([Coordinates::new(3, 2)]);
+    assert_eq!(uncovered.len(), 11);
+    assert!(board.is_completed());
+    assert!(board.covered_tiles.contains_key(&Coordinates::new(0, 0)));
+}
+
+#[test]
+fn flood_fill_stops_at_counters_and_clears_wrong_flags() {
+    let mut board = text_board(
+        "\
+..*..
+..*..
+..*..
+..*..
+",
+    );
+    let wrong_flag = Coordinates::new(4, 3);
+    board.try_toggle_mark(&wrong_flag, false);
+    let mut uncovered: Vec<_> = board
+        .uncover([Coordinates::new(4, 0)])
+        .into_iter()
+        .map(|(coords, _, _)| coords)
+        .collect();
+    uncovered.sort_unstable();
+    let expected: Vec<_> = (3..5)
+        .flat_map(|x| (0..4).map(move |y| Coordinates::new(x, y)))
+        .collect();
+    assert_eq!(uncovered, expected);
+    // The bomb wall keeps the left side covered
+    assert!(board.covered_tiles.contains_key(&Coordinates::new(1, 0)));
+    assert!(board.covered_tiles.contains_key(&Coordinates::new(2, 0)));
+    assert!(!board.marked_tiles.contains_key(&wrong_flag));
+}
+
+#[test]
+fn world_positions_map_to_tiles_and_back() {
+    let mut board = text_board(CORNER_BOMB);
+    board.bounds.mins = Vec2::new(-2.0, -1.5);
+    board.tile_size = 1.0;
+    assert_eq!(
+        board.world_to_tile(Vec2::new(-2.0, -1.5)),
+        Some(Coordinates::new(0, 0))
+    );
+    assert_eq!(
+        board.world_to_tile(Vec2::new(1.9, 1.4)),
+        Some(Coordinates::new(3, 2))
+    );
+    // The far edges are outside of the board
+    assert_eq!(board.world_to_tile(Vec2::new(2.0, 0.0)), None);
+    assert_eq!(board.world_to_tile(Vec2::new(0.0, 1.5)), None);
+    assert_eq!(board.world_to_tile(Vec2::new(-2.1, 0.0)), None);
+
+    for coords in board.covered_tiles.keys().copied().collect::<Vec<_>>() {
+        let centre = board.tile_to_world(coords);
+        assert_eq!(board.world_to_tile(centre), Some(coords));
+    }
+    assert_eq!(
+        board.tile_to_world(Coordinates::new(1, 0)),
+        Vec2::new(-0.5, -1.0)
+    );
+}
@@ -0,0 +1,237 @@
+//! Undo and redo of moves under each undo policy
+
+use bevy::prelude::{Entity, Vec2};
+use bevy::utils::HashMap;
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    resources::{
+        Board, BoardChange, BoardHistory, BoardSave, BoardState, BoardStatus, Mark, UndoPolicy,
+    },
+};
+
+/// Fully covered board from a text grid, tiles being their own cover entity
+fn text_board(text: &str) -> Board {
+    let tile_map = BoardSave::from_text(text).unwrap().tile_map();
+    let (width, height) = (tile_map.width(), tile_map.height());
+    let mut covered_tiles = HashMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            covered_tiles.insert(
+                Coordinates::new(x as u16, y as u16),
+                Entity::from_raw((x + y * width) as u32),
+            );
+        }
+    }
+    let mut board = Board::new(
+        Entity::from_raw(u32::MAX),
+        tile_map,
+        covered_tiles.clone(),
+        Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32)),
+        1.0,
+    );
+    board.tile_entities = covered_tiles;
+    board
+}
+
+/// 5x3 board with a bomb in the middle of the left side
+const BOARD: &str = "\
+.....
+*....
+.....
+";
+
+/// Board, status and history of a game, played as the board systems do
+struct Game {
+    board: Board,
+    status: BoardStatus,
+    history: BoardHistory,
+}
+
+impl Game {
+    fn new(policy: UndoPolicy) -> Self {
+        Self {
+            board: text_board(BOARD),
+            status: BoardStatus::new(),
+            history: BoardHistory::new(policy),
+        }
+    }
+
+    fn reveal(&mut self, x: u16, y: u16) {
+        let marks = self.board.marked_tiles.clone();
+        let uncovered: Vec<_> = self
+            .board
+            .uncover([Coordinates::new(x, y)])
+            .into_iter()
+            .map(|(coords, _, _)| coords)
+            .collect();
+        let change = BoardChange::reveal(&self.board, uncovered, &marks, self.status.state);
+        self.status.state = change.state_after;
+        self.history.record(change);
+    }
+
+    fn mark(&mut self, x: u16, y: u16) {
+        let coords = Coordinates::new(x, y);
+        let before = self.board.marked_tiles.get(&coords).copied();
+        let (_, after) = self.board.try_toggle_mark(&coords, false).unwrap();
+        let change = BoardChange::mark(coords, before, after, self.status.state);
+        self.history.record(change);
+    }
+
+    fn undo(&mut self) -> bool {
+        self.history
+            .undo(&mut self.board, &mut self.status, |_, tile| tile)
+            .is_some()
+    }
+
+    fn redo(&mut self) -> bool {
+        self.history
+            .redo(&mut self.board, &mut self.status)
+            .is_some()
+    }
+
+    /// Covered tiles and marks of the board
+    fn state(&self) -> (HashMap<Coordinates, Entity>, HashMap<Coordinates, Mark>) {
+        (
+            self.board.covered_tiles.clone(),
+            self.board.marked_tiles.clone(),
+        )
+    }
+}
+
+#[test]
+fn unlimited_undo_restores_the_exact_board() {
+    let mut game = Game::new(UndoPolicy::Unlimited);
+    let start = game.state();
+    // A wrong flag, cleared by the flood fill
+    game.mark(4, 2);
+    let flagged = game.state();
+    game.reveal(4, 0);
+    let revealed = game.state();
+    assert_eq!(game.board.covered_tiles.len(), 3);
+    assert!(game.board.marked_tiles.is_empty());
+
+    assert!(game.undo());
+    assert_eq!(game.state(), flagged);
+    assert!(game.undo());
+    assert_eq!(game.state(), start);
+    assert!(!game.undo());
+
+    assert!(game.redo());
+    assert_eq!(game.state(), flagged);
+    assert!(game.redo());
+    assert_eq!(game.state(), revealed);
+    assert!(!game.redo());
+
+    // New moves drop the undone ones
+    assert!(game.undo());
+    game.mark(0, 0);
+    assert!(!game.history.can_redo());
+}
+
+#[test]
+fn undoing_the_losing_move_resumes_the_game() {
+    let mut game = Game::new(UndoPolicy::Unlimited);
+    game.reveal(4, 0);
+    game.reveal(0, 1);
+    assert_eq!(game.status.state, BoardState::Lost);
+    game.status.timer.pause();
+
+    assert!(game.undo());
+    assert_eq!(game.status.state, BoardState::Playing);
+    assert!(!game.status.timer.paused());
+    assert!(game
+        .board
+        .covered_tiles
+        .contains_key(&Coordinates::new(0, 1)));
+
+    assert!(game.redo());
+    assert_eq!(game.status.state, BoardState::Lost);
+    assert!(game.status.timer.paused());
+}
+
+#[test]
+fn winning_moves_are_undone_as_well() {
+    let mut game = Game::new(UndoPolicy::Unlimited);
+    game.reveal(4, 0);
+    for (x, y) in [(0, 0), (0, 2)] {
+        game.reveal(x, y);
+    }
+    assert_eq!(game.status.state, BoardState::Won);
+    assert!(game.undo());
+    assert_eq!(game.status.state, BoardState::Playing);
+    assert!(!game.board.is_completed());
+}
+
+#[test]
+fn flags_only_stops_at_the_last_reveal() {
+    let mut game = Game::new(UndoPolicy::FlagsOnly);
+    game.mark(0, 1);
+    game.reveal(4, 0);
+    let revealed = game.state();
+    assert!(!game.undo());
+
+    game.mark(0, 0);
+    game.mark(0, 0);
+    assert!(game.undo());
+    assert_eq!(
+        game.board.marked_tiles.get(&Coordinates::new(0, 0)),
+        Some(&Mark::Flag)
+    );
+    assert!(game.undo());
+    assert_eq!(game.state(), revealed);
+    // The flag placed before the reveal stays
+    assert!(!game.undo());
+    assert!(game.board.is_flagged(&Coordinates::new(0, 1)));
+    assert!(game.redo());
+    assert!(game.board.is_flagged(&Coordinates::new(0, 0)));
+}
+
+#[test]
+fn after_death_undoes_the_losing_move_once_with_a_penalty() {
+    let mut game = Game::new(UndoPolicy::AfterDeath { penalty: 10.0 });
+    game.mark(4, 2);
+    assert!(!game.undo());
+    game.reveal(4, 0);
+    let before_death = game.state();
+    game.reveal(0, 1);
+    // Marks placed once dead don't hide the losing move
+    game.mark(0, 0);
+
+    assert!(game.undo());
+    assert_eq!(game.status.state, BoardState::Playing);
+    assert_eq!(game.status.timer.elapsed_secs(), 10.0);
+    assert_eq!(game.state().0, before_death.0);
+    assert!(!game.history.can_redo());
+
+    game.reveal(0, 1);
+    assert_eq!(game.status.state, BoardState::Lost);
+    assert!(!game.undo());
+}
+
+#[test]
+fn menu_cycles_through_every_policy() {
+    let mut policies = vec![None];
+    loop {
+        let next = UndoPolicy::cycle(*policies.last().unwrap());
+        if next.is_none() {
+            break;
+        }
+        policies.push(next);
+    }
+    assert_eq!(
+        policies,
+        [
+            None,
+            Some(UndoPolicy::Unlimited),
+            Some(UndoPolicy::FlagsOnly),
+            Some(UndoPolicy::AfterDeath {
+                penalty: UndoPolicy::DEATH_PENALTY
+            }),
+        ]
+    );
+    assert_eq!(
+        policies[3].unwrap().to_string(),
+        format!("After death (+{}s)", UndoPolicy::DEATH_PENALTY)
+    );
+}
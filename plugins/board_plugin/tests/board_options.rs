@@ -0,0 +1,292 @@
+//! Checks properties of the board options over seeded random options and their validity
+//! boundaries: accepted options always create a playable board through the board plugin, refused
+//! ones never do, and validity only changes one way as the board grows or gets more bombs
+
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use board_plugin::{
+    events::BoardOptionsErrorEvent,
+    resources::{
+        Board, BoardOptions, BoardOptionsError, BoardPreset, BoardState, BoardStatus, TileSize,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+mod common;
+
+/// Options drawn from each seed, the seeds being fixed the same options are checked every run
+const CASES: usize = 2000;
+
+/// Options run through a board plugin by each seed, apps being slower to run
+const APP_CASES: usize = 300;
+
+/// Random options around the validity boundaries
+fn random_options(rng: &mut StdRng) -> BoardOptions {
+    let width = rng.gen_range(0..24);
+    let height = rng.gen_range(0..24);
+    let cells = width * height;
+    let tile_size = if rng.gen_bool(0.5) {
+        TileSize::Fixed(rng.gen_range(-10.0..60.0))
+    } else {
+        TileSize::Adaptive {
+            min: rng.gen_range(-10.0..40.0),
+            max: rng.gen_range(-10.0..80.0),
+        }
+    };
+    BoardOptions {
+        map_size: (width, height),
+        bomb_count: rng.gen_range(0..=cells + 2) as u16,
+        tile_size,
+        tile_padding: rng.gen_range(-5.0..50.0),
+        safe_start: rng.gen_bool(0.5),
+        ..BoardOptions::default()
+    }
+}
+
+/// Options on each side of every validity boundary
+fn boundary_options() -> Vec<BoardOptions> {
+    let max_side = usize::from(u16::MAX);
+    let board = |map_size: (usize, usize), bomb_count: u16| BoardOptions {
+        map_size,
+        bomb_count,
+        ..BoardOptions::default()
+    };
+    let padded = |tile_size: f32, tile_padding: f32| BoardOptions {
+        tile_size: TileSize::Fixed(tile_size),
+        tile_padding,
+        ..board((4, 4), 3)
+    };
+    vec![
+        board((0, 5), 0),
+        board((5, 0), 0),
+        board((1, 1), 0),
+        board((max_side + 1, 1), 0),
+        board((1, max_side + 1), 0),
+        board((4, 4), 16),
+        board((4, 4), 15),
+        board((4, 4), u16::MAX),
+        padded(20.0, 20.0),
+        padded(20.0, 19.9),
+        padded(20.0, 0.0),
+        padded(20.0, -0.1),
+        padded(20.0, f32::NAN),
+        padded(0.0, 0.0),
+        padded(f32::INFINITY, 1.0),
+        BoardOptions {
+            tile_size: TileSize::Adaptive {
+                min: 10.0,
+                max: 5.0,
+            },
+            ..board((4, 4), 3)
+        },
+    ]
+}
+
+/// Runs the options through a board plugin, checking accepted options create a playable board
+/// and refused options only send their error
+fn check_board_creation(options: &BoardOptions) {
+    let mut app = board_app(options.clone());
+    app.update();
+    app.update();
+    let errors = common::drain::<BoardOptionsErrorEvent>(&mut app);
+    match options.validate() {
+        Ok(()) => {
+            assert!(errors.is_empty(), "{:?} sent {:?}", options, errors);
+            let board = app.world.get_resource::<Board>().unwrap();
+            let (width, height) = options.map_size;
+            assert_eq!(
+                (board.tile_map.width(), board.tile_map.height()),
+                (width, height)
+            );
+            let bombs = board.tile_map.map().iter().filter(|t| t.is_bomb()).count();
+            assert_eq!(bombs, usize::from(options.bomb_count), "{:?}", options);
+            assert!(bombs < width * height, "{:?} has no safe tile", options);
+            // A safe start never uncovers a bomb
+            let status = app.world.get_resource::<BoardStatus>().unwrap();
+            assert_ne!(status.state, BoardState::Lost, "{:?}", options);
+        }
+        Err(e) => {
+            assert!(app.world.get_resource::<Board>().is_none(), "{:?}", options);
+            // Errors are compared by message, as NaN options never equal themselves
+            let errors: Vec<_> = errors
+                .into_iter()
+                .map(|event| event.0.to_string())
+                .collect();
+            assert_eq!(errors, [e.to_string()]);
+        }
+    }
+}
+
+#[test]
+fn accepted_options_create_a_playable_board() {
+    let mut rng = StdRng::seed_from_u64(36);
+    for _ in 0..APP_CASES {
+        check_board_creation(&random_options(&mut rng));
+    }
+}
+
+#[test]
+fn boundary_options_create_a_board_only_when_accepted() {
+    let accepted: Vec<_> = boundary_options()
+        .into_iter()
+        .map(|options| {
+            check_board_creation(&options);
+            options.validate().is_ok()
+        })
+        .collect();
+    assert_eq!(
+        accepted,
+        [
+            false, false, true, false, false, false, true, false, false, true, true, false, false,
+            false, false, false
+        ]
+    );
+}
+
+#[test]
+fn validity_only_changes_one_way() {
+    let mut rng = StdRng::seed_from_u64(35);
+    for _ in 0..CASES {
+        let options = random_options(&mut rng);
+        let (width, height) = options.map_size;
+        let fewer_bombs = BoardOptions {
+            bomb_count: options.bomb_count.saturating_sub(1),
+            ..options.clone()
+        };
+        let larger = BoardOptions {
+            map_size: (width + 1, height + 1),
+            ..options.clone()
+        };
+        let more_bombs = BoardOptions {
+            bomb_count: options.bomb_count.saturating_add(1),
+            ..options.clone()
+        };
+        if options.validate().is_ok() {
+            assert_eq!(fewer_bombs.validate(), Ok(()), "{:?}", options);
+            assert_eq!(larger.validate(), Ok(()), "{:?}", options);
+        }
+        if let Err(BoardOptionsError::TooManyBombs { .. }) = options.validate() {
+            assert!(more_bombs.validate().is_err(), "{:?}", options);
+        }
+    }
+}
+
+#[test]
+fn errors_name_the_first_invalid_option() {
+    let mut rng = StdRng::seed_from_u64(37);
+    for _ in 0..CASES {
+        let options = random_options(&mut rng);
+        let (width, height) = options.map_size;
+        match options.validate() {
+            Ok(()) => (),
+            Err(BoardOptionsError::EmptyMap { .. }) => assert!(width == 0 || height == 0),
+            Err(BoardOptionsError::TooManyBombs {
+                bomb_count,
+                tile_count,
+            }) => {
+                assert!(width > 0 && height > 0);
+                assert_eq!(tile_count, width * height);
+                assert!(usize::from(bomb_count) >= tile_count);
+            }
+            Err(BoardOptionsError::InvalidTileSize(size)) => {
+                assert!(size.min_tile_size().is_none());
+            }
+            Err(BoardOptionsError::InvalidPadding(padding)) => assert!(padding < 0.0),
+            Err(BoardOptionsError::PaddingTooLarge {
+                tile_padding,
+                tile_size,
+            }) => assert!(tile_padding >= tile_size),
+            Err(e @ BoardOptionsError::MapTooLarge { .. }) => panic!("unexpected {}", e),
+        }
+    }
+}
+
+#[test]
+fn oversized_maps_are_refused() {
+    let options = BoardOptions {
+        map_size: (usize::from(u16::MAX) + 1, 1),
+        ..BoardOptions::default()
+    };
+    assert!(matches!(
+        options.validate(),
+        Err(BoardOptionsError::MapTooLarge { .. })
+    ));
+}
+
+#[test]
+fn presets_are_valid() {
+    let mut rng = StdRng::seed_from_u64(38);
+    let customs = (0..CASES).map(|_| {
+        BoardPreset::custom(
+            rng.gen_range(0..200),
+            rng.gen_range(0..200),
+            rng.gen_range(0..=u16::MAX),
+        )
+    });
+    for preset in [
+        BoardPreset::Beginner,
+        BoardPreset::Intermediate,
+        BoardPreset::Expert,
+    ]
+    .into_iter()
+    .chain(customs)
+    {
+        let mut options = BoardOptions::default();
+        preset.apply(&mut options);
+        assert!(preset.is_valid(), "{}", preset);
+        assert_eq!(options.validate(), Ok(()), "{}", preset);
+    }
+}
+
+/// Headless app running a `BoardPlugin` with the given options
+fn board_app(options: BoardOptions) -> App {
+    let mut app = common::board_app(options);
+    common::add_window(&mut app);
+    app
+}
+
+#[test]
+fn create_board_refuses_invalid_options() {
+    let mut app = board_app(BoardOptions {
+        map_size: (3, 3),
+        bomb_count: 9,
+        ..BoardOptions::default()
+    });
+    app.update();
+    app.update();
+
+    assert!(app.world.get_resource::<Board>().is_none());
+    let errors: Vec<_> = app
+        .world
+        .get_resource_mut::<Events<BoardOptionsErrorEvent>>()
+        .unwrap()
+        .drain()
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].0,
+        BoardOptionsError::TooManyBombs {
+            bomb_count: 9,
+            tile_count: 9
+        }
+    );
+}
+
+#[test]
+fn create_board_accepts_valid_options() {
+    let mut app = board_app(BoardOptions {
+        map_size: (3, 3),
+        bomb_count: 8,
+        ..BoardOptions::default()
+    });
+    app.update();
+    app.update();
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.tile_map.bomb_count(), 8);
+    assert!(app
+        .world
+        .get_resource::<Events<BoardOptionsErrorEvent>>()
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,287 @@
+//! Sends tile events through a `BoardPlugin` under `MinimalPlugins` and checks the resulting
+//! events and entities
+
+mod common;
+
+use bevy::ecs::event::{Events, ManualEventReader};
+use bevy::prelude::*;
+use board_plugin::{
+    components::Coordinates,
+    events::{
+        BoardCompletedEvent, BombExplosionEvent, RedoEvent, TileChordEvent, TileMarkEvent,
+        TileTriggerEvent, TilesUncoveredEvent, UndoEvent,
+    },
+    resources::{Board, BoardOptions, BoardSave, BoardState, BoardStatus, Mark, UndoPolicy},
+};
+use common::send;
+
+/// 4x3 board with a bomb in the bottom left corner
+const CORNER_BOMB: &str = "\
+....
+....
+*...
+";
+
+/// Events sent by the board
+#[derive(Debug, Default)]
+struct Sent {
+    uncovered: Vec<Coordinates>,
+    explosions: usize,
+    completions: usize,
+}
+
+/// Headless board app running the given options and text board
+fn board_app(options: BoardOptions, board: Option<&str>) -> App {
+    let mut app = common::board_app(options);
+    if let Some(text) = board {
+        app.insert_resource(BoardSave::from_text(text).unwrap());
+    }
+    app.update();
+    app
+}
+
+/// Events of a type sent during the next frames
+fn read<E: Clone + Send + Sync + 'static>(app: &App, reader: &mut ManualEventReader<E>) -> Vec<E> {
+    let events = app.world.get_resource::<Events<E>>().unwrap();
+    reader.iter(events).cloned().collect()
+}
+
+/// Runs a few frames, collecting the board events without consuming them
+fn run(app: &mut App) -> Sent {
+    let mut uncovered_reader = ManualEventReader::<TilesUncoveredEvent>::default();
+    let mut explosion_reader = ManualEventReader::<BombExplosionEvent>::default();
+    let mut completed_reader = ManualEventReader::<BoardCompletedEvent>::default();
+    let mut sent = Sent::default();
+    for _ in 0..3 {
+        app.update();
+        sent.uncovered.extend(
+            read(app, &mut uncovered_reader)
+                .into_iter()
+                .flat_map(|e| e.0),
+        );
+        sent.explosions += read(app, &mut explosion_reader).len();
+        sent.completions += read(app, &mut completed_reader).len();
+    }
+    sent.uncovered.sort_unstable();
+    sent
+}
+
+fn named(app: &mut App, name: &str) -> usize {
+    app.world
+        .query::<&Name>()
+        .iter(&app.world)
+        .filter(|n| n.as_str() == name)
+        .count()
+}
+
+fn status(app: &App) -> BoardState {
+    app.world.get_resource::<BoardStatus>().unwrap().state
+}
+
+#[test]
+fn triggering_an_empty_tile_wins_the_corner_board() {
+    let mut app = board_app(BoardOptions::default(), Some(CORNER_BOMB));
+    run(&mut app);
+    assert_eq!(named(&mut app, "Tile Cover"), 12);
+
+    send(&mut app, TileTriggerEvent(Coordinates::new(3, 2)));
+    let sent = run(&mut app);
+    assert_eq!(sent.uncovered.len(), 11);
+    assert!(!sent.uncovered.contains(&Coordinates::new(0, 0)));
+    assert_eq!(sent.explosions, 0);
+    assert_eq!(sent.completions, 1);
+    assert_eq!(status(&app), BoardState::Won);
+    // Only the bomb keeps its cover
+    assert_eq!(named(&mut app, "Tile Cover"), 1);
+}
+
+#[test]
+fn triggering_a_bomb_loses_the_game() {
+    let mut app = board_app(BoardOptions::default(), Some(CORNER_BOMB));
+    run(&mut app);
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    let sent = run(&mut app);
+    assert_eq!(sent.uncovered, vec![Coordinates::new(0, 0)]);
+    assert_eq!(sent.explosions, 1);
+    assert_eq!(sent.completions, 0);
+    assert_eq!(status(&app), BoardState::Lost);
+    assert_eq!(named(&mut app, "Tile Cover"), 11);
+}
+
+#[test]
+fn flagged_tiles_are_not_triggered() {
+    let mut app = board_app(BoardOptions::default(), Some(CORNER_BOMB));
+    run(&mut app);
+    send(&mut app, TileMarkEvent(Coordinates::new(0, 0)));
+    run(&mut app);
+    assert_eq!(named(&mut app, "Flag"), 1);
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(
+        board.marked_tiles.get(&Coordinates::new(0, 0)),
+        Some(&Mark::Flag)
+    );
+
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    let sent = run(&mut app);
+    assert!(sent.uncovered.is_empty());
+    assert_eq!(sent.explosions, 0);
+    assert_eq!(status(&app), BoardState::Playing);
+
+    // Unmarking removes the flag
+    send(&mut app, TileMarkEvent(Coordinates::new(0, 0)));
+    run(&mut app);
+    assert_eq!(named(&mut app, "Flag"), 0);
+}
+
+#[test]
+fn chording_a_satisfied_counter_uncovers_its_neighbours() {
+    let mut app = board_app(
+        BoardOptions::default(),
+        Some(
+            "\
+.....
+.*...
+.....
+",
+        ),
+    );
+    run(&mut app);
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    send(&mut app, TileMarkEvent(Coordinates::new(1, 1)));
+    let sent = run(&mut app);
+    assert_eq!(sent.uncovered, vec![Coordinates::new(0, 0)]);
+
+    send(&mut app, TileChordEvent(Coordinates::new(0, 0)));
+    let sent = run(&mut app);
+    assert_eq!(
+        sent.uncovered,
+        vec![Coordinates::new(0, 1), Coordinates::new(1, 0)]
+    );
+    assert_eq!(status(&app), BoardState::Playing);
+}
+
+#[test]
+fn safe_start_uncovers_an_opening() {
+    let options = BoardOptions {
+        map_size: (16, 16),
+        bomb_count: 40,
+        seed: Some(11),
+        safe_start: true,
+        ..BoardOptions::default()
+    };
+    let mut app = board_app(options.clone(), None);
+    let sent = run(&mut app);
+    let board = app.world.get_resource::<Board>().unwrap();
+    let start = board.tile_map.first_empty_tile().unwrap();
+    assert!(sent.uncovered.contains(&start));
+    assert!(sent.uncovered.len() > 1);
+    assert!(sent
+        .uncovered
+        .iter()
+        .all(|c| !board.tile_map.is_bomb_at(*c)));
+    assert_eq!(sent.explosions, 0);
+    assert_eq!(status(&app), BoardState::Playing);
+
+    let mut app = board_app(
+        BoardOptions {
+            safe_start: false,
+            ..options
+        },
+        None,
+    );
+    assert!(run(&mut app).uncovered.is_empty());
+}
+
+#[test]
+fn undo_covers_the_losing_tile_and_restores_marks() {
+    let options = BoardOptions {
+        undo: Some(UndoPolicy::Unlimited),
+        ..BoardOptions::default()
+    };
+    let mut app = board_app(options, Some(CORNER_BOMB));
+    run(&mut app);
+    send(&mut app, TileMarkEvent(Coordinates::new(3, 2)));
+    run(&mut app);
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Lost);
+
+    send(&mut app, UndoEvent);
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Playing);
+    assert_eq!(named(&mut app, "Tile Cover"), 12);
+    assert_eq!(named(&mut app, "Flag"), 1);
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.covered_tiles.len(), 12);
+    assert!(board.is_flagged(&Coordinates::new(3, 2)));
+
+    send(&mut app, UndoEvent);
+    run(&mut app);
+    assert_eq!(named(&mut app, "Flag"), 0);
+
+    send(&mut app, RedoEvent);
+    send(&mut app, RedoEvent);
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Lost);
+    assert_eq!(named(&mut app, "Tile Cover"), 11);
+    assert_eq!(named(&mut app, "Flag"), 1);
+}
+
+#[test]
+fn moves_cannot_be_undone_without_a_policy() {
+    let mut app = board_app(BoardOptions::default(), Some(CORNER_BOMB));
+    run(&mut app);
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    run(&mut app);
+    send(&mut app, UndoEvent);
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Lost);
+    assert_eq!(named(&mut app, "Tile Cover"), 11);
+}
+
+#[test]
+fn tile_events_are_ignored_once_the_game_is_over() {
+    let options = BoardOptions {
+        undo: Some(UndoPolicy::AfterDeath { penalty: 10.0 }),
+        ..BoardOptions::default()
+    };
+    let mut app = board_app(options, Some(CORNER_BOMB));
+    run(&mut app);
+    send(&mut app, TileMarkEvent(Coordinates::new(3, 2)));
+    run(&mut app);
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Lost);
+    let board = app.world.get_resource::<Board>().unwrap();
+    let (covered, marked) = (board.covered_tiles.clone(), board.marked_tiles.clone());
+
+    send(&mut app, TileTriggerEvent(Coordinates::new(2, 0)));
+    send(&mut app, TileMarkEvent(Coordinates::new(1, 1)));
+    send(&mut app, TileChordEvent(Coordinates::new(0, 0)));
+    let sent = run(&mut app);
+    assert!(sent.uncovered.is_empty());
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.covered_tiles, covered);
+    assert_eq!(board.marked_tiles, marked);
+
+    // Undo still takes back the losing move, the ignored events staying dropped
+    send(&mut app, UndoEvent);
+    let sent = run(&mut app);
+    assert!(sent.uncovered.is_empty());
+    assert_eq!(status(&app), BoardState::Playing);
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.covered_tiles.len(), 12);
+    assert_eq!(board.marked_tiles.len(), 1);
+
+    // Won games are left alone as well
+    send(&mut app, TileTriggerEvent(Coordinates::new(2, 0)));
+    run(&mut app);
+    assert_eq!(status(&app), BoardState::Won);
+    send(&mut app, TileMarkEvent(Coordinates::new(0, 0)));
+    send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    let sent = run(&mut app);
+    assert_eq!(sent.explosions, 0);
+    assert_eq!(status(&app), BoardState::Won);
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert!(!board.marked_tiles.contains_key(&Coordinates::new(0, 0)));
+}
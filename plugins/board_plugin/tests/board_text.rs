@@ -0,0 +1,120 @@
+//! Reads and writes boards as text grids
+
+use board_plugin::{
+    components::Coordinates,
+    resources::{BoardSave, BoardTextError, BoardTextErrorKind, Mark, Tile},
+};
+
+const BOARD: &str = "\
+# Every tile kind
+X1.f
+11?!
+..2*
+F1..
+";
+
+fn text_error(text: &str) -> BoardTextError {
+    BoardSave::from_text(text).unwrap_err()
+}
+
+#[test]
+fn text_boards_are_read_from_the_top_row() {
+    let save = BoardSave::from_text(BOARD).unwrap();
+    assert_eq!(save.map_size, (4, 4));
+    assert_eq!(
+        save.bombs,
+        vec![
+            Coordinates::new(0, 0),
+            Coordinates::new(0, 3),
+            Coordinates::new(3, 1),
+            Coordinates::new(3, 2),
+        ]
+    );
+    assert!(save.uncovered.contains(&Coordinates::new(0, 3)));
+    assert!(save.uncovered.contains(&Coordinates::new(2, 1)));
+    assert_eq!(save.uncovered.len(), 6);
+    assert_eq!(
+        save.marks,
+        vec![
+            (Coordinates::new(0, 0), Mark::Flag),
+            (Coordinates::new(2, 2), Mark::Question),
+            (Coordinates::new(3, 2), Mark::Question),
+            (Coordinates::new(3, 3), Mark::Flag),
+        ]
+    );
+
+    let tile_map = save.tile_map();
+    assert_eq!(tile_map.bomb_count(), 4);
+    assert_eq!(tile_map.map()[(1, 0)], Tile::BombNeighbor(1));
+    assert_eq!(tile_map.map()[(2, 2)], Tile::BombNeighbor(2));
+}
+
+#[test]
+fn text_boards_round_trip() {
+    let save = BoardSave::from_text(BOARD).unwrap();
+    let text = save.to_text();
+    assert_eq!(text, BOARD.trim_start_matches("# Every tile kind\n"));
+    assert_eq!(BoardSave::from_text(&text).unwrap(), save);
+}
+
+#[test]
+fn blank_lines_comments_and_trailing_spaces_are_ignored() {
+    let save = BoardSave::from_text("\n# Top\n*.  \n\n# Bottom\n..\n").unwrap();
+    assert_eq!(save.map_size, (2, 2));
+    assert_eq!(save.bombs, vec![Coordinates::new(0, 1)]);
+}
+
+#[test]
+fn errors_point_at_the_line_and_column() {
+    assert_eq!(
+        text_error("# Comment\n...\n.x.\n"),
+        BoardTextError {
+            line: 3,
+            column: 2,
+            kind: BoardTextErrorKind::UnexpectedChar('x'),
+        }
+    );
+    assert_eq!(
+        text_error("...\n..\n"),
+        BoardTextError {
+            line: 2,
+            column: 3,
+            kind: BoardTextErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            },
+        }
+    );
+    assert_eq!(
+        text_error("*..\n..2\n"),
+        BoardTextError {
+            line: 2,
+            column: 3,
+            kind: BoardTextErrorKind::WrongCounter {
+                expected: 0,
+                found: 2
+            },
+        }
+    );
+    assert_eq!(text_error("# Nothing\n\n").kind, BoardTextErrorKind::Empty);
+    assert_eq!(
+        text_error("...\n.x.\n").to_string(),
+        "line 2, column 2: unexpected 'x', expected a tile out of . * F f ? ! X or 0 to 8"
+    );
+}
+
+#[test]
+fn bombs_past_the_bomb_count_limit_are_refused() {
+    // 255 full rows hold 65280 bombs, the 65536th is the last one of the next row
+    let row = "*".repeat(256);
+    let text = vec![row; 257].join("\n");
+    assert_eq!(
+        text_error(&text),
+        BoardTextError {
+            line: 256,
+            column: 256,
+            kind: BoardTextErrorKind::TooManyBombs,
+        }
+    );
+    assert!(BoardSave::from_text(&vec!["*".repeat(255); 257].join("\n")).is_ok());
+}
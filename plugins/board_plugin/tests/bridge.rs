@@ -0,0 +1,172 @@
+//! Drives games through the `GameBridge`, as a host page would
+
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use board_plugin::{
+    bridge::BridgePlugin,
+    components::Coordinates,
+    events::{TileMarkEvent, TileTriggerEvent},
+    resources::{
+        Board, BoardOptions, BoardOptionsError, BoardPreset, BoardState, BridgeEvent, GameBridge,
+        Mark, Tile,
+    },
+};
+use common::TestState;
+
+mod common;
+
+/// Headless app waiting for the host to start a game
+fn bridge_app() -> (App, GameBridge) {
+    let bridge = GameBridge::default();
+    let mut app = common::board_app_from(
+        TestState::Idle,
+        BoardOptions {
+            question_marks: true,
+            ..BoardOptions::default()
+        },
+    );
+    app.insert_resource(bridge.clone())
+        .add_plugin(BridgePlugin {
+            running_state: TestState::InGame,
+        });
+    common::add_window(&mut app);
+    app.update();
+    (app, bridge)
+}
+
+fn run(app: &mut App) {
+    for _ in 0..4 {
+        app.update();
+    }
+}
+
+fn bombs(app: &App) -> Vec<(usize, usize)> {
+    app.world
+        .get_resource::<Board>()
+        .unwrap()
+        .tile_map
+        .map()
+        .indexed_iter()
+        .filter(|(_, tile)| **tile == Tile::Bomb)
+        .map(|(coords, _)| coords)
+        .collect()
+}
+
+#[test]
+fn seeded_games_are_reproducible() {
+    let (mut app, bridge) = bridge_app();
+    assert!(bridge.snapshot().is_none());
+
+    bridge.start(BoardPreset::Beginner, Some(7)).unwrap();
+    run(&mut app);
+    let snapshot = bridge.snapshot().unwrap();
+    assert_eq!(snapshot.state, BoardState::Playing);
+    assert_eq!(snapshot.map_size, (9, 9));
+    assert_eq!(snapshot.mines_left, 10);
+    let first = bombs(&app);
+
+    // Restarting the running game with the same seed
+    bridge.start(BoardPreset::Beginner, Some(7)).unwrap();
+    run(&mut app);
+    assert_eq!(bombs(&app), first);
+
+    bridge.start(BoardPreset::Beginner, Some(8)).unwrap();
+    run(&mut app);
+    assert_ne!(bombs(&app), first);
+}
+
+#[test]
+fn invalid_games_are_refused() {
+    let (_, bridge) = bridge_app();
+    let preset = BoardPreset::Custom {
+        width: 3,
+        height: 3,
+        bomb_count: 9,
+    };
+    assert!(matches!(
+        bridge.start(preset, None),
+        Err(BoardOptionsError::TooManyBombs { .. })
+    ));
+}
+
+#[test]
+fn reveals_and_losses_are_notified() {
+    let (mut app, bridge) = bridge_app();
+    bridge.start(BoardPreset::Intermediate, Some(3)).unwrap();
+    run(&mut app);
+    let _ = bridge.drain_events();
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    let safe = board.tile_map.first_empty_tile().unwrap();
+    let (bomb_x, bomb_y) = bombs(&app)[0];
+    let bomb = Coordinates::new(bomb_x as u16, bomb_y as u16);
+    common::send(&mut app, TileTriggerEvent(safe));
+    run(&mut app);
+    match bridge.drain_events().as_slice() {
+        [BridgeEvent::Revealed(tiles)] => assert!(tiles.contains(&safe)),
+        events => panic!("Unexpected events {:?}", events),
+    }
+
+    common::send(&mut app, TileTriggerEvent(bomb));
+    run(&mut app);
+    assert_eq!(
+        bridge.drain_events(),
+        vec![BridgeEvent::Revealed(vec![bomb]), BridgeEvent::Lost]
+    );
+    assert_eq!(bridge.snapshot().unwrap().state, BoardState::Lost);
+}
+
+#[test]
+fn saves_restore_the_exact_board_state() {
+    let (mut app, bridge) = bridge_app();
+    bridge.start(BoardPreset::Expert, Some(11)).unwrap();
+    run(&mut app);
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    let safe = board.tile_map.first_empty_tile().unwrap();
+    let (bomb_x, bomb_y) = bombs(&app)[0];
+    let flag = Coordinates::new(bomb_x as u16, bomb_y as u16);
+    common::send(&mut app, TileTriggerEvent(safe));
+    run(&mut app);
+
+    // Picked once the flood fill is over, so the mark stays
+    let board = app.world.get_resource::<Board>().unwrap();
+    let question = *board
+        .covered_tiles
+        .keys()
+        .find(|&&c| c != flag && !board.tile_map.is_bomb_at(c))
+        .unwrap();
+    let mut marks = app
+        .world
+        .get_resource_mut::<Events<TileMarkEvent>>()
+        .unwrap();
+    marks.send(TileMarkEvent(flag));
+    marks.send(TileMarkEvent(question));
+    marks.send(TileMarkEvent(question));
+    run(&mut app);
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.marked_tiles.get(&question), Some(&Mark::Question));
+    let covered = board.covered_tiles.len();
+    let marked = board.marked_tiles.clone();
+    let save = bridge.save().unwrap();
+
+    // A new game, then back to the saved one
+    bridge.start(BoardPreset::Beginner, None).unwrap();
+    run(&mut app);
+    assert_eq!(bridge.snapshot().unwrap().map_size, (9, 9));
+    bridge.restore(save.clone()).unwrap();
+    run(&mut app);
+
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.covered_tiles.len(), covered);
+    assert_eq!(board.marked_tiles, marked);
+    let snapshot = bridge.snapshot().unwrap();
+    assert_eq!(snapshot.state, BoardState::Playing);
+    assert_eq!(snapshot.map_size, (30, 16));
+    assert_eq!(snapshot.mines_left, 98);
+    let restored = bridge.save().unwrap();
+    assert_eq!(restored.bombs, save.bombs);
+    assert_eq!(restored.uncovered, save.uncovered);
+    assert_eq!(restored.marks, save.marks);
+}
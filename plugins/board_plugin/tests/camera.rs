@@ -0,0 +1,70 @@
+//! Fits the camera to the board of a headless app on the rebindable fit key
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ElementState;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use board_plugin::{resources::KeyBindings, systems::camera::fit_to_window};
+
+mod common;
+
+/// Headless app fitting its camera to the `common::insert_board` board
+fn camera_app(bindings: Option<KeyBindings>) -> App {
+    let mut app = common::headless_app();
+    if let Some(bindings) = bindings {
+        app.insert_resource(bindings);
+    }
+    app.add_system(fit_to_window);
+    common::add_window(&mut app);
+    common::spawn_camera(&mut app);
+    common::insert_board(&mut app);
+    app.update();
+    app
+}
+
+fn camera_x(app: &mut App) -> f32 {
+    let mut cameras = app.world.query_filtered::<&mut Transform, With<Camera2d>>();
+    cameras.iter(&app.world).next().unwrap().translation.x
+}
+
+/// Moves the camera away from the board, then presses and releases a key
+fn pan_and_press(app: &mut App, key_code: KeyCode) {
+    let mut cameras = app.world.query_filtered::<&mut Transform, With<Camera2d>>();
+    cameras
+        .iter_mut(&mut app.world)
+        .next()
+        .unwrap()
+        .translation
+        .x = 500.0;
+    for state in [ElementState::Pressed, ElementState::Released] {
+        common::send(
+            app,
+            KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state,
+            },
+        );
+        app.update();
+    }
+}
+
+#[test]
+fn fit_key_defaults_to_f() {
+    let mut app = camera_app(None);
+    assert_eq!(camera_x(&mut app), 0.0);
+    pan_and_press(&mut app, KeyCode::F);
+    assert_eq!(camera_x(&mut app), 0.0);
+}
+
+#[test]
+fn fit_key_can_be_rebound() {
+    let mut app = camera_app(Some(KeyBindings {
+        fit: vec![KeyCode::Home],
+        ..KeyBindings::default()
+    }));
+    pan_and_press(&mut app, KeyCode::F);
+    assert_eq!(camera_x(&mut app), 500.0);
+    pan_and_press(&mut app, KeyCode::Home);
+    assert_eq!(camera_x(&mut app), 0.0);
+}
@@ -0,0 +1,111 @@
+//! Checks mesh rendered boards spawn an entity per chunk and redraw chunks from the board state
+
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+use bevy::sprite::Mesh2dHandle;
+use board_plugin::{
+    components::{ChunkDirty, Coordinates, TileChunk},
+    events::{TileMarkEvent, TileTriggerEvent},
+    resources::{AtlasCell, Board, BoardOptions, TileAtlas, TileRendering},
+};
+
+mod common;
+
+/// Headless app running a mesh rendered `BoardPlugin` on a 130x70 board without bombs
+fn board_app() -> App {
+    let mut app = common::board_app(BoardOptions {
+        map_size: (130, 70),
+        bomb_count: 0,
+        rendering: TileRendering::Mesh,
+        ..BoardOptions::default()
+    });
+    app.add_plugin(AssetPlugin)
+        .add_asset::<Mesh>()
+        .add_asset::<Image>()
+        .add_asset::<ColorMaterial>();
+    common::add_window(&mut app);
+    for _ in 0..3 {
+        app.update();
+    }
+    app
+}
+
+/// Texture coordinates of the first tile of the chunk at `origin`
+fn first_tile_uv(app: &mut App, origin: Coordinates) -> [f32; 2] {
+    let handle = app
+        .world
+        .query::<(&TileChunk, &Mesh2dHandle)>()
+        .iter(&app.world)
+        .find(|(chunk, _)| chunk.origin == origin)
+        .map(|(_, mesh)| mesh.0.clone())
+        .unwrap();
+    let meshes = app.world.get_resource::<Assets<Mesh>>().unwrap();
+    match meshes
+        .get(&handle)
+        .unwrap()
+        .attribute(Mesh::ATTRIBUTE_UV_0)
+        .unwrap()
+    {
+        bevy::render::mesh::VertexAttributeValues::Float32x2(uvs) => uvs[3],
+        _ => panic!("Unexpected texture coordinates format"),
+    }
+}
+
+#[test]
+fn chunks_replace_tile_entities() {
+    let mut app = board_app();
+
+    let chunks: Vec<TileChunk> = app
+        .world
+        .query::<&TileChunk>()
+        .iter(&app.world)
+        .copied()
+        .collect();
+    assert_eq!(chunks.len(), 3 * 2);
+    let tiles: usize = chunks
+        .iter()
+        .map(|c| usize::from(c.width) * usize::from(c.height))
+        .sum();
+    assert_eq!(tiles, 130 * 70);
+    assert_eq!(
+        app.world.query::<&Coordinates>().iter(&app.world).count(),
+        0
+    );
+    assert_eq!(app.world.query::<&ChunkDirty>().iter(&app.world).count(), 0);
+    assert!(app.world.get_resource::<TileAtlas>().is_some());
+    let covered = AtlasCell::Covered.uv_rect().0;
+    assert_eq!(
+        first_tile_uv(&mut app, Coordinates::new(64, 0)),
+        [covered.x, covered.y]
+    );
+}
+
+#[test]
+fn marks_and_reveals_update_chunk_uvs() {
+    let mut app = board_app();
+
+    common::send(&mut app, TileMarkEvent(Coordinates::new(64, 0)));
+    for _ in 0..3 {
+        app.update();
+    }
+    let flag = AtlasCell::Flag.uv_rect().0;
+    assert_eq!(
+        first_tile_uv(&mut app, Coordinates::new(64, 0)),
+        [flag.x, flag.y]
+    );
+
+    common::send(&mut app, TileTriggerEvent(Coordinates::new(0, 0)));
+    for _ in 0..3 {
+        app.update();
+    }
+    assert!(app
+        .world
+        .get_resource::<Board>()
+        .unwrap()
+        .covered_tiles
+        .is_empty());
+    let empty = AtlasCell::Empty.uv_rect().0;
+    for origin in [Coordinates::new(0, 0), Coordinates::new(64, 0)] {
+        assert_eq!(first_tile_uv(&mut app, origin), [empty.x, empty.y]);
+    }
+}
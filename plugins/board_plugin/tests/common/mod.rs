@@ -0,0 +1,140 @@
+//! Headless apps and helpers shared by the integration tests, each test file using only some of
+//! them
+
+#![allow(dead_code)]
+
+use bevy::ecs::event::Events;
+use bevy::input::InputPlugin;
+use bevy::math::const_vec2;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use bevy::utils::HashMap;
+use bevy::window::{WindowId, WindowPlugin};
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    resources::{AnimationOptions, Board, BoardAssets, BoardOptions, TileMap},
+    BoardPlugin,
+};
+use raw_window_handle::{RawWindowHandle, WebHandle};
+
+/// Size of the primary window added by `add_window`
+pub const WINDOW_SIZE: Vec2 = const_vec2!([800.0, 600.0]);
+/// Tile size of the board inserted by `insert_board`
+pub const TILE_SIZE: f32 = 50.0;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TestState {
+    Idle,
+    InGame,
+}
+
+/// App with `MinimalPlugins` and input, without a primary window
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin {
+            add_primary_window: false,
+            ..WindowPlugin::default()
+        })
+        .add_plugin(InputPlugin);
+    app
+}
+
+/// Headless app running a `BoardPlugin` with `options`, default assets and no animations. The
+/// board is created on the first frame, other resources can be inserted until then.
+pub fn board_app(options: BoardOptions) -> App {
+    board_app_from(TestState::InGame, options)
+}
+
+/// Same as `board_app`, the board only being created once the app goes from `state` to
+/// `TestState::InGame`
+pub fn board_app_from(state: TestState, options: BoardOptions) -> App {
+    let mut app = headless_app();
+    app.insert_resource(options)
+        .insert_resource(AnimationOptions::disabled())
+        .insert_resource(BoardAssets::default())
+        .add_state(state)
+        .add_plugin(BoardPlugin {
+            running_state: TestState::InGame,
+        });
+    app
+}
+
+/// Adds a `WINDOW_SIZE` primary window without a backend
+pub fn add_window(app: &mut App) {
+    app.world
+        .get_resource_mut::<Windows>()
+        .unwrap()
+        .add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            WINDOW_SIZE.x as u32,
+            WINDOW_SIZE.y as u32,
+            1.0,
+            None,
+            RawWindowHandle::Web(WebHandle::empty()),
+        ));
+}
+
+/// Spawns a 2D camera on the origin, showing the window at a one to one scale
+pub fn spawn_camera(app: &mut App) {
+    let half = WINDOW_SIZE * 0.5;
+    app.world
+        .spawn()
+        .insert(Camera {
+            projection_matrix: Mat4::orthographic_rh(-half.x, half.x, -half.y, half.y, 0.0, 1000.0),
+            ..Camera::default()
+        })
+        .insert(Camera2d)
+        .insert(OrthographicProjection::default())
+        .insert(Transform::default())
+        .insert(GlobalTransform::default());
+}
+
+/// Inserts a 4x4 board without bombs centred on the origin, every tile covered except (0, 0)
+pub fn insert_board(app: &mut App) {
+    let board_entity = app.world.spawn().id();
+    let mut covered_tiles = HashMap::default();
+    let mut tile_entities = HashMap::default();
+    for y in 0..4 {
+        for x in 0..4 {
+            if (x, y) != (0, 0) {
+                covered_tiles.insert(Coordinates::new(x, y), app.world.spawn().id());
+            }
+            tile_entities.insert(Coordinates::new(x, y), app.world.spawn().id());
+        }
+    }
+    let board_size = Vec2::splat(4.0 * TILE_SIZE);
+    let mut board = Board::new(
+        board_entity,
+        TileMap::empty((4, 4)),
+        covered_tiles,
+        Bounds::new(-board_size * 0.5, board_size),
+        TILE_SIZE,
+    );
+    board.tile_entities = tile_entities;
+    app.insert_resource(board);
+}
+
+/// Window position of the centre of a tile of the `insert_board` board, seen by `spawn_camera`
+pub fn tile_position(x: u16, y: u16) -> Vec2 {
+    let world = Vec2::new(f32::from(x) + 0.5, f32::from(y) + 0.5) * TILE_SIZE - 2.0 * TILE_SIZE;
+    world + WINDOW_SIZE * 0.5
+}
+
+pub fn send<E: Send + Sync + 'static>(app: &mut App, event: E) {
+    app.world
+        .get_resource_mut::<Events<E>>()
+        .unwrap()
+        .send(event);
+}
+
+/// Events sent since the last drain, events only live for two updates
+pub fn drain<E: Send + Sync + 'static>(app: &mut App) -> Vec<E> {
+    app.world
+        .get_resource_mut::<Events<E>>()
+        .unwrap()
+        .drain()
+        .collect()
+}
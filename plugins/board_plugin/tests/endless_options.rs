@@ -0,0 +1,76 @@
+//! Validation of the endless board options
+
+use board_plugin::resources::{BoardOptionsError, EndlessOptions, TileSize};
+
+#[test]
+fn default_options_are_valid() {
+    assert_eq!(EndlessOptions::default().validate(), Ok(()));
+}
+
+#[test]
+fn chunks_need_tiles_and_a_safe_tile() {
+    let options = EndlessOptions {
+        chunk_size: 0,
+        bombs_per_chunk: 0,
+        ..EndlessOptions::default()
+    };
+    assert_eq!(
+        options.validate(),
+        Err(BoardOptionsError::EmptyMap {
+            width: 0,
+            height: 0
+        })
+    );
+
+    let options = EndlessOptions {
+        chunk_size: 4,
+        bombs_per_chunk: 16,
+        ..EndlessOptions::default()
+    };
+    assert_eq!(
+        options.validate(),
+        Err(BoardOptionsError::TooManyBombs {
+            bomb_count: 16,
+            tile_count: 16
+        })
+    );
+    let options = EndlessOptions {
+        bombs_per_chunk: 15,
+        ..options
+    };
+    assert_eq!(options.validate(), Ok(()));
+}
+
+#[test]
+fn tile_size_and_padding_are_checked() {
+    let options = EndlessOptions {
+        tile_size: 0.0,
+        ..EndlessOptions::default()
+    };
+    assert_eq!(
+        options.validate(),
+        Err(BoardOptionsError::InvalidTileSize(TileSize::Fixed(0.0)))
+    );
+
+    let options = EndlessOptions {
+        tile_padding: -1.0,
+        ..EndlessOptions::default()
+    };
+    assert_eq!(
+        options.validate(),
+        Err(BoardOptionsError::InvalidPadding(-1.0))
+    );
+
+    let options = EndlessOptions {
+        tile_size: 8.0,
+        tile_padding: 8.0,
+        ..EndlessOptions::default()
+    };
+    assert_eq!(
+        options.validate(),
+        Err(BoardOptionsError::PaddingTooLarge {
+            tile_padding: 8.0,
+            tile_size: 8.0
+        })
+    );
+}
@@ -0,0 +1,109 @@
+//! Compares the single pass flood fill with the previous ring per frame propagation
+
+use bevy::prelude::{Entity, Vec2};
+use bevy::utils::{HashMap, HashSet};
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    resources::{Board, Tile, TileMap},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Reveals ring by ring, as `Uncover` used to be inserted on neighbours each frame
+fn ring_by_ring(tile_map: &TileMap, start: Coordinates) -> HashMap<Coordinates, u32> {
+    let mut revealed = HashMap::default();
+    let mut ring = HashSet::default();
+    ring.insert(start);
+    let mut depth = 0;
+    while !ring.is_empty() {
+        let mut next = HashSet::default();
+        for coords in ring {
+            if revealed.contains_key(&coords) {
+                continue;
+            }
+            revealed.insert(coords, depth);
+            if tile_map.map()[(coords.x as usize, coords.y as usize)] == Tile::Empty {
+                next.extend(
+                    tile_map
+                        .neighbours(coords)
+                        .filter(|c| !revealed.contains_key(c)),
+                );
+            }
+        }
+        ring = next;
+        depth += 1;
+    }
+    revealed
+}
+
+fn random_map(rng: &mut StdRng, size: (usize, usize), bomb_count: u16) -> TileMap {
+    let mut tile_map = TileMap::empty(size);
+    tile_map.set_bombs_with_rng(bomb_count, rng);
+    tile_map
+}
+
+#[test]
+fn flood_fill_matches_ring_propagation() {
+    let mut rng = StdRng::seed_from_u64(39);
+    for _ in 0..200 {
+        let size = (rng.gen_range(1..30), rng.gen_range(1..30));
+        let bomb_count = rng.gen_range(0..(size.0 * size.1) as u16);
+        let tile_map = random_map(&mut rng, size, bomb_count);
+        let start = Coordinates::new(
+            rng.gen_range(0..size.0) as u16,
+            rng.gen_range(0..size.1) as u16,
+        );
+
+        let revealed: HashMap<_, _> = tile_map.flood_fill([start], |_| true).into_iter().collect();
+        assert_eq!(revealed, ring_by_ring(&tile_map, start));
+        if !tile_map.is_bomb_at(start) {
+            assert!(revealed.keys().all(|c| !tile_map.is_bomb_at(*c)));
+        }
+    }
+}
+
+#[test]
+fn flood_fill_skips_uncovered_tiles_and_bad_starts() {
+    let tile_map = TileMap::empty((5, 5));
+    // A wall of uncovered tiles along x = 2 stops the fill
+    let revealed = tile_map.flood_fill([Coordinates::new(0, 0)], |c| c.x != 2);
+    assert_eq!(revealed.len(), 10);
+    assert!(revealed.iter().all(|(c, _)| c.x < 2));
+
+    assert!(tile_map
+        .flood_fill([Coordinates::new(5, 0)], |_| true)
+        .is_empty());
+    assert!(tile_map
+        .flood_fill([Coordinates::new(1, 1)], |_| false)
+        .is_empty());
+}
+
+#[test]
+fn board_uncovers_large_opening_at_once() {
+    let tile_map = TileMap::empty((500, 500));
+    let mut covered_tiles = HashMap::default();
+    for y in 0..500 {
+        for x in 0..500 {
+            covered_tiles.insert(
+                Coordinates::new(x, y),
+                Entity::from_raw(u32::from(x) + u32::from(y) * 500),
+            );
+        }
+    }
+    let mut board = Board::new(
+        Entity::from_raw(u32::MAX),
+        tile_map,
+        covered_tiles,
+        Bounds::new(Vec2::ZERO, Vec2::splat(500.0)),
+        1.0,
+    );
+
+    let uncovered = board.uncover([Coordinates::new(250, 250)]);
+    assert_eq!(uncovered.len(), 500 * 500);
+    assert!(board.covered_tiles.is_empty());
+    assert!(board.is_completed());
+    let max_depth = uncovered.iter().map(|(_, _, depth)| *depth).max();
+    assert_eq!(max_depth, Some(250));
+    // Uncovering again is a no-op
+    assert!(board.uncover([Coordinates::new(0, 0)]).is_empty());
+}
@@ -0,0 +1,203 @@
+//! Parses game settings as given by the page URL query or the command line
+
+use board_plugin::resources::{
+    BoardOptions, BoardOptionsError, BoardPreset, GameConfig, GameConfigError, GameMode, UndoPolicy,
+};
+
+#[test]
+fn query_sets_the_board_seed_and_mode() {
+    let (config, errors) = GameConfig::from_query("?w=30&h=16&mines=99&seed=123&mode=endless");
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(
+        config.preset(),
+        Some(BoardPreset::Custom {
+            width: 30,
+            height: 16,
+            bomb_count: 99
+        })
+    );
+    assert_eq!(config.seed, Some(123));
+    assert_eq!(config.mode, Some(GameMode::Endless));
+
+    let mut options = BoardOptions::default();
+    config.apply(&mut options);
+    assert_eq!(options.map_size, (30, 16));
+    assert_eq!(options.bomb_count, 99);
+    assert_eq!(options.seed, Some(123));
+}
+
+#[test]
+fn empty_query_keeps_the_defaults() {
+    for query in ["", "?", "?&"] {
+        let (config, errors) = GameConfig::from_query(query);
+        assert!(errors.is_empty());
+        assert_eq!(config, GameConfig::default());
+        assert_eq!(config.preset(), None);
+    }
+}
+
+#[test]
+fn partial_boards_complete_the_preset() {
+    let (config, errors) = GameConfig::from_query("preset=expert&mines=50");
+    assert!(errors.is_empty());
+    assert_eq!(
+        config.preset(),
+        Some(BoardPreset::Custom {
+            width: 30,
+            height: 16,
+            bomb_count: 50
+        })
+    );
+    let (config, _) = GameConfig::from_query("preset=Beginner");
+    assert_eq!(config.preset(), Some(BoardPreset::Beginner));
+}
+
+#[test]
+fn invalid_settings_are_skipped() {
+    let (config, errors) = GameConfig::from_query("w=thirty&h=16&mode=hard&colour=red&seed");
+    assert_eq!(config.width, None);
+    assert_eq!(config.height, Some(16));
+    assert_eq!(config.mode, None);
+    assert_eq!(config.seed, None);
+    assert!(matches!(
+        errors.as_slice(),
+        [
+            GameConfigError::InvalidValue { .. },
+            GameConfigError::InvalidValue { .. },
+            GameConfigError::UnknownSetting(_),
+            GameConfigError::MissingValue(_),
+        ]
+    ));
+    assert_eq!(
+        errors[1].to_string(),
+        "invalid value \"hard\" for \"mode\", expected classic or endless"
+    );
+}
+
+#[test]
+fn no_guess_boards_fall_back_to_classic() {
+    let (config, errors) = GameConfig::from_query("?w=30&h=16&mines=99&seed=123&mode=noguess");
+    assert_eq!(config.mode, Some(GameMode::Classic));
+    assert_eq!(config.seed, Some(123));
+    assert!(config.preset().is_some());
+    assert!(matches!(
+        errors.as_slice(),
+        [GameConfigError::Unsupported { .. }]
+    ));
+    assert_eq!(
+        errors[0].to_string(),
+        "\"noguess\" for \"mode\" is not supported yet, using classic"
+    );
+}
+
+#[test]
+fn undo_policies_are_applied_to_the_board() {
+    let (config, errors) = GameConfig::from_query("undo=flags");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let mut options = BoardOptions::default();
+    config.apply(&mut options);
+    assert_eq!(options.undo, Some(UndoPolicy::FlagsOnly));
+
+    let (config, _) = GameConfig::from_query("undo=Death");
+    assert_eq!(
+        config.undo,
+        Some(UndoPolicy::AfterDeath {
+            penalty: UndoPolicy::DEATH_PENALTY
+        })
+    );
+
+    // Policies set elsewhere are kept without the setting
+    let mut options = BoardOptions {
+        undo: Some(UndoPolicy::Unlimited),
+        ..BoardOptions::default()
+    };
+    let (config, errors) = GameConfig::from_query("undo=always");
+    assert_eq!(
+        errors,
+        vec![GameConfigError::InvalidValue {
+            key: "undo".to_string(),
+            value: "always".to_string(),
+            expected: "unlimited, flags or death",
+        }]
+    );
+    config.apply(&mut options);
+    assert_eq!(options.undo, Some(UndoPolicy::Unlimited));
+}
+
+#[test]
+fn invalid_boards_fall_back_to_the_default_board() {
+    let (config, errors) = GameConfig::from_query("w=3&h=3&mines=9&seed=4");
+    assert!(matches!(
+        errors.as_slice(),
+        [GameConfigError::InvalidBoard(
+            BoardOptionsError::TooManyBombs { .. }
+        )]
+    ));
+    assert_eq!(config.preset(), None);
+    assert_eq!(config.seed, Some(4));
+
+    let (config, errors) = GameConfig::from_query("w=0");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(config.preset(), None);
+}
+
+#[test]
+fn oversized_boards_fall_back_to_the_default_board() {
+    let (config, errors) = GameConfig::from_query("w=60000&h=60000&seed=4");
+    assert_eq!(
+        errors,
+        [GameConfigError::BoardTooLarge {
+            width: 60000,
+            height: 60000
+        }]
+    );
+    assert_eq!(config.preset(), None);
+    assert_eq!(config.seed, Some(4));
+
+    let max = BoardPreset::MAX_SIZE;
+    let (config, errors) = GameConfig::from_query(&format!("w={}&h={}", max, max + 1));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(config.preset(), None);
+
+    let (config, errors) = GameConfig::from_query(&format!("w={}&h={}&mines=1", max, max));
+    assert_eq!(errors, []);
+    assert_eq!(
+        config.preset(),
+        Some(BoardPreset::Custom {
+            width: max,
+            height: max,
+            bomb_count: 1
+        })
+    );
+}
+
+#[test]
+fn query_values_are_percent_decoded() {
+    let (config, errors) = GameConfig::from_query("preset=Expert%20&mode=%63lassic&undo=+flags");
+    assert_eq!(errors, []);
+    assert_eq!(config.preset, Some(BoardPreset::Expert));
+    assert_eq!(config.mode, Some(GameMode::Classic));
+    assert_eq!(config.undo, Some(UndoPolicy::FlagsOnly));
+
+    // Encoded separators are part of the value
+    let (config, errors) = GameConfig::from_query("seed=1%262&w%3D4");
+    assert_eq!(
+        errors,
+        [
+            GameConfigError::InvalidValue {
+                key: "seed".to_string(),
+                value: "1&2".to_string(),
+                expected: "an unsigned integer",
+            },
+            GameConfigError::MissingValue("w%3D4".to_string()),
+        ]
+    );
+    assert_eq!(config, GameConfig::default());
+
+    // Invalid escapes are kept as they are
+    let (_, errors) = GameConfig::from_query("preset=%zz%4");
+    assert!(matches!(
+        errors.as_slice(),
+        [GameConfigError::InvalidValue { value, .. }] if value == "%zz%4"
+    ));
+}
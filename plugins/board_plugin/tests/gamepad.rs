@@ -0,0 +1,105 @@
+//! Drives the gamepad cursor with synthetic raw gamepad events on a headless app
+
+use bevy::input::gamepad::{GamepadEventRaw, GamepadEventType};
+use bevy::prelude::*;
+use board_plugin::{
+    components::Coordinates,
+    events::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{BoardCursor, GamepadBindings},
+    systems::gamepad::gamepad_handling,
+};
+
+mod common;
+
+const GAMEPAD: Gamepad = Gamepad(0);
+
+/// Headless app with a connected gamepad and the cursor on the corner of a 4x4 board
+fn test_app() -> App {
+    let mut app = common::headless_app();
+    app.add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .init_resource::<GamepadBindings>()
+        .add_system(gamepad_handling);
+    common::insert_board(&mut app);
+    let cursor_entity = app.world.spawn().id();
+    app.insert_resource(BoardCursor::new(cursor_entity, Coordinates::new(0, 0)));
+
+    send(&mut app, GamepadEventType::Connected);
+    app.update();
+    app
+}
+
+fn send(app: &mut App, event: GamepadEventType) {
+    common::send(app, GamepadEventRaw(GAMEPAD, event));
+}
+
+/// Presses and releases a button, one frame apart
+fn tap(app: &mut App, button: GamepadButtonType) {
+    send(app, GamepadEventType::ButtonChanged(button, 1.0));
+    app.update();
+    send(app, GamepadEventType::ButtonChanged(button, 0.0));
+    app.update();
+}
+
+/// Coordinates of the events sent since the last drain
+fn drain<E: Send + Sync + 'static>(
+    app: &mut App,
+    coordinates: fn(E) -> Coordinates,
+) -> Vec<Coordinates> {
+    common::drain(app).into_iter().map(coordinates).collect()
+}
+
+fn cursor(app: &App) -> Coordinates {
+    app.world.get_resource::<BoardCursor>().unwrap().coordinates
+}
+
+#[test]
+fn buttons_send_tile_events_under_the_cursor() {
+    let mut app = test_app();
+    tap(&mut app, GamepadButtonType::DPadRight);
+    tap(&mut app, GamepadButtonType::DPadUp);
+    assert_eq!(cursor(&app), Coordinates::new(1, 1));
+
+    // Events only live for two updates, they are read after each tap
+    let tile = vec![Coordinates::new(1, 1)];
+    tap(&mut app, GamepadButtonType::South);
+    assert_eq!(drain::<TileTriggerEvent>(&mut app, |e| e.0), tile);
+    tap(&mut app, GamepadButtonType::West);
+    assert_eq!(drain::<TileMarkEvent>(&mut app, |e| e.0), tile);
+    tap(&mut app, GamepadButtonType::RightTrigger);
+    assert_eq!(drain::<TileChordEvent>(&mut app, |e| e.0), tile);
+    assert!(drain::<TileTriggerEvent>(&mut app, |e| e.0).is_empty());
+}
+
+#[test]
+fn sticks_move_the_cursor_past_the_threshold_within_the_board() {
+    let mut app = test_app();
+    // Below the threshold nothing moves
+    send(
+        &mut app,
+        GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.3),
+    );
+    app.update();
+    assert_eq!(cursor(&app), Coordinates::new(0, 0));
+
+    send(
+        &mut app,
+        GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.9),
+    );
+    app.update();
+    assert_eq!(cursor(&app), Coordinates::new(1, 0));
+
+    // Pushing down on the bottom row keeps the cursor on the board
+    send(
+        &mut app,
+        GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.0),
+    );
+    send(
+        &mut app,
+        GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, -1.0),
+    );
+    app.update();
+    assert_eq!(cursor(&app), Coordinates::new(1, 0));
+    assert!(drain::<TileTriggerEvent>(&mut app, |e| e.0).is_empty());
+}
@@ -0,0 +1,76 @@
+//! Clicks the menu buttons of a headless app, without spawning the menu itself
+
+use bevy::prelude::*;
+use board_plugin::{
+    components::{MenuButton, MenuUndoText},
+    menu::MenuTarget,
+    resources::{BoardOptions, BoardPreset, UndoPolicy},
+    systems::menu::menu_buttons,
+};
+use common::TestState;
+
+mod common;
+
+/// Idle app handling the menu buttons, with an undo policy text
+fn menu_app() -> App {
+    let mut app = common::headless_app();
+    app.insert_resource(BoardOptions::default())
+        .insert_resource(MenuTarget(TestState::InGame))
+        .add_state(TestState::Idle)
+        .add_system(menu_buttons::<TestState>);
+    app.world
+        .spawn()
+        .insert(Text::with_section(
+            "Off",
+            TextStyle::default(),
+            TextAlignment::default(),
+        ))
+        .insert(MenuUndoText);
+    app
+}
+
+fn click(app: &mut App, button: MenuButton) {
+    app.world
+        .spawn()
+        .insert(Interaction::Clicked)
+        .insert(button)
+        .insert(UiColor::default());
+    app.update();
+}
+
+fn undo_text(app: &mut App) -> String {
+    let mut texts = app.world.query_filtered::<&Text, With<MenuUndoText>>();
+    texts.iter(&app.world).next().unwrap().sections[0]
+        .value
+        .clone()
+}
+
+#[test]
+fn undo_policy_is_switched_and_kept_by_started_games() {
+    let mut app = menu_app();
+    click(&mut app, MenuButton::CycleUndo);
+    click(&mut app, MenuButton::CycleUndo);
+    let options = app.world.get_resource::<BoardOptions>().unwrap();
+    assert_eq!(options.undo, Some(UndoPolicy::FlagsOnly));
+    assert_eq!(undo_text(&mut app), "Flags only");
+    let state = app.world.get_resource::<State<TestState>>().unwrap();
+    assert_eq!(state.current(), &TestState::Idle);
+
+    click(&mut app, MenuButton::Preset(BoardPreset::Beginner));
+    let options = app.world.get_resource::<BoardOptions>().unwrap();
+    assert_eq!(options.undo, Some(UndoPolicy::FlagsOnly));
+    assert_eq!(options.map_size, BoardPreset::Beginner.map_size());
+    let state = app.world.get_resource::<State<TestState>>().unwrap();
+    assert_eq!(state.current(), &TestState::InGame);
+}
+
+#[test]
+fn undo_is_switched_off_after_the_last_policy() {
+    let mut app = menu_app();
+    for _ in 0..4 {
+        click(&mut app, MenuButton::CycleUndo);
+    }
+    let options = app.world.get_resource::<BoardOptions>().unwrap();
+    assert_eq!(options.undo, None);
+    assert_eq!(undo_text(&mut app), "Off");
+}
@@ -0,0 +1,194 @@
+//! Contrast and distinguishability checks of the bomb counter palettes
+
+use bevy::prelude::*;
+use board_plugin::resources::{
+    contrast_ratio, AccessibilityOptions, BoardAssets, BoardTheme, Palette, SpriteMaterial,
+};
+
+/// WCAG 2 minimum contrast for large text and graphics
+const MIN_CONTRAST: f32 = 3.0;
+/// WCAG 2 enhanced contrast for normal text
+const HIGH_CONTRAST: f32 = 7.0;
+/// Minimum linear RGB distance between two counter colours as seen by the palette audience
+const MIN_DISTANCE: f32 = 0.25;
+
+/// Machado et al. (2009) full severity colour vision deficiency simulation, in linear RGB
+fn simulate(palette: Palette, colour: Color) -> Vec3 {
+    let matrix = match palette {
+        Palette::Deuteranopia => Mat3::from_cols_array(&[
+            0.367_322, 0.280_085, -0.011_820, 0.860_646, 0.672_501, 0.042_940, -0.227_968,
+            0.047_413, 0.968_881,
+        ]),
+        Palette::Protanopia => Mat3::from_cols_array(&[
+            0.152_286, 0.114_503, -0.003_882, 1.052_583, 0.786_281, -0.048_116, -0.204_868,
+            0.099_216, 1.051_998,
+        ]),
+        Palette::Tritanopia => Mat3::from_cols_array(&[
+            1.255_528, -0.078_411, 0.004_733, -0.076_749, 0.930_809, 0.691_367, -0.178_779,
+            0.147_602, 0.303_900,
+        ]),
+        Palette::Standard | Palette::HighContrast => Mat3::IDENTITY,
+    };
+    let [red, green, blue, _] = colour.as_linear_rgba_f32();
+    (matrix * Vec3::new(red, green, blue)).clamp(Vec3::ZERO, Vec3::ONE)
+}
+
+#[test]
+fn palettes_have_a_colour_per_count() {
+    for palette in Palette::ALL {
+        assert_eq!(palette.colours().len(), 8, "{:?}", palette);
+    }
+    assert_eq!(BoardAssets::default_colors().len(), 8);
+}
+
+#[test]
+fn palettes_contrast_with_their_tiles() {
+    for palette in Palette::ALL {
+        let min = match palette {
+            Palette::HighContrast => HIGH_CONTRAST,
+            _ => MIN_CONTRAST,
+        };
+        for (i, colour) in palette.colours().into_iter().enumerate() {
+            let ratio = contrast_ratio(colour, palette.tile_colour());
+            assert!(
+                ratio >= min,
+                "{:?} counter {} has contrast {:.2} against its tile",
+                palette,
+                i + 1,
+                ratio
+            );
+        }
+    }
+}
+
+#[test]
+fn theme_packs_contrast_with_their_tiles_under_every_palette() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/themes");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let theme: BoardTheme = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        // The theme colours are kept without a palette
+        let palettes = std::iter::once(None).chain(Palette::ALL.into_iter().map(Some));
+        for palette in palettes {
+            let mut assets = BoardAssets {
+                tile_material: SpriteMaterial {
+                    colour: theme.tile.colour,
+                    ..SpriteMaterial::default()
+                },
+                bomb_counter_colours: theme.bomb_counter_colours.clone(),
+                ..BoardAssets::default()
+            };
+            AccessibilityOptions {
+                palette,
+                glyphs: false,
+            }
+            .apply(&mut assets);
+            let min = match palette {
+                Some(Palette::HighContrast) => HIGH_CONTRAST,
+                _ => MIN_CONTRAST,
+            };
+            for (i, colour) in assets.bomb_counter_colours.iter().enumerate() {
+                let ratio = contrast_ratio(*colour, assets.tile_material.colour);
+                assert!(
+                    ratio >= min,
+                    "{} counter {} has contrast {:.2} against its tile with palette {:?}",
+                    path.display(),
+                    i + 1,
+                    ratio,
+                    palette
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn palettes_are_distinct_for_their_audience() {
+    for palette in Palette::ALL {
+        let colours: Vec<_> = palette
+            .colours()
+            .into_iter()
+            .map(|c| simulate(palette, c))
+            .collect();
+        for (i, a) in colours.iter().enumerate() {
+            for (j, b) in colours.iter().enumerate().skip(i + 1) {
+                assert!(
+                    a.distance(*b) >= MIN_DISTANCE,
+                    "{:?} counters {} and {} look alike",
+                    palette,
+                    i + 1,
+                    j + 1
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn contrast_ratio_bounds() {
+    assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 1e-3);
+    assert!((contrast_ratio(Color::GRAY, Color::GRAY) - 1.0).abs() < 1e-6);
+}
+
+fn test_assets() -> BoardAssets {
+    BoardAssets {
+        label: "Test".to_string(),
+        bomb_counter_colours: vec![Color::WHITE],
+        ..BoardAssets::default()
+    }
+}
+
+#[test]
+fn accessibility_options_override_theme() {
+    let mut assets = test_assets();
+    AccessibilityOptions {
+        palette: Some(Palette::HighContrast),
+        glyphs: true,
+    }
+    .apply(&mut assets);
+    assert_eq!(assets.bomb_counter_colours, Palette::HighContrast.colours());
+    assert_eq!(
+        assets.tile_material.colour,
+        Palette::HighContrast.tile_colour()
+    );
+    assert!(assets.bomb_counter_glyphs);
+
+    let mut assets = test_assets();
+    AccessibilityOptions::default().apply(&mut assets);
+    assert_eq!(assets.bomb_counter_colours, vec![Color::WHITE]);
+    assert!(!assets.bomb_counter_glyphs);
+}
+
+#[test]
+fn palette_cycle_returns_to_theme_colours() {
+    let mut options = AccessibilityOptions::default();
+    let mut seen = vec![];
+    for _ in 0..=Palette::ALL.len() {
+        options.cycle_palette();
+        seen.push(options.palette);
+    }
+    let mut expected: Vec<_> = Palette::ALL.into_iter().map(Some).collect();
+    expected.push(None);
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn glyphs_are_distinct_and_optional() {
+    let mut assets = test_assets();
+    assert_eq!(assets.bomb_count_text(3, 20.0).sections.len(), 1);
+    assets.bomb_counter_glyphs = true;
+    let glyphs: Vec<_> = (1..=8)
+        .map(|count| {
+            assets.bomb_count_text(count, 20.0).sections[1]
+                .value
+                .clone()
+        })
+        .collect();
+    for (i, glyph) in glyphs.iter().enumerate() {
+        assert!(
+            !glyphs[i + 1..].contains(glyph),
+            "duplicate glyph {}",
+            glyph
+        );
+    }
+}
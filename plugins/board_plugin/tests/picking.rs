@@ -0,0 +1,295 @@
+//! Picks, highlights and presses tiles under a synthetic mouse cursor on a headless app
+
+mod common;
+
+use bevy::ecs::event::Events;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::ElementState;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use board_plugin::{
+    components::{Coordinates, TileHighlight},
+    events::{TileChordEvent, TileHoverEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{Board, HoveredTile, TilePick, TilePress},
+    systems::{
+        highlight::highlight_tiles,
+        input::{input_handling, track_hover},
+    },
+};
+use common::{drain, tile_position, TILE_SIZE, WINDOW_SIZE};
+
+/// Headless app with the `common::insert_board` board, picked through `common::spawn_camera`
+fn test_app() -> App {
+    let mut app = common::headless_app();
+    app.add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .add_event::<TileHoverEvent>()
+        .init_resource::<HoveredTile>()
+        .init_resource::<TilePress>()
+        .add_system(input_handling)
+        .add_system(track_hover)
+        .add_system(highlight_tiles);
+    common::add_window(&mut app);
+    common::spawn_camera(&mut app);
+    common::insert_board(&mut app);
+    app
+}
+
+/// Moves the mouse cursor to a window position, `None` leaving the window
+fn move_cursor(app: &mut App, position: Option<Vec2>) {
+    let mut windows = app.world.get_resource_mut::<Windows>().unwrap();
+    windows
+        .get_primary_mut()
+        .unwrap()
+        .update_cursor_physical_position_from_backend(position.map(|p| p.as_dvec2()));
+}
+
+fn button(app: &mut App, button: MouseButton, state: ElementState) {
+    app.world
+        .get_resource_mut::<Events<MouseButtonInput>>()
+        .unwrap()
+        .send(MouseButtonInput { button, state });
+}
+
+/// Presses and releases a button on the given window positions, one frame apart
+fn click(app: &mut App, mouse_button: MouseButton, pressed: Vec2, released: Vec2) {
+    move_cursor(app, Some(pressed));
+    button(app, mouse_button, ElementState::Pressed);
+    app.update();
+    move_cursor(app, Some(released));
+    button(app, mouse_button, ElementState::Released);
+    app.update();
+}
+
+fn pick(app: &App, x: u16, y: u16) -> TilePick {
+    let board = app.world.get_resource::<Board>().unwrap();
+    let coordinates = Coordinates::new(x, y);
+    TilePick {
+        coordinates,
+        entity: board.tile_entities[&coordinates],
+    }
+}
+
+fn hovered(app: &App) -> Option<TilePick> {
+    app.world.get_resource::<HoveredTile>().unwrap().0
+}
+
+#[test]
+fn hovering_sends_an_event_per_tile_change() {
+    let mut app = test_app();
+    app.update();
+    assert!(drain::<TileHoverEvent>(&mut app).is_empty());
+
+    let mut events = Vec::new();
+    for position in [
+        Some(tile_position(1, 2)),
+        // Staying on the same tile sends nothing
+        Some(tile_position(1, 2) + Vec2::splat(10.0)),
+        Some(tile_position(2, 2)),
+        None,
+    ] {
+        move_cursor(&mut app, position);
+        app.update();
+        events.extend(drain::<TileHoverEvent>(&mut app));
+    }
+
+    let (a, b) = (pick(&app, 1, 2), pick(&app, 2, 2));
+    assert_eq!(
+        events,
+        vec![
+            TileHoverEvent {
+                left: None,
+                entered: Some(a)
+            },
+            TileHoverEvent {
+                left: Some(a),
+                entered: Some(b)
+            },
+            TileHoverEvent {
+                left: Some(b),
+                entered: None
+            },
+        ]
+    );
+    assert_eq!(hovered(&app), None);
+}
+
+#[test]
+fn board_edges_are_half_open() {
+    let mut app = test_app();
+    let mins = WINDOW_SIZE * 0.5 - Vec2::splat(2.0 * TILE_SIZE);
+    let maxs = WINDOW_SIZE * 0.5 + Vec2::splat(2.0 * TILE_SIZE);
+
+    move_cursor(&mut app, Some(mins));
+    app.update();
+    assert_eq!(hovered(&app), Some(pick(&app, 0, 0)));
+
+    // The right and top borders belong to no tile
+    move_cursor(&mut app, Some(Vec2::new(maxs.x, mins.y)));
+    app.update();
+    assert_eq!(hovered(&app), None);
+    move_cursor(&mut app, Some(Vec2::new(mins.x, maxs.y)));
+    app.update();
+    assert_eq!(hovered(&app), None);
+    move_cursor(&mut app, Some(maxs - Vec2::splat(0.5)));
+    app.update();
+    assert_eq!(hovered(&app), Some(pick(&app, 3, 3)));
+}
+
+#[test]
+fn picking_follows_the_camera() {
+    let mut app = test_app();
+    // Moving the camera one tile right and zooming out twice
+    let mut cameras = app
+        .world
+        .query_filtered::<&mut GlobalTransform, With<Camera2d>>();
+    *cameras.iter_mut(&mut app.world).next().unwrap() = GlobalTransform {
+        translation: Vec3::new(TILE_SIZE, 0.0, 0.0),
+        scale: Vec3::new(2.0, 2.0, 1.0),
+        ..GlobalTransform::default()
+    };
+
+    // The window centre now shows the origin of tile (3, 2)
+    let centre = WINDOW_SIZE * 0.5;
+    let position = centre + Vec2::splat(1.0);
+    click(&mut app, MouseButton::Left, position, position);
+    assert_eq!(hovered(&app), Some(pick(&app, 3, 2)));
+    let triggers: Vec<_> = drain::<TileTriggerEvent>(&mut app)
+        .into_iter()
+        .map(|e| e.0)
+        .collect();
+    assert_eq!(triggers, vec![Coordinates::new(3, 2)]);
+
+    // A tile is now 25 pixels wide on screen
+    move_cursor(&mut app, Some(centre - Vec2::new(26.0, 1.0)));
+    button(&mut app, MouseButton::Right, ElementState::Pressed);
+    app.update();
+    let marks: Vec<_> = drain::<TileMarkEvent>(&mut app)
+        .into_iter()
+        .map(|e| e.0)
+        .collect();
+    assert_eq!(marks, vec![Coordinates::new(1, 1)]);
+}
+
+/// Highlighted tiles, sorted
+fn highlights(app: &mut App, kind: TileHighlight) -> Vec<Coordinates> {
+    let tile_size = app.world.get_resource::<Board>().unwrap().tile_size;
+    let mut tiles: Vec<_> = app
+        .world
+        .query::<(&TileHighlight, &Transform)>()
+        .iter(&app.world)
+        .filter(|(highlight, _)| **highlight == kind)
+        .map(|(_, transform)| {
+            let tile = (transform.translation.truncate() / tile_size).floor();
+            Coordinates::new(tile.x as u16, tile.y as u16)
+        })
+        .collect();
+    tiles.sort_unstable();
+    tiles
+}
+
+#[test]
+fn tiles_are_uncovered_on_release_over_the_pressed_tile() {
+    let mut app = test_app();
+    move_cursor(&mut app, Some(tile_position(2, 1)));
+    button(&mut app, MouseButton::Left, ElementState::Pressed);
+    app.update();
+    app.update();
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Pressed),
+        vec![Coordinates::new(2, 1)]
+    );
+    assert!(highlights(&mut app, TileHighlight::Hover).is_empty());
+
+    // Dragging off the tile removes the preview, coming back restores it
+    move_cursor(&mut app, Some(tile_position(3, 1)));
+    app.update();
+    app.update();
+    assert!(highlights(&mut app, TileHighlight::Pressed).is_empty());
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Hover),
+        vec![Coordinates::new(3, 1)]
+    );
+    move_cursor(&mut app, Some(tile_position(2, 1)));
+    button(&mut app, MouseButton::Left, ElementState::Released);
+    app.update();
+    app.update();
+
+    let triggers: Vec<_> = drain::<TileTriggerEvent>(&mut app)
+        .into_iter()
+        .map(|e| e.0)
+        .collect();
+    assert_eq!(triggers, vec![Coordinates::new(2, 1)]);
+    assert!(highlights(&mut app, TileHighlight::Pressed).is_empty());
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Hover),
+        vec![Coordinates::new(2, 1)]
+    );
+}
+
+#[test]
+fn dragging_off_the_tile_cancels_the_press() {
+    let mut app = test_app();
+    click(
+        &mut app,
+        MouseButton::Left,
+        tile_position(2, 1),
+        tile_position(2, 2),
+    );
+    // Released off the board
+    click(
+        &mut app,
+        MouseButton::Middle,
+        tile_position(1, 1),
+        WINDOW_SIZE * 0.5 + Vec2::splat(3.0 * TILE_SIZE),
+    );
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+    assert!(drain::<TileChordEvent>(&mut app).is_empty());
+    assert_eq!(app.world.get_resource::<TilePress>().unwrap().press, None);
+}
+
+#[test]
+fn chords_preview_the_neighbours_that_can_be_uncovered() {
+    let mut app = test_app();
+    {
+        let mut board = app.world.get_resource_mut::<Board>().unwrap();
+        board.try_toggle_mark(&Coordinates::new(1, 0), false);
+    }
+    // Left presses on uncovered tiles chord as well
+    move_cursor(&mut app, Some(tile_position(0, 0)));
+    button(&mut app, MouseButton::Left, ElementState::Pressed);
+    app.update();
+    app.update();
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Pressed),
+        vec![Coordinates::new(0, 1), Coordinates::new(1, 1)]
+    );
+    button(&mut app, MouseButton::Left, ElementState::Released);
+    app.update();
+    let chords: Vec<_> = drain::<TileChordEvent>(&mut app)
+        .into_iter()
+        .map(|e| e.0)
+        .collect();
+    assert_eq!(chords, vec![Coordinates::new(0, 0)]);
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+
+    // Middle presses on covered tiles preview their neighbours, not the tile itself
+    move_cursor(&mut app, Some(tile_position(3, 3)));
+    button(&mut app, MouseButton::Middle, ElementState::Pressed);
+    app.update();
+    app.update();
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Pressed),
+        vec![
+            Coordinates::new(2, 2),
+            Coordinates::new(2, 3),
+            Coordinates::new(3, 2)
+        ]
+    );
+    assert_eq!(
+        highlights(&mut app, TileHighlight::Hover),
+        vec![Coordinates::new(3, 3)]
+    );
+}
@@ -0,0 +1,230 @@
+//! Records games and plays them back, without a window as in headless runs
+
+use bevy::prelude::*;
+use board_plugin::{
+    components::Coordinates,
+    events::{RedoEvent, TileMarkEvent, TileTriggerEvent, UndoEvent},
+    replay::ReplayPlugin,
+    resources::{
+        Board, BoardOptions, BoardSave, BoardState, BoardStatus, Move, Replay, ReplayPlayer,
+        ReplayRecorder, UndoPolicy,
+    },
+};
+use common::TestState;
+
+mod common;
+
+/// App without a primary window, running a seeded 9x9 board
+fn replay_app(save: Option<BoardSave>, player: Option<ReplayPlayer>) -> App {
+    undo_replay_app(None, save, player)
+}
+
+/// Same as `replay_app`, moves being taken back under `undo`
+fn undo_replay_app(
+    undo: Option<UndoPolicy>,
+    save: Option<BoardSave>,
+    player: Option<ReplayPlayer>,
+) -> App {
+    let mut app = common::board_app(BoardOptions {
+        map_size: (9, 9),
+        bomb_count: 10,
+        seed: Some(7),
+        safe_start: true,
+        undo,
+        ..BoardOptions::default()
+    });
+    app.add_plugin(ReplayPlugin {
+        running_state: TestState::InGame,
+    });
+    if let Some(save) = save {
+        app.insert_resource(save);
+    }
+    if let Some(player) = player {
+        app.insert_resource(player);
+    }
+    app.update();
+    app
+}
+
+fn run(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Covered safe tiles and a bomb of the board
+fn safe_tiles_and_bomb(app: &App) -> (Vec<Coordinates>, Coordinates) {
+    let board = app.world.get_resource::<Board>().unwrap();
+    let tiles = (0..9).flat_map(|y| (0..9).map(move |x| Coordinates::new(x, y)));
+    let safe = tiles
+        .clone()
+        .filter(|c| !board.tile_map.is_bomb_at(*c) && board.covered_tiles.contains_key(c))
+        .collect();
+    let bomb = tiles
+        .clone()
+        .find(|c| board.tile_map.is_bomb_at(*c))
+        .unwrap();
+    (safe, bomb)
+}
+
+fn recording(app: &App) -> Replay {
+    app.world
+        .get_resource::<ReplayRecorder>()
+        .unwrap()
+        .replay
+        .clone()
+        .unwrap()
+}
+
+/// Plays a replay back out of real time, on a board with its undo policy
+fn play_back(replay: &Replay) -> App {
+    let mut app = undo_replay_app(
+        replay.undo,
+        Some(replay.board.clone()),
+        Some(ReplayPlayer::new(replay, false)),
+    );
+    run(&mut app, 30);
+    assert!(app
+        .world
+        .get_resource::<ReplayPlayer>()
+        .unwrap()
+        .is_finished());
+    app
+}
+
+/// Board state, the time left out
+fn board_state(app: &App) -> BoardSave {
+    let board = app.world.get_resource::<Board>().unwrap();
+    let status = app.world.get_resource::<BoardStatus>().unwrap();
+    BoardSave {
+        elapsed: 0.0,
+        ..BoardSave::new(board, status)
+    }
+}
+
+#[test]
+fn recorded_games_play_back_identically() {
+    let mut app = replay_app(None, None);
+    run(&mut app, 2);
+    let (safe, bomb) = safe_tiles_and_bomb(&app);
+    common::send(&mut app, TileMarkEvent(bomb));
+    run(&mut app, 2);
+    for &coords in &safe[..2] {
+        common::send(&mut app, TileTriggerEvent(coords));
+        run(&mut app, 2);
+    }
+    run(&mut app, 2);
+
+    let replay = recording(&app);
+    assert_eq!(replay.moves.len(), 3);
+    assert_eq!(replay.moves[0].1, Move::Mark(bomb));
+    assert!(replay.board.marks.is_empty());
+
+    let playback = play_back(&replay);
+    assert_eq!(board_state(&playback), board_state(&app));
+}
+
+#[test]
+fn replayed_bomb_ends_the_game() {
+    let replay = Replay {
+        board: BoardSave {
+            map_size: (4, 3),
+            bombs: vec![Coordinates::new(0, 0)],
+            uncovered: Vec::new(),
+            marks: Vec::new(),
+            elapsed: 0.0,
+        },
+        moves: vec![(0.0, Move::Trigger(Coordinates::new(0, 0)))],
+        undo: None,
+    };
+    let mut app = replay_app(
+        Some(replay.board.clone()),
+        Some(ReplayPlayer::new(&replay, false)),
+    );
+    run(&mut app, 4);
+    let status = app.world.get_resource::<BoardStatus>().unwrap();
+    assert_eq!(status.state, BoardState::Lost);
+}
+
+#[test]
+fn undone_moves_play_back_identically() {
+    let mut app = undo_replay_app(Some(UndoPolicy::Unlimited), None, None);
+    run(&mut app, 2);
+    let (safe, bomb) = safe_tiles_and_bomb(&app);
+    common::send(&mut app, TileMarkEvent(bomb));
+    run(&mut app, 2);
+    common::send(&mut app, TileTriggerEvent(safe[0]));
+    run(&mut app, 4);
+    common::send(&mut app, UndoEvent);
+    run(&mut app, 2);
+    common::send(&mut app, UndoEvent);
+    run(&mut app, 2);
+    common::send(&mut app, RedoEvent);
+    run(&mut app, 2);
+    common::send(&mut app, TileTriggerEvent(*safe.last().unwrap()));
+    run(&mut app, 4);
+
+    let replay = recording(&app);
+    assert_eq!(replay.undo, Some(UndoPolicy::Unlimited));
+    let moves: Vec<_> = replay.moves.iter().map(|&(_, m)| m).collect();
+    assert_eq!(
+        moves,
+        [
+            Move::Mark(bomb),
+            Move::Trigger(safe[0]),
+            Move::Undo,
+            Move::Undo,
+            Move::Redo,
+            Move::Trigger(*safe.last().unwrap()),
+        ]
+    );
+    // The flag came back with the redo, the first reveal stayed undone
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert!(board.is_flagged(&bomb));
+    assert!(board.covered_tiles.contains_key(&safe[0]));
+
+    let playback = play_back(&replay);
+    assert_eq!(board_state(&playback), board_state(&app));
+}
+
+#[test]
+fn moves_undone_after_death_play_back_past_the_loss() {
+    let policy = UndoPolicy::AfterDeath { penalty: 5.0 };
+    let mut app = undo_replay_app(Some(policy), None, None);
+    run(&mut app, 2);
+    let (safe, bomb) = safe_tiles_and_bomb(&app);
+    common::send(&mut app, TileTriggerEvent(bomb));
+    run(&mut app, 4);
+    assert_eq!(
+        app.world.get_resource::<BoardStatus>().unwrap().state,
+        BoardState::Lost
+    );
+    common::send(&mut app, UndoEvent);
+    run(&mut app, 2);
+    common::send(&mut app, TileTriggerEvent(safe[0]));
+    run(&mut app, 4);
+
+    let replay = recording(&app);
+    assert_eq!(replay.undo, Some(policy));
+    let moves: Vec<_> = replay.moves.iter().map(|&(_, m)| m).collect();
+    assert_eq!(
+        moves,
+        [Move::Trigger(bomb), Move::Undo, Move::Trigger(safe[0])]
+    );
+
+    let playback = play_back(&replay);
+    let status = playback.world.get_resource::<BoardStatus>().unwrap();
+    assert_eq!(status.state, BoardState::Playing);
+    assert_eq!(board_state(&playback), board_state(&app));
+}
+
+#[test]
+fn undo_events_are_not_recorded_without_a_policy() {
+    let mut app = replay_app(None, None);
+    run(&mut app, 2);
+    common::send(&mut app, UndoEvent);
+    run(&mut app, 2);
+    let replay = recording(&app);
+    assert_eq!(replay.undo, None);
+    assert!(replay.moves.is_empty());
+}
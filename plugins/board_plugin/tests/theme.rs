@@ -0,0 +1,78 @@
+//! Checks the bundled theme packs parse, describe every board asset and keep their counters
+//! readable, and that re-skinning follows the board assets
+
+use bevy::prelude::*;
+use board_plugin::{
+    components::Themed,
+    resources::{contrast_ratio, BoardAssets, BoardTheme},
+    systems::theme::reskin,
+};
+
+mod common;
+
+#[test]
+fn bundled_themes_parse() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/themes");
+    let mut labels = vec![];
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let source = std::fs::read_to_string(&path).unwrap();
+        let theme: BoardTheme = ron::from_str(&source)
+            .unwrap_or_else(|e| panic!("{} failed to parse: {}", path.display(), e));
+        assert_eq!(theme.bomb_counter_colours.len(), 8, "{}", path.display());
+        for (i, colour) in theme.bomb_counter_colours.iter().enumerate() {
+            let ratio = contrast_ratio(*colour, theme.tile.colour);
+            assert!(
+                ratio >= 3.0,
+                "{} counter {} has contrast {:.2} against its tile",
+                path.display(),
+                i + 1,
+                ratio
+            );
+        }
+        labels.push(theme.label);
+    }
+    labels.sort();
+    labels.dedup();
+    assert_eq!(labels, ["Default", "Retro"]);
+}
+
+#[test]
+fn reskin_rebuilds_counters_with_the_glyph_option() {
+    let mut app = common::headless_app();
+    app.insert_resource(BoardAssets::default())
+        .add_system(reskin);
+    let counter = app
+        .world
+        .spawn()
+        .insert(Themed::BombCounter(3))
+        .insert(BoardAssets::default().bomb_count_text(3, 20.0))
+        .id();
+    app.update();
+
+    let sections = |app: &App| {
+        let text = app.world.get::<Text>(counter).unwrap();
+        text.sections
+            .iter()
+            .map(|s| (s.value.clone(), s.style.font_size))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(sections(&app), [("3".to_string(), 20.0)]);
+
+    app.world
+        .get_resource_mut::<BoardAssets>()
+        .unwrap()
+        .bomb_counter_glyphs = true;
+    app.update();
+    assert_eq!(
+        sections(&app),
+        [("3".to_string(), 20.0), ("^".to_string(), 10.0)]
+    );
+
+    app.world
+        .get_resource_mut::<BoardAssets>()
+        .unwrap()
+        .bomb_counter_glyphs = false;
+    app.update();
+    assert_eq!(sections(&app), [("3".to_string(), 20.0)]);
+}
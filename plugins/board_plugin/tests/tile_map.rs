@@ -0,0 +1,175 @@
+//! Bomb placement and neighbour counting on the tile map
+
+use board_plugin::{
+    components::Coordinates,
+    resources::{BoardSave, Tile, TileMap},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn text_map(text: &str) -> TileMap {
+    BoardSave::from_text(text).unwrap().tile_map()
+}
+
+#[test]
+fn corner_counts_only_see_in_bounds_neighbours() {
+    // Bombs fill every tile around the corners, the corners themselves being safe
+    let tile_map = text_map(
+        "\
+.**.
+****
+****
+.**.
+",
+    );
+    for corner in [(0, 0), (3, 0), (0, 3), (3, 3)] {
+        assert_eq!(tile_map.bomb_count_at(corner.into()), 3, "{:?}", corner);
+        assert_eq!(
+            tile_map.map()[(corner.0.into(), corner.1.into())],
+            Tile::BombNeighbor(3)
+        );
+    }
+}
+
+#[test]
+fn corner_bombs_count_once() {
+    let tile_map = text_map(
+        "\
+*..*
+....
+....
+*..*
+",
+    );
+    assert_eq!(tile_map.bomb_count(), 4);
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(1, 1)), 1);
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(1, 0)), 1);
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(2, 2)), 1);
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(1, 2)), 1);
+    // Bombs have no count
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(0, 0)), 0);
+}
+
+#[test]
+fn single_tile_map_has_no_neighbours() {
+    let mut tile_map = TileMap::empty((1, 1));
+    assert_eq!(tile_map.bomb_count_at(Coordinates::new(0, 0)), 0);
+    tile_map.set_bombs_at([Coordinates::new(0, 0)]);
+    assert!(tile_map.is_bomb_at(Coordinates::new(0, 0)));
+    assert_eq!(tile_map.bomb_count(), 1);
+}
+
+#[test]
+fn out_of_bounds_tiles_are_not_bombs() {
+    let tile_map = text_map("**\n**\n");
+    assert!(!tile_map.is_bomb_at(Coordinates::new(2, 0)));
+    assert!(!tile_map.is_bomb_at(Coordinates::new(0, 2)));
+    assert!(!tile_map.is_bomb_at(Coordinates::new(u16::MAX, u16::MAX)));
+}
+
+#[test]
+fn offsets_out_of_range_are_rejected() {
+    let origin = Coordinates::new(0, 0);
+    assert_eq!(origin.checked_offset((-1, 0)), None);
+    assert_eq!(origin.checked_offset((0, -1)), None);
+    assert_eq!(
+        Coordinates::new(5, 7).checked_offset((1, -1)),
+        Some(Coordinates::new(6, 6))
+    );
+    let far = Coordinates::new(u16::MAX, 40_000);
+    assert_eq!(far.checked_offset((1, 0)), None);
+    assert_eq!(
+        far.checked_offset((-1, 1)),
+        Some(Coordinates::new(u16::MAX - 1, 40_001))
+    );
+    assert_eq!(far.checked_add(Coordinates::new(1, 0)), None);
+    assert_eq!(
+        origin.checked_add(Coordinates::new(2, 3)),
+        Some(Coordinates::new(2, 3))
+    );
+    // Subtraction saturates instead
+    assert_eq!(origin - Coordinates::new(1, 1), origin);
+}
+
+#[test]
+fn neighbours_stay_inside_the_map() {
+    let tile_map = TileMap::empty((3, 2));
+    let mut corner: Vec<_> = tile_map.neighbours(Coordinates::new(0, 0)).collect();
+    corner.sort_unstable();
+    assert_eq!(
+        corner,
+        vec![
+            Coordinates::new(0, 1),
+            Coordinates::new(1, 0),
+            Coordinates::new(1, 1),
+        ]
+    );
+    assert_eq!(tile_map.neighbours(Coordinates::new(1, 0)).count(), 5);
+    assert!(tile_map
+        .neighbours(Coordinates::new(2, 1))
+        .all(|c| tile_map.contains(c)));
+    // Outside tiles only see the map edge
+    assert_eq!(tile_map.neighbours(Coordinates::new(3, 2)).count(), 1);
+    assert_eq!(
+        tile_map
+            .neighbours(Coordinates::new(u16::MAX, u16::MAX))
+            .count(),
+        0
+    );
+    assert_eq!(
+        TileMap::empty((1, 1))
+            .neighbours(Coordinates::new(0, 0))
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn bomb_placement_is_capped_and_seeded() {
+    let mut full = TileMap::empty((3, 3));
+    full.set_bombs_with_rng(100, &mut StdRng::seed_from_u64(1));
+    assert_eq!(full.bomb_count(), 9);
+    assert!(full.map().iter().all(|tile| tile.is_bomb()));
+
+    let seeded = |seed| {
+        let mut tile_map = TileMap::empty((9, 9));
+        tile_map.set_bombs_with_rng(10, &mut StdRng::seed_from_u64(seed));
+        tile_map.map().clone()
+    };
+    assert_eq!(seeded(3), seeded(3));
+    assert_ne!(seeded(3), seeded(4));
+}
+
+#[test]
+fn explicit_bombs_skip_duplicates_and_outsiders() {
+    let mut tile_map = TileMap::empty((3, 3));
+    tile_map.set_bombs_at([
+        Coordinates::new(1, 1),
+        Coordinates::new(1, 1),
+        Coordinates::new(3, 0),
+    ]);
+    assert_eq!(tile_map.bomb_count(), 1);
+    assert_eq!(tile_map.map()[(0, 0)], Tile::BombNeighbor(1));
+}
+
+#[test]
+fn explicit_bombs_stop_at_the_bomb_count_limit() {
+    let mut tile_map = TileMap::empty((256, 257));
+    tile_map.set_bombs_at((0..257).flat_map(|y| (0..256).map(move |x| Coordinates::new(x, y))));
+    assert_eq!(tile_map.bomb_count(), u16::MAX);
+    let bombs = tile_map.map().iter().filter(|tile| tile.is_bomb()).count();
+    assert_eq!(bombs, usize::from(u16::MAX));
+}
+
+#[test]
+fn first_empty_tile_scans_from_the_bottom_left() {
+    let tile_map = text_map(
+        "\
+....
+....
+*...
+",
+    );
+    // The bottom row touches the bomb up to x = 1
+    assert_eq!(tile_map.first_empty_tile(), Some(Coordinates::new(2, 0)));
+    assert_eq!(text_map("*\n").first_empty_tile(), None);
+}
@@ -0,0 +1,155 @@
+//! Drives the touch systems with synthetic `TouchInput` events on a headless app
+
+use bevy::ecs::event::Events;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use board_plugin::{
+    components::Coordinates,
+    events::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{CameraOptions, TouchOptions},
+    systems::touch::{pinch_zoom, touch_handling},
+};
+use common::{drain, tile_position, WINDOW_SIZE};
+
+mod common;
+
+/// Headless app with a 4x4 board centred on the origin, every tile covered except (0, 0)
+fn test_app(touch_options: TouchOptions) -> App {
+    let mut app = common::headless_app();
+    app.add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .insert_resource(touch_options)
+        .insert_resource(CameraOptions::default())
+        .add_system(touch_handling)
+        .add_system(pinch_zoom);
+    common::add_window(&mut app);
+    common::spawn_camera(&mut app);
+    common::insert_board(&mut app);
+    app
+}
+
+fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2) {
+    app.world
+        .get_resource_mut::<Events<TouchInput>>()
+        .unwrap()
+        .send(TouchInput {
+            phase,
+            position,
+            force: None,
+            id,
+        });
+}
+
+fn coordinates<E, F: Fn(&E) -> Coordinates>(events: &[E], f: F) -> Vec<Coordinates> {
+    events.iter().map(f).collect()
+}
+
+#[test]
+fn tap_uncovers_covered_tile() {
+    let mut app = test_app(TouchOptions::default());
+    touch(&mut app, 0, TouchPhase::Started, tile_position(2, 1));
+    app.update();
+    touch(&mut app, 0, TouchPhase::Ended, tile_position(2, 1));
+    app.update();
+
+    let triggers = drain::<TileTriggerEvent>(&mut app);
+    assert_eq!(
+        coordinates(&triggers, |e| e.0),
+        vec![Coordinates::new(2, 1)]
+    );
+    assert!(drain::<TileMarkEvent>(&mut app).is_empty());
+    assert!(drain::<TileChordEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn tap_on_revealed_tile_chords() {
+    let mut app = test_app(TouchOptions::default());
+    touch(&mut app, 0, TouchPhase::Started, tile_position(0, 0));
+    app.update();
+    touch(&mut app, 0, TouchPhase::Ended, tile_position(0, 0));
+    app.update();
+
+    let chords = drain::<TileChordEvent>(&mut app);
+    assert_eq!(coordinates(&chords, |e| e.0), vec![Coordinates::new(0, 0)]);
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn long_press_marks_once() {
+    let mut app = test_app(TouchOptions {
+        long_press_duration: 0.0,
+        ..TouchOptions::default()
+    });
+    touch(&mut app, 0, TouchPhase::Started, tile_position(3, 3));
+    let mut marks = vec![];
+    for _ in 0..2 {
+        app.update();
+        marks.extend(drain::<TileMarkEvent>(&mut app));
+    }
+    touch(&mut app, 0, TouchPhase::Ended, tile_position(3, 3));
+    app.update();
+    marks.extend(drain::<TileMarkEvent>(&mut app));
+
+    assert_eq!(coordinates(&marks, |e| e.0), vec![Coordinates::new(3, 3)]);
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn long_press_threshold_is_configurable() {
+    let mut app = test_app(TouchOptions {
+        long_press_duration: 60.0,
+        ..TouchOptions::default()
+    });
+    touch(&mut app, 0, TouchPhase::Started, tile_position(3, 3));
+    app.update();
+    app.update();
+    assert!(drain::<TileMarkEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn moved_press_is_not_a_tap() {
+    let mut app = test_app(TouchOptions::default());
+    touch(&mut app, 0, TouchPhase::Started, tile_position(1, 1));
+    app.update();
+    touch(&mut app, 0, TouchPhase::Moved, tile_position(2, 1));
+    app.update();
+    touch(&mut app, 0, TouchPhase::Ended, tile_position(2, 1));
+    app.update();
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn tap_outside_board_is_ignored() {
+    let mut app = test_app(TouchOptions::default());
+    touch(&mut app, 0, TouchPhase::Started, Vec2::new(10.0, 10.0));
+    app.update();
+    touch(&mut app, 0, TouchPhase::Ended, Vec2::new(10.0, 10.0));
+    app.update();
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+    assert!(drain::<TileChordEvent>(&mut app).is_empty());
+}
+
+#[test]
+fn pinch_zooms_without_revealing() {
+    let mut app = test_app(TouchOptions::default());
+    let centre = WINDOW_SIZE * 0.5;
+    touch(&mut app, 0, TouchPhase::Started, centre - Vec2::X * 50.0);
+    touch(&mut app, 1, TouchPhase::Started, centre + Vec2::X * 50.0);
+    app.update();
+    touch(&mut app, 0, TouchPhase::Moved, centre - Vec2::X * 100.0);
+    touch(&mut app, 1, TouchPhase::Moved, centre + Vec2::X * 100.0);
+    app.update();
+    touch(&mut app, 0, TouchPhase::Ended, centre - Vec2::X * 100.0);
+    touch(&mut app, 1, TouchPhase::Ended, centre + Vec2::X * 100.0);
+    app.update();
+
+    let mut projections = app
+        .world
+        .query_filtered::<&OrthographicProjection, With<Camera2d>>();
+    let scale = projections.iter(&app.world).next().unwrap().scale;
+    assert!((scale - 0.5).abs() < 1e-5, "unexpected scale {}", scale);
+    assert!(drain::<TileTriggerEvent>(&mut app).is_empty());
+    assert!(drain::<TileMarkEvent>(&mut app).is_empty());
+}
@@ -0,0 +1,402 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::{
+    app::AppExit,
+    input::InputPlugin,
+    log::{Level, LogPlugin, LogSettings},
+    window::WindowPlugin,
+};
+use bevy::{log, prelude::*, window::WindowMode};
+
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::WorldInspectorPlugin;
+
+#[cfg(target_arch = "wasm32")]
+use board_plugin::{bridge::BridgePlugin, resources::GameConfigError};
+use board_plugin::{
+    endless::EndlessBoardPlugin,
+    events::BoardOptionsErrorEvent,
+    hud::HudPlugin,
+    menu::MenuPlugin,
+    resources::{
+        BoardAssets, BoardOptions, BoardPreset, BoardThemes, EndlessOptions, GameConfig, GameMode,
+    },
+    theme::ThemePlugin,
+    BoardPlugin,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use board_plugin::{
+    replay::ReplayPlugin,
+    resources::{
+        AnimationOptions, Board, BoardSave, BoardStatus, Move, ReplayPlayer, ReplayRecorder,
+    },
+};
+#[cfg(not(target_arch = "wasm32"))]
+use fw::cli;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod tui;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AppState {
+    Loading,
+    Menu,
+    InGame,
+    Endless,
+    Out,
+}
+
+fn main() {
+    // Board settings from the page URL, logged once the logger is set up
+    #[cfg(target_arch = "wasm32")]
+    let mut app = {
+        let (config, errors) = GameConfig::from_query(&web::query());
+        let app = window_app(config, false);
+        log_config_errors(&errors);
+        if let Err(e) = web::listen_touches() {
+            log::error!("Failed to listen to the canvas touches: {:?}", e);
+        }
+        app
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut app = {
+        let cli = cli::Cli::from_env();
+        if cli.tui {
+            if let Err(e) = tui::run(native_app(cli)) {
+                eprintln!("error: terminal: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        native_app(cli)
+    };
+
+    app.run();
+}
+
+/// App from the command line options, exiting if the save or replay can't be read
+#[cfg(not(target_arch = "wasm32"))]
+fn native_app(mut cli: cli::Cli) -> App {
+    let save = cli.read_save().unwrap_or_else(|e| cli::exit_with(&e));
+    let replay = cli.read_replay().unwrap_or_else(|e| cli::exit_with(&e));
+    // Loaded games start straight away on a board of their size
+    if let Some(board) = save.as_ref().or_else(|| replay.as_ref().map(|r| &r.board)) {
+        cli.config.width = Some(board.map_size.0);
+        cli.config.height = Some(board.map_size.1);
+        cli.config.mines = Some(board.bombs.len().min(u16::MAX.into()) as u16);
+        cli.config.mode = Some(GameMode::Classic);
+    }
+    // Undo and redo moves are played back under the recorded policy
+    if let Some(replay) = replay.as_ref() {
+        cli.config.undo = replay.undo;
+    }
+
+    let mut app = if cli.headless {
+        let mut app = headless_app(cli.config);
+        // Only the outcome is printed, along warnings
+        app.insert_resource(LogSettings {
+            level: Level::WARN,
+            ..default()
+        })
+        .add_plugin(LogPlugin)
+        .add_system(headless_exit);
+        app
+    } else if cli.tui {
+        // The terminal front-end draws the board from its own loop
+        headless_app(cli.config)
+    } else {
+        window_app(cli.config, cli.fullscreen)
+    };
+    app.add_plugin(ReplayPlugin {
+        running_state: AppState::InGame,
+    });
+    if let Some(save) = save {
+        app.insert_resource(save);
+    }
+    if let Some(replay) = replay {
+        // Headless replays are played as fast as possible
+        app.insert_resource(ReplayPlayer::new(&replay, !cli.headless))
+            .insert_resource(replay.board);
+    }
+    if let Some(path) = cli.record {
+        app.insert_resource(RecordPath(path))
+            .add_system(save_recording);
+    }
+    app
+}
+
+/// Windowed app, in borderless fullscreen if requested
+fn window_app(config: GameConfig, fullscreen: bool) -> App {
+    let mut app = App::new();
+    app.insert_resource(WindowDescriptor {
+        title: "SimRPG!".to_string(),
+        width: 1200.,
+        height: 800.,
+        mode: if fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        },
+        #[cfg(target_arch = "wasm32")]
+        canvas: Some(web::CANVAS.to_string()),
+        ..default()
+    });
+    // Theme packs are reloaded when edited on disk
+    #[cfg(all(feature = "hot_reload", not(target_arch = "wasm32")))]
+    app.insert_resource(bevy::asset::AssetServerSettings {
+        watch_for_changes: true,
+        ..default()
+    });
+    app.add_plugins(DefaultPlugins);
+    app.insert_resource(config);
+    #[cfg(feature = "debug")]
+    app.add_plugin(WorldInspectorPlugin::new());
+    app.add_startup_system(camera_setup);
+    app.add_startup_system(setup_board)
+        .add_state(AppState::Loading)
+        .add_plugin(ThemePlugin)
+        .add_plugin(BoardPlugin {
+            running_state: AppState::InGame,
+        })
+        .add_plugin(MenuPlugin {
+            menu_state: AppState::Menu,
+            game_state: AppState::InGame,
+        })
+        .add_plugin(HudPlugin {
+            running_state: AppState::InGame,
+        })
+        .add_plugin(EndlessBoardPlugin {
+            running_state: AppState::Endless,
+        })
+        .add_system(bevy::input::system::exit_on_esc_system)
+        .add_system_set(SystemSet::on_update(AppState::Loading).with_system(theme_loaded))
+        .add_system(state_handler)
+        .add_system(options_error_handler);
+    // The host page drives the game through the exported JavaScript API
+    #[cfg(target_arch = "wasm32")]
+    app.insert_resource(web::bridge())
+        .add_plugin(BridgePlugin {
+            running_state: AppState::InGame,
+        })
+        .add_system(web::dispatch_callbacks)
+        .add_system_to_stage(CoreStage::First, web::forward_touches);
+    app
+}
+
+/// Classic game without a window nor rendering
+#[cfg(not(target_arch = "wasm32"))]
+fn headless_app(config: GameConfig) -> App {
+    let (options, preset) = board_options(&config);
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin {
+            add_primary_window: false,
+            ..default()
+        })
+        .add_plugin(InputPlugin)
+        .insert_resource(config)
+        .insert_resource(options)
+        .insert_resource(preset)
+        .insert_resource(BoardAssets::default())
+        .insert_resource(AnimationOptions::disabled())
+        .add_state(AppState::InGame)
+        .add_plugin(BoardPlugin {
+            running_state: AppState::InGame,
+        });
+    app
+}
+
+fn camera_setup(mut commands: Commands) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
+}
+
+fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::C) {
+        log::debug!("clearing detected");
+        if state.current() != &AppState::Out {
+            log::info!("clearing game");
+            state.set(AppState::Out).unwrap();
+        }
+    }
+    if keys.just_pressed(KeyCode::R) {
+        log::debug!("reset detected");
+        if state.current() == &AppState::Out {
+            log::info!("loading game");
+            state.set(AppState::InGame).unwrap();
+        }
+    }
+    if keys.just_pressed(KeyCode::M) {
+        log::debug!("menu detected");
+        if state.current() != &AppState::Menu {
+            log::info!("opening menu");
+            state.set(AppState::Menu).unwrap();
+        }
+    }
+    if keys.just_pressed(KeyCode::E) {
+        log::debug!("endless detected");
+        if state.current() == &AppState::Out {
+            log::info!("loading endless game");
+            state.set(AppState::Endless).unwrap();
+        }
+    }
+}
+
+/// Goes back to the menu when the board options were refused
+fn options_error_handler(
+    mut state: ResMut<State<AppState>>,
+    mut options_error_reader: EventReader<BoardOptionsErrorEvent>,
+) {
+    for event in options_error_reader.iter() {
+        log::error!("Invalid board options: {}", event.0);
+        if state.current() != &AppState::Menu {
+            if let Err(e) = state.set(AppState::Menu) {
+                log::error!("Failed to open the menu: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn log_config_errors(errors: &[GameConfigError]) {
+    for e in errors {
+        log::warn!("Ignoring game setting: {}", e);
+    }
+}
+
+/// Board options and preset of the requested board
+fn board_options(config: &GameConfig) -> (BoardOptions, BoardPreset) {
+    let preset = config.preset().unwrap_or_default();
+    let mut options = BoardOptions {
+        tile_padding: 1.0,
+        safe_start: true,
+        question_marks: true,
+        ..default()
+    };
+    preset.apply(&mut options);
+    config.apply(&mut options);
+    (options, preset)
+}
+
+fn setup_board(mut commands: Commands, asset_server: Res<AssetServer>, config: Res<GameConfig>) {
+    // Board plugin options
+    let (options, preset) = board_options(&config);
+    commands.insert_resource(options);
+    commands.insert_resource(preset);
+    // Endless board plugin options
+    let mut endless_options = EndlessOptions::default();
+    config.apply_endless(&mut endless_options);
+    commands.insert_resource(endless_options);
+    // Board assets, from the first theme pack
+    commands.insert_resource(BoardThemes::load(
+        &asset_server,
+        &["themes/default.theme.ron", "themes/retro.theme.ron"],
+    ));
+}
+
+/// Opens the menu once the board assets of the selected theme are loaded, or starts the game
+/// straight away if a board or mode was requested
+#[allow(clippy::needless_pass_by_value)]
+fn theme_loaded(
+    mut state: ResMut<State<AppState>>,
+    board_assets: Option<Res<BoardAssets>>,
+    config: Res<GameConfig>,
+) {
+    if board_assets.is_some() {
+        // Plugin activation
+        let target = match (config.mode, config.preset()) {
+            (Some(GameMode::Endless), _) => AppState::Endless,
+            (Some(GameMode::Classic), _) | (None, Some(_)) => AppState::InGame,
+            (None, None) => AppState::Menu,
+        };
+        state.set(target).unwrap();
+    }
+}
+
+/// File the game moves are recorded to
+#[cfg(not(target_arch = "wasm32"))]
+struct RecordPath(PathBuf);
+
+/// Writes the recorded moves once the game is over
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::needless_pass_by_value)]
+fn save_recording(
+    path: Res<RecordPath>,
+    recorder: Res<ReplayRecorder>,
+    status: Option<Res<BoardStatus>>,
+    mut saved: Local<bool>,
+) {
+    let status = match status {
+        Some(s) => s,
+        None => return,
+    };
+    if status.is_added() {
+        *saved = false;
+    }
+    if *saved || !status.is_over() {
+        return;
+    }
+    *saved = true;
+    if let Some(replay) = recorder.replay.as_ref() {
+        match cli::write_json(&path.0, replay) {
+            Ok(()) => log::info!("Recorded game to {}", path.0.display()),
+            Err(e) => log::error!("Failed to record the game: {}", e),
+        }
+    }
+}
+
+/// Frames for the last moves to be handled, the trigger, uncover and status updates each taking
+/// one
+#[cfg(not(target_arch = "wasm32"))]
+const SETTLE_FRAMES: u32 = 5;
+
+/// Prints the outcome and the board of the headless game and exits once it is over, or once the moves to
+/// replay were played, undo and redo moves going on past the end of the game
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::needless_pass_by_value)]
+fn headless_exit(
+    board: Option<Res<Board>>,
+    status: Option<Res<BoardStatus>>,
+    player: Option<Res<ReplayPlayer>>,
+    mut options_error_reader: EventReader<BoardOptionsErrorEvent>,
+    mut idle_frames: Local<u32>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Some(event) = options_error_reader.iter().next() {
+        eprintln!("error: invalid board: {}", event.0);
+        std::process::exit(1);
+    }
+    let (board, status) = match (board, status) {
+        (Some(b), Some(s)) => (b, s),
+        _ => return,
+    };
+    // Once over, the replay can only go on with an undo
+    let playing = match player {
+        None => false,
+        Some(p) if status.is_over() => p.peek().is_some_and(Move::is_history),
+        Some(p) => !p.is_finished(),
+    };
+    if playing {
+        *idle_frames = 0;
+        return;
+    }
+    *idle_frames += 1;
+    if *idle_frames < SETTLE_FRAMES {
+        return;
+    }
+    let tile_count = board.tile_map.width() * board.tile_map.height();
+    println!(
+        "{:?} after {:.1}s, {} of {} tiles uncovered, {} mines left",
+        status.state,
+        status.timer.elapsed_secs(),
+        tile_count - board.covered_tiles.len(),
+        tile_count,
+        board.remaining_bombs()
+    );
+    print!("{}", BoardSave::new(&board, &status).to_text());
+    exit.send(AppExit);
+}
@@ -0,0 +1,150 @@
+//! Terminal front-end, playing the board rules of a headless app with the keyboard or the mouse
+
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+use bevy::app::App;
+use board_plugin::{
+    components::Coordinates,
+    resources::{Board, BoardState, BoardStatus},
+};
+use crossterm::{
+    cursor,
+    event::{self, Event},
+    execute, queue,
+    style::{PrintStyledContent, Stylize},
+    terminal::{self, ClearType},
+};
+use fw::terminal::{apply, key_action, mouse_action, tile_cell, BoardView, HEADER};
+
+use crate::AppState;
+
+/// Time waited for input between two frames
+const FRAME: Duration = Duration::from_millis(30);
+const HELP: &str =
+    "arrows/hjkl move  space reveal  f flag  c chord  u/y undo/redo  r restart  q quit";
+
+/// Raw mode alternate screen with mouse capture, restored when dropped
+struct Terminal {
+    out: Stdout,
+}
+
+impl Terminal {
+    fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut out = io::stdout();
+        execute!(
+            out,
+            terminal::EnterAlternateScreen,
+            event::EnableMouseCapture,
+            cursor::Hide
+        )?;
+        Ok(Self { out })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Restoring is best effort, the terminal may already be gone
+        let _ = execute!(
+            self.out,
+            cursor::Show,
+            event::DisableMouseCapture,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays the classic game of a headless app in the terminal until the player quits
+///
+/// # Errors
+///
+/// Returns an error if the terminal can't be set up or written to
+pub fn run(mut app: App) -> io::Result<()> {
+    let mut terminal = Terminal::new()?;
+    let mut view = BoardView::default();
+    loop {
+        app.update();
+        draw(&mut terminal.out, &app, &mut view)?;
+        if !event::poll(FRAME)? {
+            continue;
+        }
+        // Every pending input is handled before the next frame
+        loop {
+            let action = match event::read()? {
+                Event::Key(key) => key_action(key),
+                Event::Mouse(mouse) => match app.world.get_resource::<Board>() {
+                    Some(board) => mouse_action(&view, board, mouse),
+                    None => Vec::new(),
+                },
+                Event::Resize(..) => {
+                    queue!(terminal.out, terminal::Clear(ClearType::All))?;
+                    Vec::new()
+                }
+                _ => Vec::new(),
+            };
+            for action in action {
+                if !apply::<AppState>(&mut app, &mut view, action) {
+                    return Ok(());
+                }
+            }
+            if !event::poll(Duration::ZERO)? {
+                break;
+            }
+        }
+    }
+}
+
+fn draw(out: &mut Stdout, app: &App, view: &mut BoardView) -> io::Result<()> {
+    let (board, status) = match (
+        app.world.get_resource::<Board>(),
+        app.world.get_resource::<BoardStatus>(),
+    ) {
+        (Some(b), Some(s)) => (b, s),
+        _ => return Ok(()),
+    };
+    let cursor = view.update(board, terminal::size()?);
+    let height = board.tile_map.height() as u16;
+
+    let state = match status.state {
+        BoardState::Playing => "Playing",
+        BoardState::Won => "You won! r to play again",
+        BoardState::Lost => "Boom! r to play again",
+    };
+    let status_line = format!(
+        "{} mines left  {:.0}s  {}",
+        board.remaining_bombs(),
+        status.timer.elapsed_secs(),
+        state
+    );
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        PrintStyledContent(status_line.bold()),
+        terminal::Clear(ClearType::UntilNewLine)
+    )?;
+    for row in 0..view.size.1 {
+        queue!(out, cursor::MoveTo(0, HEADER + row))?;
+        let y = height - 1 - (view.top + row);
+        for x in view.left..view.left + view.size.0 {
+            let coords = Coordinates::new(x, y);
+            let (glyph, colour) = tile_cell(board, status, coords);
+            let cell = format!("{} ", glyph).with(colour);
+            let cell = if coords == cursor {
+                cell.reverse()
+            } else {
+                cell
+            };
+            queue!(out, PrintStyledContent(cell))?;
+        }
+        queue!(out, terminal::Clear(ClearType::UntilNewLine))?;
+    }
+    queue!(
+        out,
+        cursor::MoveTo(0, HEADER + view.size.1),
+        PrintStyledContent(HELP.dark_grey()),
+        terminal::Clear(ClearType::FromCursorDown)
+    )?;
+    out.flush()
+}
@@ -0,0 +1,268 @@
+//! JavaScript API of the web build, exported through `wasm-bindgen`.
+//!
+//! The app keeps running in the browser event loop once started, so the host page talks to it
+//! through a `GameBridge` shared with the app: requests are handled on the next frame, and the
+//! state and notifications are published every frame.
+//!
+//! ```js
+//! import init, { GameOptions, startGame, gameState, onWin, onLoss, onReveal, saveGame, restoreGame } from "./wasm/main.js";
+//!
+//! await init().catch((e) => {
+//!     // Winit leaves `main` through an exception to hand over to the browser event loop
+//!     if (!e.message.startsWith("Using exceptions for control flow")) throw e;
+//! });
+//! onLoss(() => console.log("Boom after", gameState().elapsed, "s"));
+//! startGame(new GameOptions(30, 16, 99, 123));
+//! ```
+
+use std::cell::RefCell;
+
+use bevy::{input::touch::TouchInput, log, prelude::*};
+use board_plugin::resources::{BoardPreset, BoardState, BridgeEvent, GameBridge};
+use fw::page_touch::{touch_input, touch_phase, TOUCH_EVENTS};
+use js_sys::{Function, Uint16Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{AddEventListenerOptions, Element, Event, TouchEvent};
+
+/// Selector of the canvas the app draws to
+pub const CANVAS: &str = "#bevy";
+
+thread_local! {
+    static BRIDGE: GameBridge = GameBridge::default();
+    static CALLBACKS: RefCell<Callbacks> = RefCell::default();
+    /// Touches of the canvas not yet sent to the app
+    static TOUCHES: RefCell<Vec<TouchInput>> = RefCell::default();
+}
+
+/// Host page callbacks
+#[derive(Default)]
+struct Callbacks {
+    win: Vec<Function>,
+    loss: Vec<Function>,
+    reveal: Vec<Function>,
+}
+
+/// Bridge shared with the host page, to be inserted as a resource
+pub fn bridge() -> GameBridge {
+    BRIDGE.with(Clone::clone)
+}
+
+/// Query of the page URL, such as `?w=30&h=16&mines=99`, empty if it can't be read
+pub fn query() -> String {
+    web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default()
+}
+
+/// Listens to the touch events of the canvas, winit not forwarding them on the web. The browser
+/// doesn't turn the handled touches into mouse clicks nor open the context menu on long presses.
+pub fn listen_touches() -> Result<(), JsValue> {
+    let canvas = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?
+        .query_selector(CANVAS)?
+        .ok_or_else(|| JsValue::from_str("no canvas"))?;
+    let target = canvas.clone();
+    let on_touch = Closure::<dyn FnMut(TouchEvent)>::new(move |event: TouchEvent| {
+        event.prevent_default();
+        let phase = match touch_phase(&event.type_()) {
+            None => return,
+            Some(p) => p,
+        };
+        let rect = target.get_bounding_client_rect();
+        let canvas_min = Vec2::new(rect.left() as f32, rect.top() as f32);
+        let canvas_size = Vec2::new(rect.width() as f32, rect.height() as f32);
+        let touches = event.changed_touches();
+        TOUCHES.with(|queue| {
+            let mut queue = queue.borrow_mut();
+            for touch in (0..touches.length()).filter_map(|i| touches.get(i)) {
+                let client = Vec2::new(touch.client_x() as f32, touch.client_y() as f32);
+                queue.push(touch_input(
+                    phase,
+                    touch.identifier(),
+                    client,
+                    touch.force(),
+                    canvas_min,
+                    canvas_size,
+                ));
+            }
+        });
+    });
+    // Non passive listeners can prevent the default scrolling and zooming of the page
+    let mut options = AddEventListenerOptions::new();
+    options.passive(false);
+    for event in TOUCH_EVENTS {
+        canvas.add_event_listener_with_callback_and_add_event_listener_options(
+            event,
+            on_touch.as_ref().unchecked_ref(),
+            &options,
+        )?;
+    }
+    // Listening for the whole page life
+    on_touch.forget();
+    prevent_context_menu(&canvas)
+}
+
+fn prevent_context_menu(canvas: &Element) -> Result<(), JsValue> {
+    let on_menu = Closure::<dyn FnMut(Event)>::new(|event: Event| event.prevent_default());
+    canvas.add_event_listener_with_callback("contextmenu", on_menu.as_ref().unchecked_ref())?;
+    on_menu.forget();
+    Ok(())
+}
+
+/// Sends the canvas touches to the app, before the touch state is updated from them
+pub fn forward_touches(mut touch_ewr: EventWriter<TouchInput>) {
+    TOUCHES.with(|queue| touch_ewr.send_batch(queue.borrow_mut().drain(..)));
+}
+
+/// Outcome of the current game
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// Board of a new game
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub struct GameOptions {
+    pub width: u16,
+    pub height: u16,
+    pub mines: u16,
+    /// Seed of the bomb placement, the same seed always generates the same board
+    pub seed: Option<u32>,
+}
+
+#[wasm_bindgen]
+impl GameOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u16, height: u16, mines: u16, seed: Option<u32>) -> Self {
+        Self {
+            width,
+            height,
+            mines,
+            seed,
+        }
+    }
+}
+
+/// State of the current game
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub struct GameState {
+    pub status: GameStatus,
+    /// Mines minus flags, negative when too many tiles are flagged
+    #[wasm_bindgen(js_name = minesLeft)]
+    pub mines_left: i32,
+    /// Time spent playing, in seconds
+    pub elapsed: f32,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Starts a new game on the next frame, throws if the options can't generate a board
+#[wasm_bindgen(js_name = startGame)]
+pub fn start_game(options: &GameOptions) -> Result<(), JsValue> {
+    let preset = BoardPreset::Custom {
+        width: options.width,
+        height: options.height,
+        bomb_count: options.mines,
+    };
+    bridge()
+        .start(preset, options.seed.map(u64::from))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// State of the current game, `undefined` while no game is running
+#[wasm_bindgen(js_name = gameState)]
+pub fn game_state() -> Option<GameState> {
+    bridge().snapshot().map(|snapshot| GameState {
+        status: match snapshot.state {
+            BoardState::Playing => GameStatus::Playing,
+            BoardState::Won => GameStatus::Won,
+            BoardState::Lost => GameStatus::Lost,
+        },
+        mines_left: snapshot.mines_left.clamp(i32::MIN.into(), i32::MAX.into()) as i32,
+        elapsed: snapshot.elapsed,
+        width: snapshot.map_size.0,
+        height: snapshot.map_size.1,
+    })
+}
+
+/// Calls `callback()` when a game is won
+#[wasm_bindgen(js_name = onWin)]
+pub fn on_win(callback: Function) {
+    CALLBACKS.with(|c| c.borrow_mut().win.push(callback));
+}
+
+/// Calls `callback()` when a game is lost
+#[wasm_bindgen(js_name = onLoss)]
+pub fn on_loss(callback: Function) {
+    CALLBACKS.with(|c| c.borrow_mut().loss.push(callback));
+}
+
+/// Calls `callback(tiles)` when tiles are uncovered, `tiles` holding their `x, y` coordinates
+/// one after the other
+#[wasm_bindgen(js_name = onReveal)]
+pub fn on_reveal(callback: Function) {
+    CALLBACKS.with(|c| c.borrow_mut().reveal.push(callback));
+}
+
+/// Saves the current game as JSON, `undefined` while no game is running
+#[wasm_bindgen(js_name = saveGame)]
+pub fn save_game() -> Result<Option<String>, JsValue> {
+    bridge()
+        .save()
+        .map(|save| serde_json::to_string(&save))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Restores a game saved by `saveGame` on the next frame, throws if the save is invalid
+#[wasm_bindgen(js_name = restoreGame)]
+pub fn restore_game(save: &str) -> Result<(), JsValue> {
+    let save = serde_json::from_str(save).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    bridge()
+        .restore(save)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Calls the host page callbacks for the notifications published by the app
+#[allow(clippy::needless_pass_by_value)]
+pub fn dispatch_callbacks(bridge: Res<GameBridge>) {
+    let events = bridge.drain_events();
+    if events.is_empty() {
+        return;
+    }
+    CALLBACKS.with(|callbacks| {
+        let callbacks = callbacks.borrow();
+        for event in events {
+            let result = match event {
+                BridgeEvent::Won => call(&callbacks.win, None),
+                BridgeEvent::Lost => call(&callbacks.loss, None),
+                BridgeEvent::Revealed(tiles) => {
+                    let coords: Vec<u16> = tiles.iter().flat_map(|c| [c.x, c.y]).collect();
+                    call(
+                        &callbacks.reveal,
+                        Some(Uint16Array::from(&coords[..]).into()),
+                    )
+                }
+            };
+            if let Err(e) = result {
+                log::error!("Host callback failed: {:?}", e);
+            }
+        }
+    });
+}
+
+fn call(callbacks: &[Function], argument: Option<JsValue>) -> Result<(), JsValue> {
+    for callback in callbacks {
+        match argument {
+            None => callback.call0(&JsValue::NULL)?,
+            Some(ref a) => callback.call1(&JsValue::NULL, a)?,
+        };
+    }
+    Ok(())
+}
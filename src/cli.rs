@@ -0,0 +1,312 @@
+//! Command line options of the native build, kept apart from the process environment
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use board_plugin::resources::{
+    BoardSave, BoardTextError, GameConfig, GameConfigError, GameMode, Replay,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Command line help
+pub const USAGE: &str = "\
+Usage: fw [OPTIONS]
+
+Board options, skipping the menu:
+  --width <TILES>     Board width, up to 100 tiles
+  --height <TILES>    Board height, up to 100 tiles
+  --mines <COUNT>     Bomb count
+  --preset <NAME>     Base board: beginner, intermediate or expert
+  --seed <SEED>       Seed of the bomb placement or of the endless world
+  --mode <MODE>       Game mode: classic or endless, noguess falling back to
+                      classic as no-guess boards are not generated yet
+
+Game options:
+  --load <SAVE>       Restores a game saved by the web build as JSON, or a
+                      board written as a .txt text grid
+  --replay <FILE>     Plays back a game recorded with --record
+  --record <FILE>     Records the game moves once the game is over
+  --undo <POLICY>     Moves that can be taken back with U and replayed with
+                      Y: unlimited, flags since the last reveal, or death
+                      for the losing move once, adding 30 seconds
+
+Display options:
+  --fullscreen        Starts in borderless fullscreen
+  --headless          Runs without a window, printing the game outcome and
+                      the final board as a text grid
+  --tui               Plays in the terminal, with the keyboard or the mouse
+  -h, --help          Prints this help
+
+Values are given as `--width 30` or `--width=30`.";
+
+/// Reason why the command line was refused
+#[derive(Debug)]
+pub enum CliError {
+    /// The flag doesn't exist
+    UnknownFlag(String),
+    /// The flag expects a value
+    MissingValue(&'static str),
+    /// The flag doesn't take a value
+    UnexpectedValue(&'static str),
+    /// Positional arguments are not supported
+    UnexpectedArgument(String),
+    /// Both flags can't be given together
+    Conflict(&'static str, &'static str),
+    /// A board setting was refused
+    Config(GameConfigError),
+    /// A file can't be read or written
+    Io(PathBuf, std::io::Error),
+    /// A file is not a valid save or replay
+    Format(PathBuf, serde_json::Error),
+    /// A text file is not a valid board
+    Text(PathBuf, BoardTextError),
+}
+
+impl Display for CliError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnknownFlag(ref flag) => write!(f, "unknown option \"{}\"", flag),
+            Self::MissingValue(flag) => write!(f, "missing value for \"--{}\"", flag),
+            Self::UnexpectedValue(flag) => write!(f, "\"--{}\" doesn't take a value", flag),
+            Self::UnexpectedArgument(ref arg) => write!(f, "unexpected argument \"{}\"", arg),
+            Self::Conflict(a, b) => write!(f, "\"--{}\" can't be used with \"--{}\"", a, b),
+            Self::Config(ref e) => write!(f, "{}", e),
+            Self::Io(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+            Self::Format(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+            Self::Text(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for CliError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            Self::Config(ref e) => Some(e),
+            Self::Io(_, ref e) => Some(e),
+            Self::Format(_, ref e) => Some(e),
+            Self::Text(_, ref e) => Some(e),
+            Self::UnknownFlag(_)
+            | Self::MissingValue(_)
+            | Self::UnexpectedValue(_)
+            | Self::UnexpectedArgument(_)
+            | Self::Conflict(..) => None,
+        }
+    }
+}
+
+/// Native command line options
+#[derive(Debug, Clone, Default)]
+pub struct Cli {
+    /// Game settings, shared with the web build URL query.
+    pub config: GameConfig,
+    /// Save to restore.
+    pub load: Option<PathBuf>,
+    /// Replay to play back.
+    pub replay: Option<PathBuf>,
+    /// File the game moves are recorded to.
+    pub record: Option<PathBuf>,
+    /// Borderless fullscreen window.
+    pub fullscreen: bool,
+    /// No window, the outcome is printed.
+    pub headless: bool,
+    /// No window, the game is played in the terminal.
+    pub tui: bool,
+    /// Settings replaced by their fallback, printed as warnings.
+    pub warnings: Vec<GameConfigError>,
+}
+
+/// Flags taking a value, the game settings being named as in the web build URL query
+const VALUE_FLAGS: [&str; 10] = [
+    "width", "height", "mines", "preset", "seed", "mode", "undo", "load", "replay", "record",
+];
+
+impl Cli {
+    /// Parses the command line, exiting with the help or an error message when asked or refused,
+    /// and printing the warnings
+    #[inline]
+    #[must_use]
+    pub fn from_env() -> Self {
+        match Self::parse(std::env::args().skip(1)) {
+            Ok(Some(cli)) => {
+                for warning in &cli.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+                cli
+            }
+            Ok(None) => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            Err(e) => exit_with(&e),
+        }
+    }
+
+    /// Parses the command line arguments, `None` if the help was requested
+    ///
+    /// # Errors
+    ///
+    /// Returns the first unknown flag, missing or invalid value and conflicting flags
+    #[inline]
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Self>, CliError> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "--help" {
+                return Ok(None);
+            }
+            let flag = match arg.strip_prefix("--") {
+                Some(f) => f,
+                None => return Err(CliError::UnexpectedArgument(arg)),
+            };
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            match VALUE_FLAGS.iter().find(|&&f| f == name) {
+                Some(&name) => {
+                    let value = match inline_value {
+                        Some(v) => v,
+                        None => match args.next() {
+                            Some(v) if !v.starts_with("--") => v,
+                            _ => return Err(CliError::MissingValue(name)),
+                        },
+                    };
+                    cli.set(name, &value)?;
+                }
+                None => {
+                    let (name, switch) = match name {
+                        "fullscreen" => ("fullscreen", &mut cli.fullscreen),
+                        "headless" => ("headless", &mut cli.headless),
+                        "tui" => ("tui", &mut cli.tui),
+                        _ => return Err(CliError::UnknownFlag(arg)),
+                    };
+                    if inline_value.is_some() {
+                        return Err(CliError::UnexpectedValue(name));
+                    }
+                    *switch = true;
+                }
+            }
+        }
+        cli.validate()?;
+        Ok(Some(cli))
+    }
+
+    fn set(&mut self, name: &'static str, value: &str) -> Result<(), CliError> {
+        match name {
+            "load" => self.load = Some(value.into()),
+            "replay" => self.replay = Some(value.into()),
+            "record" => self.record = Some(value.into()),
+            _ => match self.config.set(name, value) {
+                // Unsupported values were replaced by their fallback
+                Err(e @ GameConfigError::Unsupported { .. }) => self.warnings.push(e),
+                result => result.map_err(CliError::Config)?,
+            },
+        }
+        Ok(())
+    }
+
+    /// Checks the options can be used together and the board can be generated
+    fn validate(&self) -> Result<(), CliError> {
+        let game = match (&self.load, &self.replay) {
+            (Some(_), Some(_)) => return Err(CliError::Conflict("load", "replay")),
+            (Some(_), None) => Some("load"),
+            (None, Some(_)) => Some("replay"),
+            (None, None) => None,
+        };
+        if let Some(game) = game {
+            let board_setting = [
+                ("width", self.config.width.is_some()),
+                ("height", self.config.height.is_some()),
+                ("mines", self.config.mines.is_some()),
+                ("preset", self.config.preset.is_some()),
+                ("seed", self.config.seed.is_some()),
+                ("mode", self.config.mode == Some(GameMode::Endless)),
+            ]
+            .into_iter()
+            .find(|&(_, set)| set);
+            if let Some((setting, _)) = board_setting {
+                return Err(CliError::Conflict(game, setting));
+            }
+        }
+        // Replays are played back under their recorded undo policy
+        if self.replay.is_some() && self.config.undo.is_some() {
+            return Err(CliError::Conflict("replay", "undo"));
+        }
+        // Both run the classic board rules without a window
+        let windowless = match (self.headless, self.tui) {
+            (true, true) => return Err(CliError::Conflict("headless", "tui")),
+            (true, false) => Some("headless"),
+            (false, true) => Some("tui"),
+            (false, false) => None,
+        };
+        if let Some(windowless) = windowless {
+            if self.fullscreen {
+                return Err(CliError::Conflict(windowless, "fullscreen"));
+            }
+            if self.config.mode == Some(GameMode::Endless) {
+                return Err(CliError::Conflict(windowless, "mode endless"));
+            }
+        }
+        self.config.validate().map_err(CliError::Config)
+    }
+
+    /// Reads the `--load` save, a text board if the file has the `txt` extension
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or is not a save
+    #[inline]
+    pub fn read_save(&self) -> Result<Option<BoardSave>, CliError> {
+        let path = match self.load.as_deref() {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        if path.extension().is_some_and(|e| e == "txt") {
+            let text = fs::read_to_string(path).map_err(|e| CliError::Io(path.to_path_buf(), e))?;
+            return BoardSave::from_text(&text)
+                .map(Some)
+                .map_err(|e| CliError::Text(path.to_path_buf(), e));
+        }
+        read_json(path).map(Some)
+    }
+
+    /// Reads the `--replay` replay
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or is not a replay
+    #[inline]
+    pub fn read_replay(&self) -> Result<Option<Replay>, CliError> {
+        self.replay.as_deref().map(read_json).transpose()
+    }
+}
+
+/// Prints the error with a pointer to the help and exits
+#[inline]
+pub fn exit_with(error: &CliError) -> ! {
+    eprintln!(
+        "error: {}\n\nRun with --help for the list of options.",
+        error
+    );
+    std::process::exit(2);
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, CliError> {
+    let json = fs::read_to_string(path).map_err(|e| CliError::Io(path.to_path_buf(), e))?;
+    serde_json::from_str(&json).map_err(|e| CliError::Format(path.to_path_buf(), e))
+}
+
+/// Writes a save or a replay as JSON
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written
+#[inline]
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), CliError> {
+    let json = serde_json::to_string(value).map_err(|e| CliError::Format(path.to_path_buf(), e))?;
+    fs::write(path, json).map_err(|e| CliError::Io(path.to_path_buf(), e))
+}
@@ -39,3 +39,9 @@
     clippy::unreachable,
     clippy::unwrap_used
 )]
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+pub mod page_touch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod terminal;
@@ -0,0 +1,43 @@
+//! Touch points of the web page, read by the web build as winit doesn't forward them there
+
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+use bevy::math::Vec2;
+
+/// Page touch events listened to on the canvas
+pub const TOUCH_EVENTS: [&str; 4] = ["touchstart", "touchmove", "touchend", "touchcancel"];
+
+/// Phase of a page touch event, `None` for other events
+#[inline]
+#[must_use]
+pub fn touch_phase(event_type: &str) -> Option<TouchPhase> {
+    match event_type {
+        "touchstart" => Some(TouchPhase::Started),
+        "touchmove" => Some(TouchPhase::Moved),
+        "touchend" => Some(TouchPhase::Ended),
+        "touchcancel" => Some(TouchPhase::Cancelled),
+        _ => None,
+    }
+}
+
+/// Touch input of a page touch point, `client` being its position in the page viewport and
+/// `canvas_min` and `canvas_size` the canvas bounds in the viewport, all in CSS pixels. Positions
+/// start from the bottom left corner of the canvas, as cursor positions do, and a zero force
+/// means the browser doesn't measure it.
+#[inline]
+#[must_use]
+pub fn touch_input(
+    phase: TouchPhase,
+    id: i32,
+    client: Vec2,
+    force: f32,
+    canvas_min: Vec2,
+    canvas_size: Vec2,
+) -> TouchInput {
+    let offset = client - canvas_min;
+    TouchInput {
+        phase,
+        position: Vec2::new(offset.x, canvas_size.y - offset.y),
+        force: (force > 0.0).then(|| ForceTouch::Normalized(f64::from(force))),
+        id: id.unsigned_abs().into(),
+    }
+}
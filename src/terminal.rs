@@ -0,0 +1,248 @@
+//! Board view and input mapping of the terminal front-end, kept apart from the terminal I/O
+
+use bevy::{app::App, ecs::event::Events, ecs::schedule::StateData, prelude::State};
+use board_plugin::{
+    components::Coordinates,
+    events::{RedoEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent, UndoEvent},
+    resources::{Board, BoardState, BoardStatus, Mark, Tile},
+};
+use crossterm::{
+    event::{
+        KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
+    style::Color,
+};
+
+/// Terminal columns per tile
+pub const TILE_WIDTH: u16 = 2;
+/// Terminal rows above the board, for the status line
+pub const HEADER: u16 = 1;
+/// Terminal rows below the board, for the help line
+pub const FOOTER: u16 = 1;
+
+/// Player action read from the terminal
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Moves the cursor by a tile offset
+    Move(i32, i32),
+    /// Moves the cursor to a tile
+    Point(Coordinates),
+    Trigger,
+    Mark,
+    Chord,
+    /// Takes back the last move, when the board has an undo policy
+    Undo,
+    Redo,
+    Restart,
+    Quit,
+}
+
+/// Cursor and scrolling of the board view
+#[derive(Debug, Default)]
+pub struct BoardView {
+    /// Selected tile, `None` until a board is drawn.
+    pub cursor: Option<Coordinates>,
+    /// First visible column, in tiles.
+    pub left: u16,
+    /// First visible row from the top of the board, in tiles.
+    pub top: u16,
+    /// Visible columns and rows, in tiles.
+    pub size: (u16, u16),
+}
+
+impl BoardView {
+    /// Fits the view in a terminal of `columns` by `rows` cells, scrolled to keep the cursor
+    /// visible
+    #[inline]
+    pub fn update(&mut self, board: &Board, (columns, rows): (u16, u16)) -> Coordinates {
+        let (width, height) = board_size(board);
+        let cursor = match self.cursor {
+            Some(c) if c.x < width && c.y < height => c,
+            _ => Coordinates::new(width / 2, height / 2),
+        };
+        self.cursor = Some(cursor);
+        self.size = (
+            (columns / TILE_WIDTH).min(width),
+            rows.saturating_sub(HEADER + FOOTER).min(height),
+        );
+        let row = height - 1 - cursor.y;
+        self.left = scroll(self.left, cursor.x, self.size.0);
+        self.top = scroll(self.top, row, self.size.1);
+        cursor
+    }
+
+    /// Tile under a terminal cell
+    #[inline]
+    #[must_use]
+    pub fn tile_at(&self, board: &Board, column: u16, row: u16) -> Option<Coordinates> {
+        let (column, row) = (column / TILE_WIDTH, row.checked_sub(HEADER)?);
+        if column >= self.size.0 || row >= self.size.1 {
+            return None;
+        }
+        let height = board_size(board).1;
+        Some(Coordinates::new(
+            self.left + column,
+            height - 1 - (self.top + row),
+        ))
+    }
+
+    /// Applies a cursor action, returning the event target of the others
+    #[inline]
+    pub fn apply(&mut self, board: &Board, action: Action) -> Option<Coordinates> {
+        let (width, height) = board_size(board);
+        let cursor = self.cursor?;
+        match action {
+            Action::Move(dx, dy) => {
+                let clamp = |v: u16, d: i32, max: u16| {
+                    (i32::from(v) + d).clamp(0, i32::from(max) - 1) as u16
+                };
+                self.cursor = Some(Coordinates::new(
+                    clamp(cursor.x, dx, width),
+                    clamp(cursor.y, dy, height),
+                ));
+                None
+            }
+            Action::Point(coords) => {
+                self.cursor = Some(coords);
+                None
+            }
+            Action::Trigger | Action::Mark | Action::Chord => Some(cursor),
+            Action::Undo | Action::Redo | Action::Restart | Action::Quit => None,
+        }
+    }
+}
+
+/// First visible index keeping `index` in a view of `size` items
+fn scroll(first: u16, index: u16, size: u16) -> u16 {
+    if size == 0 || index < first {
+        index
+    } else if index >= first + size {
+        index + 1 - size
+    } else {
+        first
+    }
+}
+
+fn board_size(board: &Board) -> (u16, u16) {
+    (
+        board.tile_map.width() as u16,
+        board.tile_map.height() as u16,
+    )
+}
+
+/// Action of a key press, if any
+#[inline]
+#[must_use]
+pub fn key_action(key: KeyEvent) -> Vec<Action> {
+    if key.kind == KeyEventKind::Release {
+        return Vec::new();
+    }
+    let action = match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        KeyCode::Up | KeyCode::Char('k') => Action::Move(0, 1),
+        KeyCode::Down | KeyCode::Char('j') => Action::Move(0, -1),
+        KeyCode::Left | KeyCode::Char('h') => Action::Move(-1, 0),
+        KeyCode::Right | KeyCode::Char('l') => Action::Move(1, 0),
+        KeyCode::Char(' ') | KeyCode::Enter => Action::Trigger,
+        KeyCode::Char('f') => Action::Mark,
+        KeyCode::Char('c') => Action::Chord,
+        KeyCode::Char('u') => Action::Undo,
+        KeyCode::Char('y') => Action::Redo,
+        KeyCode::Char('r') => Action::Restart,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => return Vec::new(),
+    };
+    vec![action]
+}
+
+/// Hovering moves the cursor, clicks also reveal, flag or chord the tile
+#[inline]
+#[must_use]
+pub fn mouse_action(view: &BoardView, board: &Board, mouse: MouseEvent) -> Vec<Action> {
+    let coords = match view.tile_at(board, mouse.column, mouse.row) {
+        Some(c) => c,
+        None => return Vec::new(),
+    };
+    let action = match mouse.kind {
+        MouseEventKind::Moved => return vec![Action::Point(coords)],
+        MouseEventKind::Down(MouseButton::Left) => Action::Trigger,
+        MouseEventKind::Down(MouseButton::Right) => Action::Mark,
+        MouseEventKind::Down(MouseButton::Middle) => Action::Chord,
+        _ => return Vec::new(),
+    };
+    vec![Action::Point(coords), action]
+}
+
+/// Applies an action to the app, restarting the `S` state of the board, `false` to quit. Tile
+/// actions are ignored once the game is over.
+#[inline]
+pub fn apply<S: StateData>(app: &mut App, view: &mut BoardView, action: Action) -> bool {
+    match action {
+        Action::Quit => return false,
+        Action::Undo => send(app, UndoEvent),
+        Action::Redo => send(app, RedoEvent),
+        Action::Restart => {
+            if let Some(mut state) = app.world.get_resource_mut::<State<S>>() {
+                // Refused only while a transition is queued, the key can be pressed again
+                let _ = state.restart();
+            }
+        }
+        _ => {
+            let target = match app.world.get_resource::<Board>() {
+                Some(board) => view.apply(board, action),
+                None => None,
+            };
+            // Only undo and redo act once the game is over
+            let over = app
+                .world
+                .get_resource::<BoardStatus>()
+                .is_some_and(BoardStatus::is_over);
+            match (action, target.filter(|_| !over)) {
+                (Action::Trigger, Some(coords)) => send(app, TileTriggerEvent(coords)),
+                (Action::Mark, Some(coords)) => send(app, TileMarkEvent(coords)),
+                (Action::Chord, Some(coords)) => send(app, TileChordEvent(coords)),
+                _ => (),
+            }
+        }
+    }
+    true
+}
+
+fn send<E: Send + Sync + 'static>(app: &mut App, event: E) {
+    if let Some(mut events) = app.world.get_resource_mut::<Events<E>>() {
+        events.send(event);
+    }
+}
+
+/// Character and colour of a tile
+#[inline]
+#[must_use]
+pub fn tile_cell(board: &Board, status: &BoardStatus, coords: Coordinates) -> (char, Color) {
+    let tile = board.tile_map.map()[(coords.x as usize, coords.y as usize)];
+    if board.covered_tiles.contains_key(&coords) {
+        return match board.marked_tiles.get(&coords) {
+            Some(Mark::Flag) => ('F', Color::Red),
+            Some(Mark::Question) => ('?', Color::Yellow),
+            // Bombs are shown once the game is lost
+            None if status.state == BoardState::Lost && tile.is_bomb() => ('*', Color::DarkRed),
+            None => ('#', Color::DarkGrey),
+        };
+    }
+    match tile {
+        Tile::Bomb => ('*', Color::Red),
+        Tile::BombNeighbor(count) => (
+            char::from_digit(count.into(), 10).unwrap_or('?'),
+            match count {
+                1 => Color::Blue,
+                2 => Color::Green,
+                3 => Color::Red,
+                4 => Color::DarkBlue,
+                5 => Color::DarkRed,
+                6 => Color::Cyan,
+                7 => Color::Magenta,
+                _ => Color::Grey,
+            },
+        ),
+        Tile::Empty => ('.', Color::DarkGrey),
+    }
+}
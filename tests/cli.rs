@@ -0,0 +1,161 @@
+//! Command line parsing of the native build: value syntax, refused flags and values, conflicting
+//! options and settings falling back with a warning
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use board_plugin::resources::{
+    BoardOptionsError, BoardPreset, GameConfig, GameConfigError, GameMode,
+};
+use fw::cli::{Cli, CliError};
+
+fn parse(args: &[&str]) -> Result<Option<Cli>, CliError> {
+    Cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+fn parsed(args: &[&str]) -> Cli {
+    parse(args).unwrap().unwrap()
+}
+
+fn refused(args: &[&str]) -> CliError {
+    parse(args).unwrap_err()
+}
+
+#[test]
+fn values_follow_the_flag_or_an_equal_sign() {
+    let spaced = parsed(&[
+        "--width", "30", "--height", "16", "--mines", "99", "--seed", "7",
+    ]);
+    let inline = parsed(&["--width=30", "--height=16", "--mines=99", "--seed=7"]);
+    assert_eq!(spaced.config, inline.config);
+    assert_eq!(
+        inline.config.preset(),
+        Some(BoardPreset::Custom {
+            width: 30,
+            height: 16,
+            bomb_count: 99
+        })
+    );
+    assert_eq!(inline.config.seed, Some(7));
+    assert!(inline.warnings.is_empty());
+
+    let cli = parsed(&["--load=game.txt", "--record", "moves.json", "--fullscreen"]);
+    assert_eq!(cli.load.unwrap().to_str(), Some("game.txt"));
+    assert_eq!(cli.record.unwrap().to_str(), Some("moves.json"));
+    assert!(cli.fullscreen);
+}
+
+#[test]
+fn no_arguments_keep_the_defaults() {
+    let cli = parsed(&[]);
+    assert_eq!(cli.config, GameConfig::default());
+    assert!(!cli.fullscreen && !cli.headless && !cli.tui);
+    assert!(cli.load.is_none() && cli.replay.is_none() && cli.record.is_none());
+}
+
+#[test]
+fn help_is_requested_before_any_error() {
+    assert!(parse(&["--help"]).unwrap().is_none());
+    assert!(parse(&["--width=0", "-h", "--unknown"]).unwrap().is_none());
+}
+
+#[test]
+fn missing_values_are_refused() {
+    assert!(matches!(
+        refused(&["--width"]),
+        CliError::MissingValue("width")
+    ));
+    // A following flag is not taken as the value
+    assert!(matches!(
+        refused(&["--mines", "--tui"]),
+        CliError::MissingValue("mines")
+    ));
+}
+
+#[test]
+fn unknown_flags_and_arguments_are_refused() {
+    assert!(matches!(
+        refused(&["--colour=red"]),
+        CliError::UnknownFlag(flag) if flag == "--colour=red"
+    ));
+    assert!(matches!(
+        refused(&["-w", "30"]),
+        CliError::UnexpectedArgument(arg) if arg == "-w"
+    ));
+    assert!(matches!(
+        refused(&["--width", "30", "16"]),
+        CliError::UnexpectedArgument(arg) if arg == "16"
+    ));
+    assert!(matches!(
+        refused(&["--headless=yes"]),
+        CliError::UnexpectedValue("headless")
+    ));
+}
+
+#[test]
+fn invalid_values_are_refused() {
+    assert!(matches!(
+        refused(&["--width=wide"]),
+        CliError::Config(GameConfigError::InvalidValue { key, .. }) if key == "width"
+    ));
+    assert!(matches!(
+        refused(&["--mode", "arcade"]),
+        CliError::Config(GameConfigError::InvalidValue { key, .. }) if key == "mode"
+    ));
+}
+
+#[test]
+fn conflicting_options_are_refused() {
+    let conflict = |args: &[&str]| match refused(args) {
+        CliError::Conflict(a, b) => (a, b),
+        e => panic!("{:?} refused with {:?}", args, e),
+    };
+    assert_eq!(
+        conflict(&["--load", "game.json", "--replay", "moves.json"]),
+        ("load", "replay")
+    );
+    assert_eq!(
+        conflict(&["--load", "game.json", "--seed=3"]),
+        ("load", "seed")
+    );
+    assert_eq!(
+        conflict(&["--replay", "moves.json", "--undo", "unlimited"]),
+        ("replay", "undo")
+    );
+    assert_eq!(conflict(&["--headless", "--tui"]), ("headless", "tui"));
+    assert_eq!(
+        conflict(&["--headless", "--fullscreen"]),
+        ("headless", "fullscreen")
+    );
+    assert_eq!(
+        conflict(&["--tui", "--mode=endless"]),
+        ("tui", "mode endless")
+    );
+}
+
+#[test]
+fn invalid_boards_are_refused() {
+    assert!(matches!(
+        refused(&["--width=3", "--height=3", "--mines=9"]),
+        CliError::Config(GameConfigError::InvalidBoard(
+            BoardOptionsError::TooManyBombs { .. }
+        ))
+    ));
+    assert!(matches!(
+        refused(&["--width=101"]),
+        CliError::Config(GameConfigError::BoardTooLarge { width: 101, .. })
+    ));
+}
+
+#[test]
+fn no_guess_boards_fall_back_to_classic_with_a_warning() {
+    let cli = parsed(&["--mode=noguess", "--headless"]);
+    assert_eq!(cli.config.mode, Some(GameMode::Classic));
+    assert_eq!(
+        cli.warnings,
+        [GameConfigError::Unsupported {
+            key: "mode".to_string(),
+            value: "noguess".to_string(),
+            fallback: "classic",
+        }]
+    );
+}
@@ -0,0 +1,43 @@
+//! Page touch points converted to the touch input of the app, as forwarded by the web build
+
+use bevy::input::touch::{ForceTouch, TouchPhase};
+use bevy::math::Vec2;
+use fw::page_touch::{touch_input, touch_phase, TOUCH_EVENTS};
+
+#[test]
+fn every_listened_event_has_a_phase() {
+    let phases: Vec<_> = TOUCH_EVENTS.iter().map(|e| touch_phase(e)).collect();
+    assert_eq!(
+        phases,
+        [
+            Some(TouchPhase::Started),
+            Some(TouchPhase::Moved),
+            Some(TouchPhase::Ended),
+            Some(TouchPhase::Cancelled),
+        ]
+    );
+    assert_eq!(touch_phase("click"), None);
+}
+
+#[test]
+fn positions_start_from_the_bottom_left_of_the_canvas() {
+    // Canvas of 400x300 pixels, 10 pixels right and 20 pixels down the viewport
+    let (canvas_min, canvas_size) = (Vec2::new(10.0, 20.0), Vec2::new(400.0, 300.0));
+    let touch = |client: Vec2| {
+        touch_input(TouchPhase::Started, 3, client, 0.0, canvas_min, canvas_size).position
+    };
+    assert_eq!(touch(Vec2::new(10.0, 320.0)), Vec2::ZERO);
+    assert_eq!(touch(Vec2::new(410.0, 20.0)), canvas_size);
+    assert_eq!(touch(Vec2::new(60.0, 70.0)), Vec2::new(50.0, 250.0));
+}
+
+#[test]
+fn ids_and_forces_are_kept() {
+    let input = touch_input(TouchPhase::Moved, 7, Vec2::ZERO, 0.5, Vec2::ZERO, Vec2::ONE);
+    assert_eq!(input.id, 7);
+    assert_eq!(input.phase, TouchPhase::Moved);
+    assert!(matches!(input.force, Some(ForceTouch::Normalized(f)) if f == 0.5));
+    // Browsers without pressure sensing report no force
+    let input = touch_input(TouchPhase::Ended, 0, Vec2::ZERO, 0.0, Vec2::ZERO, Vec2::ONE);
+    assert_eq!(input.force, None);
+}
@@ -0,0 +1,253 @@
+//! Scripted terminal play: key and mouse mapping, the scrolled board view, and actions applied to
+//! a headless board app, without a terminal
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bevy::ecs::event::Events;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::window::WindowPlugin;
+use board_plugin::{
+    bounds::Bounds,
+    components::Coordinates,
+    events::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resources::{
+        AnimationOptions, Board, BoardAssets, BoardOptions, BoardState, BoardStatus, Mark, TileMap,
+        UndoPolicy,
+    },
+    BoardPlugin,
+};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use fw::terminal::{apply, key_action, mouse_action, tile_cell, Action, BoardView, HEADER};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum TestState {
+    InGame,
+}
+
+/// Board of `width` by `height` tiles, all covered, without entities
+fn board(width: usize, height: usize) -> Board {
+    let mut covered_tiles = HashMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            covered_tiles.insert(Coordinates::new(x as u16, y as u16), Entity::from_raw(0));
+        }
+    }
+    Board::new(
+        Entity::from_raw(0),
+        TileMap::empty((width, height)),
+        covered_tiles,
+        Bounds::new(Vec2::ZERO, Vec2::ONE),
+        1.0,
+    )
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+#[test]
+fn keys_map_to_actions() {
+    assert_eq!(key_action(key(KeyCode::Up)), [Action::Move(0, 1)]);
+    assert_eq!(key_action(key(KeyCode::Char('j'))), [Action::Move(0, -1)]);
+    assert_eq!(key_action(key(KeyCode::Char('h'))), [Action::Move(-1, 0)]);
+    assert_eq!(key_action(key(KeyCode::Enter)), [Action::Trigger]);
+    assert_eq!(key_action(key(KeyCode::Char('f'))), [Action::Mark]);
+    assert_eq!(key_action(key(KeyCode::Char('c'))), [Action::Chord]);
+    assert_eq!(key_action(key(KeyCode::Char('u'))), [Action::Undo]);
+    assert_eq!(key_action(key(KeyCode::Char('y'))), [Action::Redo]);
+    assert_eq!(key_action(key(KeyCode::Char('r'))), [Action::Restart]);
+    assert_eq!(key_action(key(KeyCode::Esc)), [Action::Quit]);
+    assert_eq!(
+        key_action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        [Action::Quit]
+    );
+    assert!(key_action(key(KeyCode::Char('x'))).is_empty());
+
+    // Only presses and repeats act
+    let mut release = key(KeyCode::Enter);
+    release.kind = KeyEventKind::Release;
+    assert!(key_action(release).is_empty());
+}
+
+#[test]
+fn view_scrolls_to_the_cursor_and_clamps_it() {
+    let board = board(40, 30);
+    let mut view = BoardView::default();
+    // 10 tiles wide, 8 tiles high once the status and help lines are left out
+    let cursor = view.update(&board, (20, 10));
+    assert_eq!(cursor, Coordinates::new(20, 15));
+    assert_eq!(view.size, (10, 8));
+    assert_eq!((view.left, view.top), (11, 7));
+    // The top left cell shows the first visible column of the first visible row
+    assert_eq!(
+        view.tile_at(&board, 0, HEADER),
+        Some(Coordinates::new(11, 22))
+    );
+    assert_eq!(
+        view.tile_at(&board, 19, HEADER + 7),
+        Some(Coordinates::new(20, 15))
+    );
+    assert_eq!(view.tile_at(&board, 0, 0), None);
+    assert_eq!(view.tile_at(&board, 20, HEADER), None);
+
+    assert_eq!(view.apply(&board, Action::Move(-100, 100)), None);
+    assert_eq!(view.update(&board, (20, 10)), Coordinates::new(0, 29));
+    assert_eq!((view.left, view.top), (0, 0));
+    assert_eq!(
+        view.apply(&board, Action::Mark),
+        Some(Coordinates::new(0, 29))
+    );
+
+    // Cursors outside a smaller board go back to its centre
+    let small = self::board(4, 4);
+    assert_eq!(view.update(&small, (20, 10)), Coordinates::new(2, 2));
+    assert_eq!(view.size, (4, 4));
+    assert_eq!((view.left, view.top), (0, 0));
+}
+
+#[test]
+fn mouse_points_and_clicks_tiles() {
+    let board = board(4, 4);
+    let mut view = BoardView::default();
+    view.update(&board, (80, 24));
+    let tile = Coordinates::new(1, 3);
+
+    assert_eq!(
+        mouse_action(&view, &board, mouse(MouseEventKind::Moved, 2, HEADER)),
+        [Action::Point(tile)]
+    );
+    let click = |button| mouse(MouseEventKind::Down(button), 3, HEADER);
+    assert_eq!(
+        mouse_action(&view, &board, click(MouseButton::Left)),
+        [Action::Point(tile), Action::Trigger]
+    );
+    assert_eq!(
+        mouse_action(&view, &board, click(MouseButton::Right)),
+        [Action::Point(tile), Action::Mark]
+    );
+    assert_eq!(
+        mouse_action(&view, &board, click(MouseButton::Middle)),
+        [Action::Point(tile), Action::Chord]
+    );
+    // The status line and the cells right of the board hold no tile
+    assert!(mouse_action(&view, &board, mouse(MouseEventKind::Moved, 0, 0)).is_empty());
+    assert!(mouse_action(&view, &board, mouse(MouseEventKind::Moved, 8, HEADER)).is_empty());
+}
+
+#[test]
+fn tiles_are_drawn_from_the_board_state() {
+    let mut board = board(2, 1);
+    board
+        .marked_tiles
+        .insert(Coordinates::new(1, 0), Mark::Flag);
+    let status = BoardStatus::default();
+    assert_eq!(tile_cell(&board, &status, Coordinates::new(0, 0)).0, '#');
+    assert_eq!(tile_cell(&board, &status, Coordinates::new(1, 0)).0, 'F');
+    board.covered_tiles.clear();
+    assert_eq!(tile_cell(&board, &status, Coordinates::new(0, 0)).0, '.');
+}
+
+/// Headless app running a 3x3 board without bombs, every move being undoable
+fn board_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin {
+            add_primary_window: false,
+            ..WindowPlugin::default()
+        })
+        .add_plugin(InputPlugin)
+        .insert_resource(BoardOptions {
+            map_size: (3, 3),
+            bomb_count: 0,
+            undo: Some(UndoPolicy::Unlimited),
+            ..BoardOptions::default()
+        })
+        .insert_resource(AnimationOptions::disabled())
+        .insert_resource(BoardAssets::default())
+        .add_state(TestState::InGame)
+        .add_plugin(BoardPlugin {
+            running_state: TestState::InGame,
+        });
+    app.update();
+    app
+}
+
+#[test]
+fn scripted_actions_play_the_headless_app() {
+    let mut app = board_app();
+    let mut view = BoardView::default();
+    view.update(app.world.get_resource::<Board>().unwrap(), (80, 24));
+
+    for action in key_action(key(KeyCode::Left))
+        .into_iter()
+        .chain(key_action(key(KeyCode::Char(' '))))
+    {
+        assert!(apply::<TestState>(&mut app, &mut view, action));
+    }
+    let triggers: Vec<_> = app
+        .world
+        .get_resource_mut::<Events<TileTriggerEvent>>()
+        .unwrap()
+        .drain()
+        .map(|e| e.0)
+        .collect();
+    assert_eq!(triggers, [Coordinates::new(0, 1)]);
+
+    // The whole empty board opens up
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Trigger));
+    for _ in 0..3 {
+        app.update();
+    }
+    let status = app.world.get_resource::<BoardStatus>().unwrap();
+    assert_eq!(status.state, BoardState::Won);
+
+    // The winning move is taken back and played again
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Undo));
+    app.update();
+    let status = app.world.get_resource::<BoardStatus>().unwrap();
+    assert_eq!(status.state, BoardState::Playing);
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Redo));
+    app.update();
+    let status = app.world.get_resource::<BoardStatus>().unwrap();
+    assert_eq!(status.state, BoardState::Won);
+
+    // Tile actions are not sent once the game is over
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Mark));
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Chord));
+    let world = &mut app.world;
+    assert_eq!(
+        world
+            .resource_mut::<Events<TileMarkEvent>>()
+            .drain()
+            .count(),
+        0
+    );
+    assert_eq!(
+        world
+            .resource_mut::<Events<TileChordEvent>>()
+            .drain()
+            .count(),
+        0
+    );
+
+    assert!(apply::<TestState>(&mut app, &mut view, Action::Restart));
+    for _ in 0..2 {
+        app.update();
+    }
+    let board = app.world.get_resource::<Board>().unwrap();
+    assert_eq!(board.covered_tiles.len(), 9);
+    assert!(!apply::<TestState>(&mut app, &mut view, Action::Quit));
+}